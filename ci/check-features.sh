@@ -0,0 +1,31 @@
+#!/bin/sh
+# Checks that the library - including its tests and examples - builds with
+# representative combinations of features, as the `cfg` gating of optional
+# dependencies is easy to get wrong for combinations other than the default.
+set -ex
+
+# Enabling `reqwest` alone is intentionally a compile error pointing to
+# `reqwest-support`, so it isn't checked here.
+for features in \
+    "" \
+    "serde-items" \
+    "serde-items strict-models" \
+    "config" \
+    "keyring" \
+    "exif-strip" \
+    "hyper-support" \
+    "hyper-support config" \
+    "hyper-support dangerous-config" \
+    "reqwest-support" \
+    "reqwest-support config dangerous-config exif-strip keyring" \
+    "hyper-rustls" \
+    "reqwest-rustls" \
+    "reqwest-rustls config dangerous-config" \
+    "hyper-support reqwest-support" \
+    "hyper-rustls reqwest-rustls" \
+    "hyper-support reqwest-support backtrace"
+do
+    cargo check --all-targets --no-default-features --features "$features"
+done
+
+cargo check --all-targets --all-features
@@ -0,0 +1,63 @@
+extern crate owo;
+extern crate reqwest;
+
+use owo::OwoReqwestClient;
+use reqwest::Client;
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::thread;
+
+// You would want to handle results in a normal program.
+fn main() {
+    // Retrieve the key from an environment variable named "OWO_KEY".
+    let key = env::var("OWO_KEY")
+        .expect("Must provide OWO_KEY as an environment variable");
+
+    // Take the paths of the files to upload from the arguments.
+    let paths = env::args().skip(1).collect::<Vec<_>>();
+
+    if paths.is_empty() {
+        println!("Usage: 03_shared_client <path>...");
+
+        return;
+    }
+
+    // Create a single reqwest Client. Creating one sets up TLS and a
+    // connection pool, so it should be done once and then shared.
+    let http = Client::new();
+
+    // Create the owo client from it. The reqwest Client is cloned so it can
+    // still be used for other requests; both clones share one connection
+    // pool.
+    let owo = OwoReqwestClient::from_client(http.clone(), key)
+        .expect("Error creating client");
+
+    // Upload each file from its own thread. Cloning the owo client is cheap,
+    // and each clone reuses the connections of the same pool.
+    let handles = paths.into_iter().map(|path| {
+        let owo = owo.clone();
+
+        thread::spawn(move || {
+            let mut buffer = vec![];
+            File::open(&path)
+                .and_then(|mut file| file.read_to_end(&mut buffer))
+                .expect("Error reading file");
+
+            let response = owo.upload_file(buffer);
+
+            println!("{}: {:?}", path, response);
+        })
+    }).collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().expect("Error joining thread");
+    }
+
+    // The same reqwest Client can also be used directly.
+    let status = http.get(owo.base_url())
+        .send()
+        .map(|response| response.status());
+
+    println!("API status: {:?}", status);
+}
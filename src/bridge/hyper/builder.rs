@@ -0,0 +1,407 @@
+use hyper::client::{Client as HyperClient, HttpConnector};
+#[cfg(not(feature = "tokio-rustls"))]
+use hyper_tls::HttpsConnector;
+#[cfg(feature = "hyper-tls")]
+use native_tls::Protocol;
+#[cfg(not(feature = "tokio-rustls"))]
+use native_tls::TlsConnector;
+#[cfg(feature = "tokio-rustls")]
+use rustls::ClientConfig;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::rc::Rc;
+#[cfg(feature = "tokio-rustls")]
+use std::sync::Arc;
+use super::connector::ResolvingConnector;
+use super::{Inner, OwoClient, OwoConnector};
+use tokio_core::reactor::Handle;
+#[cfg(feature = "tokio-rustls")]
+use webpki_roots;
+use ::bridge::multipart;
+use ::secret::Secret;
+use ::{Error, Result, constants, endpoints};
+
+/// A builder for configuring an [`OwoClient`] before creating it.
+///
+/// The TLS and DNS options offered here are only available for the `hyper`
+/// bridge, as the version of `reqwest` in use does not expose them.
+///
+/// # Examples
+///
+/// Create a client which only speaks TLS 1.2 and only accepts a single pinned
+/// certificate:
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "hyper-tls")]
+/// extern crate native_tls;
+/// extern crate owo;
+/// extern crate tokio_core;
+/// # use std::error::Error;
+/// #
+/// # #[cfg(feature = "hyper-tls")]
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// #
+/// use native_tls::Protocol;
+/// use owo::OwoHyperClientBuilder;
+/// use std::env;
+/// use std::fs::File;
+/// use std::io::Read;
+/// use tokio_core::reactor::Core;
+///
+/// let mut certificate = vec![];
+/// File::open("./api.der")?.read_to_end(&mut certificate)?;
+///
+/// let core = Core::new()?;
+/// let client = OwoHyperClientBuilder::new(env::var("OWO_TOKEN")?)
+///     .min_tls_version(Protocol::Tlsv12)
+///     .pin_certificate(certificate)
+///     .build(&core.handle())?;
+/// #     Ok(())
+/// # }
+/// #
+/// # #[cfg(not(feature = "hyper-tls"))]
+/// # fn try_main() -> Result<(), Box<Error>> { Ok(()) }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// [`OwoClient`]: struct.OwoClient.html
+pub struct OwoClientBuilder {
+    allow_unsuccessful: bool,
+    base_url: Option<String>,
+    boundary: Option<String>,
+    key: Secret,
+    locale: Option<String>,
+    #[cfg(feature = "hyper-tls")]
+    min_tls_version: Option<Protocol>,
+    pinned_certificate: Option<Vec<u8>>,
+    resolve_overrides: HashMap<String, SocketAddr>,
+    shorten_path: Option<String>,
+    upload_path: Option<String>,
+}
+
+impl OwoClientBuilder {
+    /// Creates a new builder for a client using the given key.
+    ///
+    /// By default, this creates a client identical to one created via
+    /// [`OwoClient::new`].
+    ///
+    /// [`OwoClient::new`]: struct.OwoClient.html#method.new
+    pub fn new<S: Into<String>>(key: S) -> Self {
+        Self {
+            allow_unsuccessful: false,
+            base_url: None,
+            boundary: None,
+            key: Secret::new(key.into()),
+            locale: None,
+            #[cfg(feature = "hyper-tls")]
+            min_tls_version: None,
+            pinned_certificate: None,
+            resolve_overrides: HashMap::new(),
+            shorten_path: None,
+            upload_path: None,
+        }
+    }
+
+    /// Returns upload responses with `success: false` as they are, rather than
+    /// as an [`Error::Api`] or [`Error::ApiFailure`].
+    ///
+    /// Responses with an unsuccessful HTTP status are still returned as
+    /// errors. This only affects requests whose responses the client parses
+    /// itself, such as via [`OwoClient::upload_file_progress_stream`].
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::ApiFailure`]: ../../enum.Error.html#variant.ApiFailure
+    /// [`OwoClient::upload_file_progress_stream`]: struct.OwoClient.html#method.upload_file_progress_stream
+    pub fn allow_unsuccessful(mut self) -> Self {
+        self.allow_unsuccessful = true;
+
+        self
+    }
+
+    /// Sets the base URL of the API to make requests to, such as
+    /// `"https://api.example.com"` for a self-hosted instance of the service.
+    ///
+    /// If not set, the `OWO_API_URL` environment variable is used if present,
+    /// such as to redirect an integration environment to a staging instance
+    /// without code changes. Otherwise, defaults to [`constants::API_URL`].
+    ///
+    /// # Errors
+    ///
+    /// [`build`] returns [`Error::Config`] if the base URL - or the
+    /// environment variable - is not an absolute `http` or `https` URL.
+    ///
+    /// [`Error::Config`]: ../../enum.Error.html#variant.Config
+    /// [`build`]: #method.build
+    /// [`constants::API_URL`]: ../../constants/constant.API_URL.html
+    pub fn base_url<S: Into<String>>(mut self, base_url: S) -> Self {
+        self.base_url = Some(base_url.into());
+
+        self
+    }
+
+    /// Sets a fixed boundary to separate the parts of multipart upload bodies
+    /// with.
+    ///
+    /// This is useful for producing reproducible request bodies in tests, or
+    /// for services which are picky about the format of boundaries.
+    ///
+    /// By default, a unique boundary is generated for each upload.
+    ///
+    /// # Errors
+    ///
+    /// [`build`] returns [`Error::InvalidBoundary`] if the boundary is not
+    /// valid per RFC 2046.
+    ///
+    /// [`Error::InvalidBoundary`]: ../../enum.Error.html#variant.InvalidBoundary
+    /// [`build`]: #method.build
+    pub fn boundary<S: Into<String>>(mut self, boundary: S) -> Self {
+        self.boundary = Some(boundary.into());
+
+        self
+    }
+
+    /// Sets the locale to request responses in, sent as the `Accept-Language`
+    /// header of every request.
+    ///
+    /// Some services localize their error descriptions, so setting this - for
+    /// example to `"en"` - keeps them consistent regardless of the server's
+    /// locale.
+    ///
+    /// By default, no `Accept-Language` header is sent.
+    pub fn locale<S: Into<String>>(mut self, locale: S) -> Self {
+        self.locale = Some(locale.into());
+
+        self
+    }
+
+    /// Sets the minimum TLS protocol version to allow when connecting.
+    ///
+    /// By default, the platform's TLS defaults are used.
+    ///
+    /// This has no effect if the `hyper-rustls` feature is also enabled, as
+    /// `rustls` only supports TLS 1.2 and later.
+    #[cfg(feature = "hyper-tls")]
+    pub fn min_tls_version(mut self, version: Protocol) -> Self {
+        self.min_tls_version = Some(version);
+
+        self
+    }
+
+    /// Pins the certificate that the service must present, in DER form.
+    ///
+    /// Connections to a server presenting any other certificate - or no
+    /// certificate at all - are rejected. The resultant `hyper::Error::Io`
+    /// wraps an [`Error::CertificatePinMismatch`], which can be retrieved via
+    /// `std::io::Error::get_ref`.
+    ///
+    /// [`Error::CertificatePinMismatch`]: ../../enum.Error.html#variant.CertificatePinMismatch
+    pub fn pin_certificate(mut self, der: Vec<u8>) -> Self {
+        self.pinned_certificate = Some(der);
+
+        self
+    }
+
+    /// Forces connections to the given host to be made to the given address,
+    /// rather than to an address resolved via DNS.
+    ///
+    /// The port of the address is used as-is. This can be called multiple
+    /// times to override multiple hosts; hosts which are not overridden are
+    /// resolved as normal. TLS is still negotiated and verified against the
+    /// host, not the address.
+    ///
+    /// # Examples
+    ///
+    /// Pin the API host to an allow-listed address:
+    ///
+    /// ```rust,no_run
+    /// extern crate owo;
+    /// extern crate tokio_core;
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::OwoHyperClientBuilder;
+    /// use std::env;
+    /// use tokio_core::reactor::Core;
+    ///
+    /// let core = Core::new()?;
+    /// let client = OwoHyperClientBuilder::new(env::var("OWO_TOKEN")?)
+    ///     .resolve("api.awau.moe", "203.0.113.7:443".parse()?)
+    ///     .build(&core.handle())?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn resolve<S: Into<String>>(mut self, host: S, addr: SocketAddr)
+        -> Self {
+        self.resolve_overrides.insert(host.into(), addr);
+
+        self
+    }
+
+    /// Sets the path of the endpoint to shorten URLs, relative to the base
+    /// URL, for instances which do not use the official service's path.
+    ///
+    /// Defaults to [`constants::SHORTEN_PATH`].
+    ///
+    /// # Errors
+    ///
+    /// [`build`] returns [`Error::Config`] if the path contains a query
+    /// string, a fragment, or whitespace.
+    ///
+    /// [`Error::Config`]: ../../enum.Error.html#variant.Config
+    /// [`build`]: #method.build
+    /// [`constants::SHORTEN_PATH`]: ../../constants/constant.SHORTEN_PATH.html
+    pub fn shorten_path<S: Into<String>>(mut self, path: S) -> Self {
+        self.shorten_path = Some(path.into());
+
+        self
+    }
+
+    /// Sets the path of the endpoint to upload files, relative to the base
+    /// URL, for instances which do not use the official service's path.
+    ///
+    /// Defaults to [`constants::UPLOAD_PATH`].
+    ///
+    /// # Errors
+    ///
+    /// [`build`] returns [`Error::Config`] if the path contains a query
+    /// string, a fragment, or whitespace.
+    ///
+    /// [`Error::Config`]: ../../enum.Error.html#variant.Config
+    /// [`build`]: #method.build
+    /// [`constants::UPLOAD_PATH`]: ../../constants/constant.UPLOAD_PATH.html
+    pub fn upload_path<S: Into<String>>(mut self, path: S) -> Self {
+        self.upload_path = Some(path.into());
+
+        self
+    }
+
+    /// Builds the client, spawning its connections on the given handle.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming
+    /// surrounding whitespace.
+    ///
+    /// Returns [`Error::InvalidBoundary`] if a configured multipart boundary is
+    /// invalid.
+    ///
+    /// Returns [`Error::Config`] if the base URL or an endpoint path is
+    /// invalid.
+    ///
+    /// Returns [`Error::NativeTls`] if there was an error instantiating the
+    /// `native-tls` connector, such as if the platform does not support the
+    /// requested minimum TLS version.
+    ///
+    /// [`Error::Config`]: ../../enum.Error.html#variant.Config
+    /// [`Error::InvalidBoundary`]: ../../enum.Error.html#variant.InvalidBoundary
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::NativeTls`]: ../../enum.Error.html#variant.NativeTls
+    pub fn build(self, handle: &Handle) -> Result<OwoClient> {
+        let key = Secret::key(self.key.as_str().to_owned())?;
+
+        if let Some(boundary) = self.boundary.as_ref() {
+            if !multipart::is_valid_boundary(boundary) {
+                return Err(Error::InvalidBoundary(boundary.clone()));
+            }
+        }
+
+        let base_url = endpoints::base_url(self.base_url)?;
+        let shorten_path = endpoints::path(
+            "shorten_path",
+            self.shorten_path,
+            constants::SHORTEN_PATH,
+        )?;
+        let upload_path = endpoints::path(
+            "upload_path",
+            self.upload_path,
+            constants::UPLOAD_PATH,
+        )?;
+
+        let mut http = HttpConnector::new(4, handle);
+        http.enforce_http(false);
+        let http = ResolvingConnector::new(
+            http,
+            self.resolve_overrides,
+            handle,
+        );
+
+        #[cfg(not(feature = "tokio-rustls"))]
+        let connector = {
+            let mut tls = TlsConnector::builder()?;
+
+            if let Some(version) = self.min_tls_version {
+                tls.supported_protocols(protocols_from(version))?;
+            }
+
+            let https = HttpsConnector::from((http, tls.build()?));
+
+            OwoConnector::new(https, self.pinned_certificate)
+        };
+
+        #[cfg(feature = "tokio-rustls")]
+        let connector = {
+            // `rustls` only supports TLS 1.2 and later, which satisfies any
+            // minimum version.
+            #[cfg(feature = "hyper-tls")]
+            let _ = self.min_tls_version;
+
+            let mut tls = ClientConfig::new();
+            tls.root_store
+                .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+
+            OwoConnector::new(
+                http,
+                Arc::new(tls).into(),
+                self.pinned_certificate,
+            )
+        };
+
+        let client = HyperClient::configure()
+            .connector(connector)
+            .build(handle);
+
+        let inner = Inner {
+            allow_unsuccessful: self.allow_unsuccessful,
+            base_url,
+            boundary: self.boundary,
+            client,
+            handle: handle.clone(),
+            locale: self.locale,
+            shorten_path,
+            upload_path,
+        };
+
+        Ok(OwoClient {
+            inner: Rc::new(inner),
+            key,
+        })
+    }
+}
+
+/// Produces the list of protocols with a version of at least the given one.
+#[cfg(not(feature = "tokio-rustls"))]
+fn protocols_from(min: Protocol) -> &'static [Protocol] {
+    match min {
+        Protocol::Sslv3 => &[
+            Protocol::Sslv3,
+            Protocol::Tlsv10,
+            Protocol::Tlsv11,
+            Protocol::Tlsv12,
+        ],
+        Protocol::Tlsv10 => &[
+            Protocol::Tlsv10,
+            Protocol::Tlsv11,
+            Protocol::Tlsv12,
+        ],
+        Protocol::Tlsv11 => &[Protocol::Tlsv11, Protocol::Tlsv12],
+        _ => &[Protocol::Tlsv12],
+    }
+}
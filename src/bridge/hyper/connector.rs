@@ -0,0 +1,263 @@
+use futures::Future;
+#[cfg(feature = "tokio-rustls")]
+use futures::future;
+use hyper::client::HttpConnector;
+use hyper::Uri;
+#[cfg(not(feature = "tokio-rustls"))]
+use hyper_tls::{HttpsConnector, MaybeHttpsStream};
+#[cfg(not(feature = "tokio-rustls"))]
+use native_tls::TlsStream;
+#[cfg(feature = "tokio-rustls")]
+use rustls::{ClientSession, Session};
+use std::collections::HashMap;
+use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+use std::net::SocketAddr;
+use std::sync::Arc;
+#[cfg(feature = "tokio-rustls")]
+use super::MaybeHttpsStream;
+use tokio_core::net::TcpStream;
+use tokio_core::reactor::Handle;
+#[cfg(feature = "tokio-rustls")]
+use tokio_rustls::TlsConnector;
+use tokio_service::Service;
+#[cfg(feature = "tokio-rustls")]
+use webpki::DNSNameRef;
+use ::error::ConnectError;
+use ::{Error, NetworkErrorKind};
+
+/// An HTTPS connector used by the [`OwoClient`], wrapping a `hyper-tls`
+/// connector, or a `rustls` connector with the `hyper-rustls` feature.
+///
+/// This enforces the options configured via the [`OwoClientBuilder`] that
+/// `hyper` and the TLS connectors do not offer themselves, such as
+/// certificate pinning and DNS resolution overrides.
+///
+/// [`OwoClient`]: struct.OwoClient.html
+/// [`OwoClientBuilder`]: struct.OwoClientBuilder.html
+#[derive(Clone)]
+pub struct OwoConnector {
+    #[cfg(not(feature = "tokio-rustls"))]
+    https: HttpsConnector<ResolvingConnector>,
+    #[cfg(feature = "tokio-rustls")]
+    http: ResolvingConnector,
+    #[cfg(feature = "tokio-rustls")]
+    tls: TlsConnector,
+    pinned_certificate: Option<Arc<Vec<u8>>>,
+}
+
+impl OwoConnector {
+    #[cfg(not(feature = "tokio-rustls"))]
+    pub(crate) fn new(
+        https: HttpsConnector<ResolvingConnector>,
+        pinned_certificate: Option<Vec<u8>>,
+    ) -> Self {
+        Self {
+            pinned_certificate: pinned_certificate.map(Arc::new),
+            https,
+        }
+    }
+
+    #[cfg(feature = "tokio-rustls")]
+    pub(crate) fn new(
+        http: ResolvingConnector,
+        tls: TlsConnector,
+        pinned_certificate: Option<Vec<u8>>,
+    ) -> Self {
+        Self {
+            pinned_certificate: pinned_certificate.map(Arc::new),
+            http,
+            tls,
+        }
+    }
+
+    /// Connects to the host of the URI, negotiating TLS for `https` URIs.
+    #[cfg(not(feature = "tokio-rustls"))]
+    fn connect(&self, uri: Uri) -> <Self as Service>::Future {
+        Box::new(self.https.call(uri))
+    }
+
+    /// Connects to the host of the URI, negotiating TLS for `https` URIs.
+    #[cfg(feature = "tokio-rustls")]
+    fn connect(&self, uri: Uri) -> <Self as Service>::Future {
+        if uri.scheme() != Some("https") {
+            return Box::new(self.http.call(uri).map(MaybeHttpsStream::Http));
+        }
+
+        // The name is checked before connecting, as TLS can't be negotiated
+        // without a valid one.
+        let domain = match uri.host().map(DNSNameRef::try_from_ascii_str) {
+            Some(Ok(domain)) => domain.to_owned(),
+            _ => {
+                let why = IoError::new(
+                    IoErrorKind::InvalidInput,
+                    "the host is not a valid DNS name",
+                );
+
+                return Box::new(future::err(why));
+            },
+        };
+        let tls = self.tls.clone();
+
+        Box::new(self.http.call(uri).and_then(move |stream| {
+            tls.connect(domain.as_ref(), stream)
+                .map(|stream| MaybeHttpsStream::Https(Box::new(stream)))
+        }))
+    }
+}
+
+impl Service for OwoConnector {
+    type Request = Uri;
+    type Response = MaybeHttpsStream<TcpStream>;
+    type Error = IoError;
+    type Future = Box<dyn Future<Item = Self::Response, Error = IoError>>;
+
+    fn call(&self, uri: Uri) -> Self::Future {
+        let connecting = self.connect(uri);
+
+        let pinned = match self.pinned_certificate {
+            Some(ref pinned) => Arc::clone(pinned),
+            None => return connecting,
+        };
+
+        Box::new(connecting.and_then(move |stream| {
+            verify_pin(&stream, &pinned)?;
+
+            Ok(stream)
+        }))
+    }
+}
+
+/// An HTTP connector which connects to fixed addresses for some hosts instead
+/// of resolving them, falling back to regular resolution for all other hosts.
+///
+/// As this sits beneath the TLS layer, TLS is still negotiated with the
+/// original hostname. Errors which can't be classified by their kind are
+/// wrapped in a `ConnectError`, so that they're still known to be from
+/// connecting.
+#[derive(Clone)]
+pub(crate) struct ResolvingConnector {
+    handle: Handle,
+    http: HttpConnector,
+    overrides: Arc<HashMap<String, SocketAddr>>,
+}
+
+impl ResolvingConnector {
+    pub(crate) fn new(
+        http: HttpConnector,
+        overrides: HashMap<String, SocketAddr>,
+        handle: &Handle,
+    ) -> Self {
+        Self {
+            handle: handle.clone(),
+            overrides: Arc::new(overrides),
+            http,
+        }
+    }
+}
+
+impl Service for ResolvingConnector {
+    type Request = Uri;
+    type Response = TcpStream;
+    type Error = IoError;
+    type Future = Box<dyn Future<Item = TcpStream, Error = IoError>>;
+
+    fn call(&self, uri: Uri) -> Self::Future {
+        let addr = uri.host().and_then(|host| self.overrides.get(host));
+
+        match addr {
+            Some(addr) => Box::new(
+                TcpStream::connect(addr, &self.handle).map_err(connect_error),
+            ),
+            None => Box::new(self.http.call(uri).map_err(connect_error)),
+        }
+    }
+}
+
+/// Wraps an error from connecting in a `ConnectError` if it can't be
+/// classified by its kind, such as when the host name failed to resolve.
+fn connect_error(why: IoError) -> IoError {
+    match NetworkErrorKind::from_io(&why) {
+        NetworkErrorKind::Other => IoError::new(why.kind(), ConnectError(why)),
+        _ => why,
+    }
+}
+
+/// Verifies that the certificate presented by the peer is byte-for-byte the
+/// pinned DER-encoded certificate.
+fn verify_pin(stream: &MaybeHttpsStream<TcpStream>, pinned: &[u8])
+    -> Result<(), IoError> {
+    let tls = match *stream {
+        MaybeHttpsStream::Https(ref tls) => tls,
+        // A plain HTTP connection has no certificate to match against, which
+        // is as good as a mismatch.
+        MaybeHttpsStream::Http(_) => return Err(pin_mismatch()),
+    };
+
+    let der = peer_certificate_der(tls.get_ref())?
+        .ok_or_else(pin_mismatch)?;
+
+    if der.as_slice() == pinned {
+        Ok(())
+    } else {
+        Err(pin_mismatch())
+    }
+}
+
+/// Retrieves the DER encoding of the certificate presented by the peer.
+#[cfg(feature = "tokio-rustls")]
+fn peer_certificate_der((_, session): (&TcpStream, &ClientSession))
+    -> Result<Option<Vec<u8>>, IoError> {
+    let certificates = session.get_peer_certificates().unwrap_or_default();
+
+    Ok(certificates.into_iter().next().map(|certificate| certificate.0))
+}
+
+/// Retrieves the DER encoding of the certificate presented by the peer.
+///
+/// `native-tls` has no portable way of doing this, so each of its backends is
+/// handled on its own.
+#[cfg(all(
+    not(feature = "tokio-rustls"),
+    not(any(target_os = "macos", target_os = "windows", target_os = "ios")),
+))]
+fn peer_certificate_der(stream: &TlsStream<TcpStream>)
+    -> Result<Option<Vec<u8>>, IoError> {
+    use native_tls::backend::openssl::TlsStreamExt;
+
+    match stream.raw_stream().ssl().peer_certificate() {
+        Some(certificate) => certificate.to_der()
+            .map(Some)
+            .map_err(|why| IoError::new(IoErrorKind::Other, why)),
+        None => Ok(None),
+    }
+}
+
+#[cfg(all(
+    not(feature = "tokio-rustls"),
+    any(target_os = "macos", target_os = "ios"),
+))]
+fn peer_certificate_der(stream: &TlsStream<TcpStream>)
+    -> Result<Option<Vec<u8>>, IoError> {
+    use native_tls::backend::security_framework::TlsStreamExt;
+
+    let trust = stream.raw_stream()
+        .context()
+        .peer_trust()
+        .map_err(|why| IoError::new(IoErrorKind::Other, why))?;
+
+    Ok(trust.certificate_at_index(0).map(|certificate| certificate.to_der()))
+}
+
+#[cfg(all(not(feature = "tokio-rustls"), target_os = "windows"))]
+fn peer_certificate_der(stream: &TlsStream<TcpStream>)
+    -> Result<Option<Vec<u8>>, IoError> {
+    use native_tls::backend::schannel::TlsStreamExt;
+
+    let certificate = stream.raw_stream().peer_certificate()?;
+
+    Ok(Some(certificate.to_der().to_vec()))
+}
+
+fn pin_mismatch() -> IoError {
+    IoError::new(IoErrorKind::InvalidData, Error::CertificatePinMismatch)
+}
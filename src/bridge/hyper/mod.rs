@@ -8,13 +8,90 @@
 
 pub mod oneshot;
 
-use hyper::client::{Client as HyperClient, FutureResponse, HttpConnector};
+mod builder;
+mod connector;
+#[cfg(feature = "tokio-rustls")]
+mod tls_stream;
+
+pub use self::builder::OwoClientBuilder;
+pub use self::connector::OwoConnector;
+#[cfg(feature = "tokio-rustls")]
+pub use self::tls_stream::MaybeHttpsStream;
+
+#[cfg(feature = "serde_json")]
+use futures::sync::mpsc;
+use futures::future::Either;
+use futures::{Future, Sink, Stream, stream};
+use hyper::client::{Client as HyperClient, Connect, FutureResponse};
 use hyper::header::UserAgent;
-use hyper::{Body, Method, Request, Uri};
-use hyper_tls::HttpsConnector;
+use hyper::{
+    Body,
+    Chunk,
+    Error as HyperError,
+    Method,
+    Request,
+    Uri,
+};
+#[cfg(feature = "serde_json")]
+use hyper::{Headers, StatusCode};
+#[cfg(feature = "serde_json")]
+use serde_json;
+#[cfg(feature = "serde_json")]
+use std::cmp;
+use std::io::Error as IoError;
+#[cfg(feature = "serde_json")]
+use std::io::ErrorKind as IoErrorKind;
+use std::rc::Rc;
+#[cfg(feature = "serde_json")]
+use std::str;
 use std::str::FromStr;
-use tokio_core::reactor::Handle;
-use ::{constants, Result};
+use std::time::Duration;
+use super::multipart::Multipart;
+use tokio_codec::{BytesCodec, FramedRead};
+use tokio_core::reactor::{Handle, Timeout};
+use tokio_io::AsyncRead;
+#[cfg(feature = "serde_json")]
+use ::bridge::response::{
+    body_error,
+    html_response,
+    snippet,
+    status_error,
+};
+#[cfg(feature = "serde_json")]
+use ::model::{FileUploadResponse, Progress, UploadEvent};
+use ::secret::Secret;
+use ::{constants, endpoints, Error, Result};
+
+/// The size of the pieces a file is sent in by
+/// [`OwoClient::upload_file_progress_stream`], in bytes.
+///
+/// [`OwoClient::upload_file_progress_stream`]: struct.OwoClient.html#method.upload_file_progress_stream
+#[cfg(feature = "serde_json")]
+const PROGRESS_CHUNK_LEN: usize = 64 * 1024;
+
+/// A future bounded by a timeout, as returned by
+/// [`OwoClient::with_operation_timeout`].
+///
+/// [`OwoClient::with_operation_timeout`]: struct.OwoClient.html#method.with_operation_timeout
+pub type OperationFuture<T> = Box<dyn Future<Item = T, Error = Error>>;
+
+/// A stream of the events of a file upload, ending with the response of the
+/// service.
+#[cfg(feature = "serde_json")]
+pub type ProgressStream = Box<dyn Stream<Item = UploadEvent, Error = Error>>;
+
+/// A stream of the results of uploading a batch of files, as returned by
+/// [`OwoClient::upload_files_stream`].
+///
+/// Results are yielded in the order in which the uploads complete, not the
+/// order in which the files were given. The stream itself never fails.
+///
+/// [`OwoClient::upload_files_stream`]: struct.OwoClient.html#method.upload_files_stream
+#[cfg(feature = "serde_json")]
+pub type UploadStream = Box<dyn Stream<
+    Item = Result<FileUploadResponse>,
+    Error = Error,
+>>;
 
 /// A light wrapper around a hyper Client, containing the client and the key to
 /// use in requests.
@@ -24,13 +101,41 @@ use ::{constants, Result};
 /// the best performance on memory, manage your own hyper Client for re-use
 /// across multiple services.
 ///
+/// To configure the client, such as its TLS options, use an
+/// [`OwoClientBuilder`].
+///
+/// Cloning the client is cheap, as clones share the same underlying hyper
+/// Client - and so the same connection pool - and the rest of the client's
+/// configuration, only copying the key. This makes it suitable for handing to
+/// multiple futures running on the same reactor.
+///
 /// Refer to [`OwoRequester`] for more information.
 ///
+/// [`OwoClientBuilder`]: struct.OwoClientBuilder.html
 /// [`OwoRequester`]: trait.OwoRequester.html
+#[derive(Clone)]
 pub struct OwoClient {
-    client: HyperClient<HttpsConnector<HttpConnector>, Body>,
-    /// The key in use by the client.
-    pub key: String,
+    inner: Rc<Inner>,
+    key: Secret,
+}
+
+/// The configuration of a client, shared between its clones so that cloning
+/// stays cheap however much configuration there is.
+///
+/// The key is kept outside of this, as clones may use other keys. This is
+/// reference counted with an `Rc` rather than an `Arc`, as the client is tied
+/// to the thread of its reactor's `Handle` regardless.
+struct Inner {
+    // Only read when parsing upload responses, which requires `serde_json`.
+    #[cfg_attr(not(feature = "serde_json"), allow(dead_code))]
+    allow_unsuccessful: bool,
+    base_url: String,
+    boundary: Option<String>,
+    client: HyperClient<OwoConnector, Body>,
+    handle: Handle,
+    locale: Option<String>,
+    shorten_path: String,
+    upload_path: String,
 }
 
 impl OwoClient {
@@ -62,22 +167,386 @@ impl OwoClient {
     ///
     /// # Errors
     ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming
+    /// surrounding whitespace.
+    ///
+    /// Returns [`Error::Config`] if the `OWO_API_URL` environment variable is
+    /// set, but is not an absolute `http` or `https` URL.
+    ///
     /// Returns [`Error::NativeTls`] if there was an error instantiating the
-    /// HTTPS connector.
+    /// `native-tls` connector.
     ///
+    /// [`Error::Config`]: ../../enum.Error.html#variant.Config
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
     /// [`Error::NativeTls`]: ../../enum.Error.html#variant.NativeTls
     pub fn new<S: Into<String>>(key: S, handle: &Handle) -> Result<Self> {
-        let connector = HttpsConnector::new(4, handle)?;
-        let client = HyperClient::configure()
-            .connector(connector)
-            .build(handle);
+        OwoClientBuilder::new(key).build(handle)
+    }
+
+    /// Retrieves the key in use by the client.
+    pub fn key(&self) -> &str {
+        self.key.as_str()
+    }
+
+    /// Retrieves the base URL of the API which requests are made to.
+    ///
+    /// Defaults to [`constants::API_URL`].
+    ///
+    /// [`constants::API_URL`]: ../../constants/constant.API_URL.html
+    pub fn base_url(&self) -> &str {
+        &self.inner.base_url
+    }
+
+    /// Retrieves the fixed boundary used to separate the parts of multipart
+    /// upload bodies, if one was configured.
+    ///
+    /// If none was configured, a unique boundary is generated for each upload.
+    pub fn boundary(&self) -> Option<&str> {
+        self.inner.boundary.as_deref()
+    }
+
+    /// Replaces the key in use by the client.
+    ///
+    /// The key is trimmed of surrounding whitespace. With the `zeroize`
+    /// feature enabled, the old key is wiped from memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty, in which case the
+    /// old key is kept.
+    ///
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    pub fn set_key<S: Into<String>>(&mut self, key: S) -> Result<()> {
+        self.key = Secret::key(key.into())?;
 
+        Ok(())
+    }
+
+    /// Creates a new client which shares this client's underlying hyper
+    /// Client - and so its connection pool - but uses a different key.
+    ///
+    /// This is useful when making requests on behalf of many users, each with
+    /// their own key, as a single pool of connections can serve all of them.
+    ///
+    /// The key is trimmed of surrounding whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use owo::OwoHyperClient;
+    /// #
+    /// # fn tenants(client: OwoHyperClient) -> owo::Result<()> {
+    /// let alice = client.clone_with_key("alice's key")?;
+    /// let bob = client.clone_with_key("bob's key")?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty.
+    ///
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    pub fn clone_with_key<S: Into<String>>(&self, key: S) -> Result<Self> {
         Ok(Self {
-            key: key.into(),
-            client,
+            inner: Rc::clone(&self.inner),
+            key: Secret::key(key.into())?,
         })
     }
 
+    /// Shortcut for uploading a file read from an `AsyncRead`.
+    ///
+    /// Refer to [`OwoRequester::upload_async_reader`] for more information.
+    ///
+    /// [`OwoRequester::upload_async_reader`]: trait.OwoRequester.html#tymethod.upload_async_reader
+    #[must_use = "the request is not made until the future is polled"]
+    pub fn upload_async_reader<R: AsyncRead + 'static>(&self, reader: R)
+        -> Result<FutureResponse> {
+        let uri = endpoints::upload_at(
+            &self.inner.base_url,
+            &self.inner.upload_path,
+            self.key(),
+        );
+        let request = upload_request(
+            &Secret::new(uri),
+            reader,
+            self.boundary(),
+            &self.inner.handle,
+        )?;
+
+        Ok(self.inner.client.request(self.prepare(request)))
+    }
+
+    /// Uploads a file, yielding the progress of the upload as it is sent.
+    ///
+    /// The returned stream yields an [`UploadEvent::Progress`] each time
+    /// another piece of the file is handed to hyper to send, followed by a
+    /// single [`UploadEvent::Complete`] containing the parsed response. This
+    /// suits interfaces which poll streams, such as to drive a progress bar
+    /// while selecting on other events.
+    ///
+    /// Unlike the other methods of the client, the request is run on the
+    /// client's reactor as soon as this is called, rather than when the
+    /// stream is polled. The stream only needs to be polled to receive the
+    /// events.
+    ///
+    /// This is only available with the `serde-items` feature enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// extern crate futures;
+    /// extern crate owo;
+    /// extern crate tokio_core;
+    ///
+    /// use futures::Stream;
+    /// use owo::model::UploadEvent;
+    /// use owo::OwoHyperClient;
+    /// use std::env;
+    /// use tokio_core::reactor::Core;
+    ///
+    /// let mut core = Core::new()?;
+    /// let key = env::var("OWO_TOKEN")?;
+    /// let client = OwoHyperClient::new(key, &core.handle())?;
+    ///
+    /// let file = std::fs::read("./my-file.png")?;
+    ///
+    /// let runner = client.upload_file_progress_stream(file)?
+    ///     .for_each(|event| {
+    ///         match event {
+    ///             UploadEvent::Progress(progress) => {
+    ///                 println!("Sent {}/{}", progress.sent, progress.total);
+    ///             },
+    ///             UploadEvent::Complete(response) => {
+    ///                 println!("Uploaded: {}", response[0].full_url());
+    ///             },
+    ///         }
+    ///
+    ///         Ok(())
+    ///     });
+    ///
+    /// core.run(runner)?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Uri`] if the request URI could not be built.
+    ///
+    /// The stream fails with [`Error::Hyper`] if the request could not be
+    /// made, [`Error::Status`] if the service did not respond successfully,
+    /// or [`Error::InvalidJson`] if the response could not be parsed.
+    ///
+    /// The stream fails with [`Error::Api`] if the service rejected the
+    /// upload, or [`Error::ApiFailure`] if it responded with `success: false`
+    /// without describing the error and the client was not built to
+    /// [allow unsuccessful responses].
+    ///
+    /// As with the reqwest bridge, the stream fails with
+    /// [`Error::InvalidKey`], [`Error::PayloadTooLarge`] or
+    /// [`Error::RateLimited`] if the response's status indicates so, and with
+    /// [`Error::ServiceUnavailable`] rather than [`Error::Status`] if an
+    /// unsuccessful response is an HTML page.
+    ///
+    /// [allow unsuccessful responses]: struct.OwoClientBuilder.html#method.allow_unsuccessful
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::ApiFailure`]: ../../enum.Error.html#variant.ApiFailure
+    /// [`Error::Hyper`]: ../../enum.Error.html#variant.Hyper
+    /// [`Error::InvalidJson`]: ../../enum.Error.html#variant.InvalidJson
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::PayloadTooLarge`]: ../../enum.Error.html#variant.PayloadTooLarge
+    /// [`Error::RateLimited`]: ../../enum.Error.html#variant.RateLimited
+    /// [`Error::ServiceUnavailable`]: ../../enum.Error.html#variant.ServiceUnavailable
+    /// [`Error::Status`]: ../../enum.Error.html#variant.Status
+    /// [`Error::Uri`]: ../../enum.Error.html#variant.Uri
+    /// [`UploadEvent::Complete`]: ../../model/enum.UploadEvent.html#variant.Complete
+    /// [`UploadEvent::Progress`]: ../../model/enum.UploadEvent.html#variant.Progress
+    #[cfg(feature = "serde_json")]
+    pub fn upload_file_progress_stream(&self, file: Vec<u8>)
+        -> Result<ProgressStream> {
+        let uri = Secret::new(endpoints::upload_at(
+            &self.inner.base_url,
+            &self.inner.upload_path,
+            self.key(),
+        ));
+        let uri = Uri::from_str(uri.as_str())?;
+        let total = file.len() as u64;
+
+        // Each piece of the body is paired with the number of bytes of the
+        // file it contains, so that the multipart framing isn't counted.
+        let multipart = Multipart::with_boundary(self.boundary());
+        let mut end = multipart.file_end().to_vec();
+        end.extend(multipart.finish());
+
+        let mut pieces = vec![(multipart.file_start("files[]", None), 0)];
+        pieces.extend(file.chunks(PROGRESS_CHUNK_LEN).map(|chunk| {
+            (chunk.to_vec(), chunk.len() as u64)
+        }));
+        pieces.push((end, 0));
+
+        let (progress_tx, progress_rx) = mpsc::unbounded();
+        let (sender, body) = Body::pair();
+
+        let feed = stream::iter_ok::<_, ()>(pieces)
+            .fold((sender, 0), move |(sender, sent), (piece, len)| {
+                let progress_tx = progress_tx.clone();
+
+                sender.send(Ok(Chunk::from(piece)))
+                    .map_err(|_| ())
+                    .map(move |sender| {
+                        let sent = sent + len;
+
+                        if len > 0 {
+                            let progress = Progress { sent, total };
+                            let _ = progress_tx.unbounded_send(progress);
+                        }
+
+                        (sender, sent)
+                    })
+            })
+            .map(|_| ());
+        self.inner.handle.spawn(feed);
+
+        let mut request = Request::new(Method::Post, uri);
+        request.headers_mut().set(UserAgent::new(constants::USER_AGENT));
+        request.headers_mut().set_raw("Content-Type", multipart.content_type());
+        request.set_body(body);
+
+        let (response_tx, response_rx) = ::futures::sync::oneshot::channel();
+        let response = self.request_upload(request)
+            .then(move |result| {
+                let _ = response_tx.send(result);
+
+                Ok(())
+            });
+        self.inner.handle.spawn(response);
+
+        // The reactor dropping the spawned request is the only way for the
+        // channels to be closed early.
+        let complete = response_rx
+            .map_err(|_| dropped())
+            .and_then(|result| result)
+            .map(UploadEvent::Complete)
+            .into_stream();
+        let events = progress_rx
+            .map(UploadEvent::Progress)
+            .map_err(|_| dropped())
+            .chain(complete);
+
+        Ok(Box::new(events))
+    }
+
+    /// Uploads multiple files at once, yielding the result of each upload as
+    /// soon as it completes.
+    ///
+    /// Each file is uploaded in its own request, with at most `concurrency`
+    /// of the requests running at a time. This suits interfaces which show
+    /// each file's link as soon as it is available, rather than after the
+    /// whole batch.
+    ///
+    /// Limiting the concurrency avoids overwhelming the service - and
+    /// tripping its rate limits - when uploading many files. A `concurrency`
+    /// of `1` uploads the files one-by-one. A `concurrency` of `0` is treated
+    /// as `1`.
+    ///
+    /// Results are yielded in the order in which the uploads complete, which
+    /// is **not** necessarily the order in which the files were given. The
+    /// failure of one upload is yielded as an `Err` item, without ending the
+    /// stream or affecting the other uploads.
+    ///
+    /// This is only available with the `serde-items` feature enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// extern crate futures;
+    /// extern crate owo;
+    /// extern crate tokio_core;
+    ///
+    /// use futures::Stream;
+    /// use owo::OwoHyperClient;
+    /// use std::{env, fs};
+    /// use tokio_core::reactor::Core;
+    ///
+    /// let mut core = Core::new()?;
+    /// let key = env::var("OWO_TOKEN")?;
+    /// let client = OwoHyperClient::new(key, &core.handle())?;
+    ///
+    /// let files = vec![fs::read("./a.png")?, fs::read("./b.png")?];
+    ///
+    /// let runner = client.upload_files_stream(files, 2)?.for_each(|result| {
+    ///     match result {
+    ///         Ok(res) => println!("Uploaded: {}", res[0].full_url()),
+    ///         Err(why) => println!("Failed: {}", why),
+    ///     }
+    ///
+    ///     Ok(())
+    /// });
+    ///
+    /// core.run(runner)?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Uri`] if the request URI could not be built. No
+    /// requests are made in this case.
+    ///
+    /// Each upload's result is an [`Error::Hyper`] if the request could not
+    /// be made, [`Error::Status`] if the service did not respond
+    /// successfully, or [`Error::InvalidJson`] if the response could not be
+    /// parsed. It is an [`Error::Api`] or [`Error::ApiFailure`] if the
+    /// service rejected the upload, and the other errors of a response's
+    /// status are as with [`upload_file_progress_stream`].
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::ApiFailure`]: ../../enum.Error.html#variant.ApiFailure
+    /// [`Error::Hyper`]: ../../enum.Error.html#variant.Hyper
+    /// [`Error::InvalidJson`]: ../../enum.Error.html#variant.InvalidJson
+    /// [`Error::Status`]: ../../enum.Error.html#variant.Status
+    /// [`Error::Uri`]: ../../enum.Error.html#variant.Uri
+    /// [`upload_file_progress_stream`]: #method.upload_file_progress_stream
+    #[cfg(feature = "serde_json")]
+    #[must_use = "the uploads are not made until the stream is polled"]
+    pub fn upload_files_stream(&self, files: Vec<Vec<u8>>, concurrency: usize)
+        -> Result<UploadStream> {
+        let uri = Secret::new(endpoints::upload_at(
+            &self.inner.base_url,
+            &self.inner.upload_path,
+            self.key(),
+        ));
+        let uri = Uri::from_str(uri.as_str())?;
+
+        // Build all of the requests up-front, so that the stream doesn't
+        // borrow the client.
+        let requests = files.into_iter()
+            .map(|file| {
+                let multipart = Multipart::with_boundary(self.boundary());
+                let mut body = multipart.file_start("files[]", None);
+                body.extend(file);
+                body.extend(multipart.file_end());
+                body.extend(multipart.finish());
+
+                let mut request = Request::new(Method::Post, uri.clone());
+                {
+                    let headers = request.headers_mut();
+                    headers.set(UserAgent::new(constants::USER_AGENT));
+                    headers.set_raw("Content-Type", multipart.content_type());
+                }
+                request.set_body(body);
+
+                request
+            })
+            .collect::<Vec<_>>();
+
+        let client = self.clone();
+        let uploads = stream::iter_ok::<_, Error>(requests)
+            .map(move |request| {
+                client.request_upload(request).then(Ok::<_, Error>)
+            })
+            .buffer_unordered(cmp::max(concurrency, 1));
+
+        Ok(Box::new(uploads))
+    }
+
     /// Shortcut for shortening a URL.
     ///
     /// Refer to [`OwoRequester::upload_files`] for more information.
@@ -95,7 +564,7 @@ impl OwoClient {
     /// use owo::OwoReqwestClient;
     /// use std::env;
     ///
-    /// let client = OwoReqwestClient::new(env::var("OWO_TOKEN")?);
+    /// let client = OwoReqwestClient::new(env::var("OWO_TOKEN")?)?;
     ///
     /// println!("Response: {:?}", client.shorten_url("https://google.com")?);
     /// #     Ok(())
@@ -108,8 +577,125 @@ impl OwoClient {
     ///
     /// [`OwoRequester::upload_files`]: trait.OwoRequester.html#tymethod.upload_files
     #[inline]
+    #[must_use = "the request is not made until the future is polled"]
     pub fn shorten_url(&self, url: &str) -> Result<FutureResponse> {
-        self.client.shorten_url(&self.key, url)
+        let uri = endpoints::shorten_at(
+            &self.inner.base_url,
+            &self.inner.shorten_path,
+            self.key(),
+            url,
+        );
+        let request = self.prepare(shorten_request(&Secret::new(uri))?);
+
+        Ok(self.inner.client.request(request))
+    }
+
+    /// Bounds the time taken by a whole operation - such as a request, the
+    /// reading of its response, and any retries of it - by a timeout.
+    ///
+    /// This differs from a per-request network timeout, such as that set via
+    /// `OwoReqwestClientBuilder::timeout`, which restarts with each attempt.
+    /// An operation timeout instead limits the worst-case time of a job as a
+    /// whole, however many requests it makes.
+    ///
+    /// The timer starts when this is called, and runs on the client's
+    /// reactor.
+    ///
+    /// # Examples
+    ///
+    /// Shorten a URL, giving up if the response hasn't been read within ten
+    /// seconds:
+    ///
+    /// ```rust,ignore
+    /// extern crate futures;
+    /// extern crate owo;
+    /// extern crate tokio_core;
+    ///
+    /// use futures::{Future, Stream};
+    /// use owo::OwoHyperClient;
+    /// use std::env;
+    /// use std::time::Duration;
+    /// use tokio_core::reactor::Core;
+    ///
+    /// let mut core = Core::new()?;
+    /// let key = env::var("OWO_TOKEN")?;
+    /// let client = OwoHyperClient::new(key, &core.handle())?;
+    ///
+    /// let operation = client.shorten_url("https://google.com")?
+    ///     .and_then(|res| res.body().concat2());
+    /// let runner = client.with_operation_timeout(
+    ///     operation,
+    ///     Duration::from_secs(10),
+    /// )?;
+    ///
+    /// let body = core.run(runner)?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the timer could not be created.
+    ///
+    /// The future fails with [`Error::Timeout`] if the operation did not
+    /// complete in time, in which case the operation is dropped. Otherwise,
+    /// it fails with the operation's own error.
+    ///
+    /// [`Error::Io`]: ../../enum.Error.html#variant.Io
+    /// [`Error::Timeout`]: ../../enum.Error.html#variant.Timeout
+    #[must_use = "the operation is not run until the future is polled"]
+    pub fn with_operation_timeout<F>(&self, operation: F, timeout: Duration)
+        -> Result<OperationFuture<F::Item>>
+        where F: Future + 'static, Error: From<F::Error> {
+        let timer = Timeout::new(timeout, &self.inner.handle)?;
+
+        let bounded = operation.map_err(Error::from)
+            .select2(timer)
+            .then(move |result| match result {
+                Ok(Either::A((item, _))) => Ok(item),
+                Ok(Either::B(((), _))) => Err(Error::Timeout {
+                    elapsed: timeout,
+                }),
+                Err(Either::A((why, _))) => Err(why),
+                // The bound on the operation's error makes `Error::from`
+                // ambiguous here.
+                Err(Either::B((why, _))) => {
+                    Err(<Error as From<IoError>>::from(why))
+                },
+            });
+
+        Ok(Box::new(bounded))
+    }
+
+    /// Makes an upload request, parsing the service's response to it.
+    #[cfg(feature = "serde_json")]
+    fn request_upload(&self, request: Request)
+        -> Box<dyn Future<Item = FileUploadResponse, Error = Error>> {
+        let allow_unsuccessful = self.inner.allow_unsuccessful;
+        let key = self.key.clone();
+
+        let response = self.inner.client.request(self.prepare(request))
+            .and_then(|response| {
+                let status = response.status();
+                let headers = response.headers().clone();
+
+                response.body()
+                    .concat2()
+                    .map(move |body| (status, headers, body))
+            })
+            .map_err(Error::from)
+            .and_then(move |(status, headers, body)| {
+                parse_upload(status, &headers, &body, &key, allow_unsuccessful)
+            });
+
+        Box::new(response)
+    }
+
+    /// Applies the client's configuration to a request.
+    fn prepare(&self, mut request: Request) -> Request {
+        if let Some(ref locale) = self.inner.locale {
+            request.headers_mut().set_raw("Accept-Language", locale.clone());
+        }
+
+        request
     }
 }
 
@@ -125,7 +711,71 @@ impl OwoClient {
 /// ```
 ///
 /// At this point, the methods will be on your Hyper Client.
+///
+/// Requests are not made until the returned futures and streams are polled,
+/// such as by running them on a `Core`. Dropping one without polling it
+/// silently skips the request, so the compiler warns when they are unused.
 pub trait OwoRequester {
+    /// Uploads a file to the service, streaming its content from an
+    /// `AsyncRead`.
+    ///
+    /// The file is streamed to the service as it is read, without buffering it
+    /// fully in memory or touching the disk. This makes it suitable for
+    /// relaying an incoming upload straight to the service.
+    ///
+    /// # Examples
+    ///
+    /// Upload a file from an `AsyncRead`, using an environment variable for
+    /// the key:
+    ///
+    /// ```rust,ignore
+    /// extern crate futures;
+    /// extern crate hyper;
+    /// extern crate hyper_tls;
+    /// extern crate owo;
+    /// extern crate tokio_core;
+    ///
+    /// use futures::{Future, Stream};
+    /// use hyper::Client;
+    /// use hyper_tls::HttpsConnector;
+    /// use owo::OwoHyperRequester;
+    /// use std::env;
+    /// use tokio_core::reactor::Core;
+    ///
+    /// let key = env::var("OWO_TOKEN")?;
+    ///
+    /// let mut core = Core::new()?;
+    ///
+    /// let connector = HttpsConnector::new(4, &core.handle())?;
+    /// let client = Client::configure()
+    ///     .connector(connector)
+    ///     .build(&core.handle());
+    ///
+    /// // Any `AsyncRead`, such as a socket or an incoming request body, works
+    /// // here.
+    /// let reader = get_async_reader();
+    ///
+    /// let runner = client.upload_async_reader(&key, reader)?
+    ///     .and_then(|res| res.body().concat2())
+    ///     .map(|body| {
+    ///         println!("Response: {}", String::from_utf8_lossy(&body));
+    ///     });
+    ///
+    /// core.run(runner)?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming.
+    ///
+    /// Returns [`Error::Uri`] if the request URI could not be built.
+    ///
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::Uri`]: ../../enum.Error.html#variant.Uri
+    #[must_use = "the request is not made until the future is polled"]
+    fn upload_async_reader<R: AsyncRead + 'static>(&self, key: &str, reader: R)
+        -> Result<FutureResponse>;
+
     /// Shortens a URL via the service.
     ///
     /// # Examples
@@ -170,23 +820,166 @@ pub trait OwoRequester {
     /// core.run(runner)?;
     /// ```
     ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming.
+    ///
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
     // Note: This doc example can not be tested due to the reliance on
     // tokio_core. Instead, this is taken from example `01_hyper` and should
     // roughly match it to ensure accuracy.
+    #[must_use = "the request is not made until the future is polled"]
     fn shorten_url(&self, key: &str, url: &str) -> Result<FutureResponse>;
 }
 
-impl OwoRequester for HyperClient<HttpsConnector<HttpConnector>, Body> {
+impl<C: Clone + Connect> OwoRequester for HyperClient<C, Body> {
+    // hyper v0.11 offers no other way of reaching the reactor of a Client, on
+    // which the body has to be fed.
+    #[allow(deprecated)]
+    fn upload_async_reader<R: AsyncRead + 'static>(&self, key: &str, reader: R)
+        -> Result<FutureResponse> {
+        let key = Secret::key(key.to_owned())?;
+        let uri = Secret::new(endpoints::upload(key.as_str()));
+
+        Ok(self.request(upload_request(&uri, reader, None, self.handle())?))
+    }
+
     fn shorten_url(&self, key: &str, url: &str) -> Result<FutureResponse> {
-        let req_url = format!(
-            "https://api.awau.moe/shorten/polr?action=shorten&url={}&key={}",
-            url,
-            key,
-        );
-        let uri = Uri::from_str(&req_url)?;
-        let mut request = Request::new(Method::Get, uri);
-        request.headers_mut().set(UserAgent::new(constants::USER_AGENT));
+        let key = Secret::key(key.to_owned())?;
+        let uri = Secret::new(endpoints::shorten(key.as_str(), url));
+
+        Ok(self.request(shorten_request(&uri)?))
+    }
+}
+
+/// Builds a request uploading a file streamed from the reader.
+///
+/// The multipart body is fed from a task spawned on the handle. An error while
+/// reading is forwarded to the body, failing the request.
+///
+/// If no boundary is given, a unique one is generated.
+fn upload_request<R: AsyncRead + 'static>(
+    uri: &Secret,
+    reader: R,
+    boundary: Option<&str>,
+    handle: &Handle,
+) -> Result<Request> {
+    let uri = Uri::from_str(uri.as_str())?;
+
+    let multipart = Multipart::with_boundary(boundary);
+    let start = Chunk::from(multipart.file_start("files[]", None));
+    let mut end = multipart.file_end().to_vec();
+    end.extend(multipart.finish());
+
+    let file = FramedRead::new(reader, BytesCodec::new())
+        .map(|bytes| Chunk::from(bytes.freeze()))
+        .map_err(HyperError::from);
+    let chunks = stream::once(Ok(start))
+        .chain(file)
+        .chain(stream::once(Ok(Chunk::from(end))))
+        .then(Ok::<_, ()>);
+
+    let (sender, body) = Body::pair();
+    handle.spawn(sender.sink_map_err(|_| ()).send_all(chunks).map(|_| ()));
+
+    let mut request = Request::new(Method::Post, uri);
+    request.headers_mut().set(UserAgent::new(constants::USER_AGENT));
+    request.headers_mut().set_raw("Content-Type", multipart.content_type());
+    request.set_body(body);
+
+    Ok(request)
+}
+
+/// Parses the response to an upload made by the client itself.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidKey`], [`Error::PayloadTooLarge`] or
+/// [`Error::RateLimited`] if the response's status indicates so, whatever
+/// the body.
+///
+/// Returns [`Error::Api`] if the service rejected the upload - unless
+/// unsuccessful responses are allowed and the status is successful - or
+/// otherwise [`Error::ServiceUnavailable`] or [`Error::Status`] if it did not
+/// respond successfully, depending on whether the response is an HTML page.
+///
+/// Returns [`Error::HtmlResponse`] if the body is an HTML page, or
+/// [`Error::InvalidJson`] if it otherwise could not be deserialized.
+///
+/// Returns [`Error::ApiFailure`] if the service responded with
+/// `success: false`, unless unsuccessful responses are allowed.
+///
+/// [`Error::Api`]: ../../enum.Error.html#variant.Api
+/// [`Error::ApiFailure`]: ../../enum.Error.html#variant.ApiFailure
+/// [`Error::HtmlResponse`]: ../../enum.Error.html#variant.HtmlResponse
+/// [`Error::InvalidJson`]: ../../enum.Error.html#variant.InvalidJson
+/// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+/// [`Error::PayloadTooLarge`]: ../../enum.Error.html#variant.PayloadTooLarge
+/// [`Error::RateLimited`]: ../../enum.Error.html#variant.RateLimited
+/// [`Error::ServiceUnavailable`]: ../../enum.Error.html#variant.ServiceUnavailable
+/// [`Error::Status`]: ../../enum.Error.html#variant.Status
+#[cfg(feature = "serde_json")]
+fn parse_upload(
+    status: StatusCode,
+    headers: &Headers,
+    body: &[u8],
+    key: &Secret,
+    allow_unsuccessful: bool,
+) -> Result<FileUploadResponse> {
+    let header = |name| {
+        headers.get_raw(name)
+            .and_then(|raw| raw.one())
+            .and_then(|value| str::from_utf8(value).ok())
+    };
+    let status = status.as_u16();
+
+    if let Some(why) = status_error(status, header("Retry-After")) {
+        return Err(why);
+    }
+
+    let body = String::from_utf8_lossy(body);
+    let content_type = header("Content-Type");
+
+    if let Some(why) = body_error(
+        status,
+        content_type,
+        &body,
+        key,
+        allow_unsuccessful,
+    ) {
+        return Err(why);
+    }
+
+    if let Some(why) = html_response(&body, key) {
+        return Err(why);
+    }
+
+    let response = serde_json::from_str::<FileUploadResponse>(&body)
+        .map_err(|why| Error::InvalidJson {
+            body: snippet(key.redact(&body)),
+            error: why,
+        })?;
 
-        Ok(self.request(request))
+    if !response.success && !allow_unsuccessful {
+        return Err(Error::ApiFailure {
+            body: snippet(key.redact(&body)),
+        });
     }
+
+    Ok(response)
+}
+
+/// The error of an upload whose task was dropped by the reactor before it
+/// completed.
+#[cfg(feature = "serde_json")]
+fn dropped() -> Error {
+    Error::from(IoError::new(IoErrorKind::Other, "Upload task was dropped"))
+}
+
+fn shorten_request(uri: &Secret) -> Result<Request> {
+    let uri = Uri::from_str(uri.as_str())?;
+    let mut request = Request::new(Method::Get, uri);
+    request.headers_mut().set(UserAgent::new(constants::USER_AGENT));
+
+    Ok(request)
 }
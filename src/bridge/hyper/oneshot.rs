@@ -21,6 +21,7 @@ use ::Result;
 /// [`Error::NativeTls`]: ../../../enum.Error.html#variant.NativeTls
 /// [`OwoHyperRequester`]: ../trait.OwoRequester.html
 #[inline]
+#[must_use = "the request is not made until the future is polled"]
 pub fn shorten_url(key: &str, url: &str, handle: &Handle)
     -> Result<FutureResponse> {
     OwoClient::new(key, handle)?.shorten_url(url)
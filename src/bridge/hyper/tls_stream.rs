@@ -0,0 +1,76 @@
+use futures::Poll;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::io::{Error as IoError, Read, Result as IoResult, Write};
+use tokio_io::{AsyncRead, AsyncWrite};
+use tokio_rustls::client::TlsStream;
+
+/// A stream made by the [`OwoConnector`] when using `rustls`, which is only
+/// protected by TLS for `https` URLs.
+///
+/// This mirrors the stream of the same name from `hyper-tls`, which is used
+/// when using `native-tls`.
+///
+/// [`OwoConnector`]: struct.OwoConnector.html
+pub enum MaybeHttpsStream<T> {
+    /// A stream over plain text.
+    Http(T),
+    /// A stream protected with TLS, boxed as the state of the TLS session is
+    /// large.
+    Https(Box<TlsStream<T>>),
+}
+
+impl<T> Debug for MaybeHttpsStream<T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            MaybeHttpsStream::Http(..) => f.pad("Http(..)"),
+            MaybeHttpsStream::Https(..) => f.pad("Https(..)"),
+        }
+    }
+}
+
+impl<T: AsyncRead + AsyncWrite> Read for MaybeHttpsStream<T> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        match *self {
+            MaybeHttpsStream::Http(ref mut stream) => stream.read(buf),
+            MaybeHttpsStream::Https(ref mut stream) => stream.read(buf),
+        }
+    }
+}
+
+impl<T: AsyncRead + AsyncWrite> Write for MaybeHttpsStream<T> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        match *self {
+            MaybeHttpsStream::Http(ref mut stream) => stream.write(buf),
+            MaybeHttpsStream::Https(ref mut stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        match *self {
+            MaybeHttpsStream::Http(ref mut stream) => stream.flush(),
+            MaybeHttpsStream::Https(ref mut stream) => stream.flush(),
+        }
+    }
+}
+
+impl<T: AsyncRead + AsyncWrite> AsyncRead for MaybeHttpsStream<T> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        match *self {
+            MaybeHttpsStream::Http(ref stream) => {
+                stream.prepare_uninitialized_buffer(buf)
+            },
+            MaybeHttpsStream::Https(ref stream) => {
+                stream.prepare_uninitialized_buffer(buf)
+            },
+        }
+    }
+}
+
+impl<T: AsyncRead + AsyncWrite> AsyncWrite for MaybeHttpsStream<T> {
+    fn shutdown(&mut self) -> Poll<(), IoError> {
+        match *self {
+            MaybeHttpsStream::Http(ref mut stream) => stream.shutdown(),
+            MaybeHttpsStream::Https(ref mut stream) => stream.shutdown(),
+        }
+    }
+}
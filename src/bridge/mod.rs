@@ -4,3 +4,8 @@
 pub mod hyper;
 #[cfg(feature = "reqwest")]
 pub mod reqwest;
+
+#[cfg(any(feature = "hyper", feature = "reqwest"))]
+mod multipart;
+#[cfg(any(feature = "reqwest", all(feature = "hyper", feature = "serde_json")))]
+mod response;
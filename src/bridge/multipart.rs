@@ -0,0 +1,125 @@
+//! Minimal `multipart/form-data` encoding, for when a body needs to be built
+//! by hand, such as when streaming it.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// The encoder of a multipart form, holding the boundary between parts.
+pub(crate) struct Multipart {
+    boundary: String,
+}
+
+impl Multipart {
+    /// Creates a new encoder with a generated boundary.
+    ///
+    /// The boundary is unique within the process, which is enough to not clash
+    /// with the content of the uploaded files in practice.
+    pub(crate) fn new() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.subsec_nanos())
+            .unwrap_or(0);
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            boundary: format!("owo-rs-{:08x}{:08x}", nanos, count),
+        }
+    }
+
+    /// Creates a new encoder with the given boundary, or a generated one if
+    /// none is given.
+    ///
+    /// The boundary should have been checked via [`is_valid_boundary`].
+    ///
+    /// [`is_valid_boundary`]: fn.is_valid_boundary.html
+    pub(crate) fn with_boundary(boundary: Option<&str>) -> Self {
+        match boundary {
+            Some(boundary) => Self {
+                boundary: boundary.to_owned(),
+            },
+            None => Self::new(),
+        }
+    }
+
+    /// The value of the `Content-Type` header of the form.
+    ///
+    /// The boundary is quoted if it contains characters which [RFC 2045]
+    /// doesn't allow in an unquoted parameter value, such as spaces.
+    ///
+    /// [RFC 2045]: https://tools.ietf.org/html/rfc2045#section-5.1
+    pub(crate) fn content_type(&self) -> String {
+        let token = |c: char| c.is_ascii_alphanumeric() || "'+_-.".contains(c);
+
+        if self.boundary.chars().all(token) {
+            format!("multipart/form-data; boundary={}", self.boundary)
+        } else {
+            format!("multipart/form-data; boundary=\"{}\"", self.boundary)
+        }
+    }
+
+    /// The headers of a file part, to be followed by the content of the file
+    /// and then a [`file_end`].
+    ///
+    /// [`file_end`]: #method.file_end
+    pub(crate) fn file_start(&self, name: &str, filename: Option<&str>)
+        -> Vec<u8> {
+        let mut disposition = format!("form-data; name=\"{}\"", name);
+
+        if let Some(filename) = filename {
+            disposition.push_str(&format!("; filename=\"{}\"", filename));
+        }
+
+        format!(
+            "--{}\r\n\
+             Content-Disposition: {}\r\n\
+             Content-Type: application/octet-stream\r\n\
+             \r\n",
+            self.boundary,
+            disposition,
+        ).into_bytes()
+    }
+
+    /// The end of a file part.
+    pub(crate) fn file_end(&self) -> &'static [u8] {
+        b"\r\n"
+    }
+
+    /// The end of the form, to be written after all parts.
+    pub(crate) fn finish(&self) -> Vec<u8> {
+        format!("--{}--\r\n", self.boundary).into_bytes()
+    }
+
+    /// The length in bytes of the form containing the given files - each with
+    /// an optional filename - under the given name, without encoding it.
+    #[cfg(feature = "reqwest")]
+    pub(crate) fn body_len(
+        &self,
+        name: &str,
+        files: &[(Option<&str>, &[u8])],
+    ) -> u64 {
+        let parts = files.iter().map(|&(filename, file)| {
+            let start = self.file_start(name, filename).len();
+
+            (start + file.len() + self.file_end().len()) as u64
+        }).sum::<u64>();
+
+        parts + self.finish().len() as u64
+    }
+}
+
+/// Whether a boundary is valid per [RFC 2046]: between 1 and 70 characters
+/// from a restricted set, not ending with a space.
+///
+/// [RFC 2046]: https://tools.ietf.org/html/rfc2046#section-5.1.1
+pub(crate) fn is_valid_boundary(boundary: &str) -> bool {
+    let allowed = |c: char| {
+        c.is_ascii_alphanumeric() || "'()+_,-./:=? ".contains(c)
+    };
+
+    !boundary.is_empty()
+        && boundary.len() <= 70
+        && !boundary.ends_with(' ')
+        && boundary.chars().all(allowed)
+}
@@ -0,0 +1,852 @@
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use reqwest::{Certificate, Client, Identity};
+use std::sync::Arc;
+use std::time::Duration;
+use super::failover::Failover;
+use super::retry::{BackoffStrategy, RetryPolicy};
+use super::{Inner, OwoClient};
+use ::bridge::multipart;
+use ::secret::Secret;
+use ::{
+    Config,
+    Error,
+    Result,
+    ServiceLimits,
+    constants,
+    domains,
+    endpoints,
+};
+
+/// A builder for configuring an [`OwoClient`] before creating it.
+///
+/// # Examples
+///
+/// Create a client using a key from the environment:
+///
+/// ```rust,no_run
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// #
+/// use owo::OwoReqwestClientBuilder;
+/// use std::env;
+///
+/// let client = OwoReqwestClientBuilder::new(env::var("OWO_KEY")?).build()?;
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// [`OwoClient`]: struct.OwoClient.html
+pub struct OwoClientBuilder {
+    allow_unsuccessful: bool,
+    base_url: Option<String>,
+    boundary: Option<String>,
+    domain: Option<String>,
+    identity: Option<Identity>,
+    key: Secret,
+    limits: ServiceLimits,
+    locale: Option<String>,
+    max_response_bytes: Option<usize>,
+    mirrors: Vec<String>,
+    retry: RetryPolicy,
+    root_certificates: Vec<Certificate>,
+    shorten_path: Option<String>,
+    timeout: Option<Duration>,
+    upload_path: Option<String>,
+    validate_domain: bool,
+    #[cfg(all(feature = "dangerous-config", feature = "reqwest-native-tls"))]
+    disable_hostname_verification: bool,
+}
+
+impl OwoClientBuilder {
+    /// Creates a new builder for a client using the given key.
+    ///
+    /// By default, this creates a client identical to one created via
+    /// [`OwoClient::new`].
+    ///
+    /// [`OwoClient::new`]: struct.OwoClient.html#method.new
+    pub fn new<S: Into<String>>(key: S) -> Self {
+        Self {
+            allow_unsuccessful: false,
+            base_url: None,
+            boundary: None,
+            domain: None,
+            identity: None,
+            key: Secret::new(key.into()),
+            limits: ServiceLimits::default(),
+            locale: None,
+            max_response_bytes: None,
+            mirrors: vec![],
+            retry: RetryPolicy::default(),
+            root_certificates: vec![],
+            shorten_path: None,
+            timeout: None,
+            upload_path: None,
+            validate_domain: false,
+            #[cfg(all(
+                feature = "dangerous-config",
+                feature = "reqwest-native-tls",
+            ))]
+            disable_hostname_verification: false,
+        }
+    }
+
+    /// Sets the base URL of the API to make requests to, such as
+    /// `"https://api.example.com"` for a self-hosted instance of the service.
+    ///
+    /// If not set, the `OWO_API_URL` environment variable is used if present,
+    /// such as to redirect an integration environment to a staging instance
+    /// without code changes. Otherwise, defaults to [`constants::API_URL`].
+    ///
+    /// # Errors
+    ///
+    /// [`build`] returns [`Error::Config`] if the base URL - or the
+    /// environment variable - is not an absolute `http` or `https` URL.
+    ///
+    /// [`Error::Config`]: ../../enum.Error.html#variant.Config
+    /// [`build`]: #method.build
+    /// [`constants::API_URL`]: ../../constants/constant.API_URL.html
+    pub fn base_url<S: Into<String>>(mut self, base_url: S) -> Self {
+        self.base_url = Some(base_url.into());
+
+        self
+    }
+
+    /// Sets a fixed boundary to separate the parts of multipart upload bodies
+    /// with.
+    ///
+    /// This is useful for producing reproducible request bodies in tests, or
+    /// for services which are picky about the format of boundaries.
+    ///
+    /// By default, a unique boundary is generated for each upload.
+    ///
+    /// # Errors
+    ///
+    /// [`build`] returns [`Error::InvalidBoundary`] if the boundary is not
+    /// valid per RFC 2046.
+    ///
+    /// [`Error::InvalidBoundary`]: ../../enum.Error.html#variant.InvalidBoundary
+    /// [`build`]: #method.build
+    pub fn boundary<S: Into<String>>(mut self, boundary: S) -> Self {
+        self.boundary = Some(boundary.into());
+
+        self
+    }
+
+    /// Sets the domain which uploaded files are linked to via
+    /// [`OwoClient::full_url`], such as one of the service's vanity domains.
+    ///
+    /// Any domain is accepted, such as that of a self-hosted instance. Use
+    /// [`preferred_domain`] to only accept the service's known domains.
+    ///
+    /// Defaults to [`constants::DEFAULT_DOMAIN`].
+    ///
+    /// [`OwoClient::full_url`]: struct.OwoClient.html#method.full_url
+    /// [`constants::DEFAULT_DOMAIN`]: ../../constants/constant.DEFAULT_DOMAIN.html
+    /// [`preferred_domain`]: #method.preferred_domain
+    pub fn domain<S: Into<String>>(mut self, domain: S) -> Self {
+        self.domain = Some(domain.into());
+        self.validate_domain = false;
+
+        self
+    }
+
+    /// Sets the domain which uploaded files are linked to, which must be one
+    /// of the service's [known domains].
+    ///
+    /// This is useful for letting users choose a domain, such as
+    /// `uwu.whats-th.is`, which is then honoured by [`OwoClient::full_url`]
+    /// and the methods which produce links to uploaded files.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn try_main() -> owo::Result<()> {
+    /// use owo::OwoReqwestClientBuilder;
+    ///
+    /// let client = OwoReqwestClientBuilder::new("my key")
+    ///     .preferred_domain("https://uwu.whats-th.is/")
+    ///     .build()?;
+    ///
+    /// assert_eq!(client.domain(), "uwu.whats-th.is");
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`build`] returns [`Error::UnknownDomain`] if the domain is not a known
+    /// domain.
+    ///
+    /// [`Error::UnknownDomain`]: ../../enum.Error.html#variant.UnknownDomain
+    /// [`OwoClient::full_url`]: struct.OwoClient.html#method.full_url
+    /// [`build`]: #method.build
+    /// [known domains]: ../../domains/constant.KNOWN.html
+    pub fn preferred_domain<S: Into<String>>(mut self, domain: S) -> Self {
+        self.domain = Some(domain.into());
+        self.validate_domain = true;
+
+        self
+    }
+
+    /// Sets the identity - a client certificate and its private key - to
+    /// present to the service, for instances behind mutual TLS.
+    ///
+    /// This only applies to clients whose reqwest Client is built by the
+    /// builder, so not to those created via [`OwoClient::from_client`], and
+    /// requires a TLS backend which supports client certificates. The `hyper`
+    /// bridge does not support client certificates.
+    ///
+    /// With the `reqwest-rustls` feature, the identity must be read via
+    /// `Identity::from_pem` rather than from a PKCS #12 archive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # extern crate owo;
+    /// # extern crate reqwest;
+    /// #
+    /// # use std::error::Error;
+    /// #
+    /// # #[cfg(not(feature = "reqwest-rustls"))]
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::OwoReqwestClientBuilder;
+    /// use reqwest::Identity;
+    /// use std::fs::File;
+    /// use std::io::Read;
+    ///
+    /// let mut der = vec![];
+    /// File::open("client.p12")?.read_to_end(&mut der)?;
+    /// let identity = Identity::from_pkcs12_der(&der, "password")?;
+    ///
+    /// let client = OwoReqwestClientBuilder::new("my key")
+    ///     .base_url("https://owo.example.com")
+    ///     .identity(identity)
+    ///     .build()?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # #[cfg(feature = "reqwest-rustls")]
+    /// # fn try_main() -> Result<(), Box<Error>> { Ok(()) }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`OwoClient::from_client`]: struct.OwoClient.html#method.from_client
+    pub fn identity(mut self, identity: Identity) -> Self {
+        self.identity = Some(identity);
+
+        self
+    }
+
+    /// Sets the limits of the service which requests are checked against
+    /// before being made, such as the maximum number of files per upload.
+    ///
+    /// Defaults to the limits of the official service. Self-hosted instances
+    /// often allow more, so their limits can be raised or disabled here.
+    ///
+    /// # Examples
+    ///
+    /// Allow up to 10 files per upload on a self-hosted instance, keeping the
+    /// other limits:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::{OwoReqwestClientBuilder, ServiceLimits};
+    /// use std::env;
+    ///
+    /// let limits = ServiceLimits {
+    ///     max_files: Some(10),
+    ///     ..ServiceLimits::default()
+    /// };
+    ///
+    /// let client = OwoReqwestClientBuilder::new(env::var("OWO_KEY")?)
+    ///     .base_url("https://api.example.com")
+    ///     .limits(limits)
+    ///     .build()?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn limits(mut self, limits: ServiceLimits) -> Self {
+        self.limits = limits;
+
+        self
+    }
+
+    /// Sets the locale to request responses in, sent as the `Accept-Language`
+    /// header of every request.
+    ///
+    /// Some services localize their error descriptions, so setting this - for
+    /// example to `"en"` - keeps them consistent regardless of the server's
+    /// locale.
+    ///
+    /// By default, no `Accept-Language` header is sent.
+    ///
+    /// [`build`] fails if the locale is not a valid header value.
+    ///
+    /// [`build`]: #method.build
+    pub fn locale<S: Into<String>>(mut self, locale: S) -> Self {
+        self.locale = Some(locale.into());
+
+        self
+    }
+
+    /// Sets the maximum size of a response body to read when uploading files
+    /// or shortening URLs, in bytes.
+    ///
+    /// This protects memory when the client is pointed at a misbehaving or
+    /// malicious endpoint, such as a proxy returning a huge error page.
+    /// Requests whose responses are larger fail with
+    /// [`Error::ResponseTooLarge`], without the rest of the body being read.
+    ///
+    /// By default, response bodies larger than 1 MiB fail in the same way.
+    ///
+    /// [`Error::ResponseTooLarge`]: ../../enum.Error.html#variant.ResponseTooLarge
+    pub fn max_response_bytes(mut self, max: usize) -> Self {
+        self.max_response_bytes = Some(max);
+
+        self
+    }
+
+    /// Adds a mirror of the API to fail over to when the service can not be
+    /// reached, given by its base URL.
+    ///
+    /// This can be called multiple times to add multiple mirrors, which are
+    /// tried in the order they were added after the [`base_url`].
+    ///
+    /// Only failures to reach the service - such as failing to connect - cause
+    /// a request to fail over to the next mirror. If the service responds,
+    /// even with an error status, the response is used as-is. The first base
+    /// URL to respond is remembered, and used first by subsequent requests,
+    /// including those of clones of the client.
+    ///
+    /// With the `log` feature enabled, each fail over is logged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::OwoReqwestClientBuilder;
+    /// use std::env;
+    ///
+    /// let client = OwoReqwestClientBuilder::new(env::var("OWO_KEY")?)
+    ///     .mirror("https://api-eu.example.com")
+    ///     .mirror("https://api-us.example.com")
+    ///     .build()?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`build`] returns [`Error::Config`] if the base URL is not an absolute
+    /// `http` or `https` URL.
+    ///
+    /// [`Error::Config`]: ../../enum.Error.html#variant.Config
+    /// [`base_url`]: #method.base_url
+    /// [`build`]: #method.build
+    pub fn mirror<S: Into<String>>(mut self, base_url: S) -> Self {
+        self.mirrors.push(base_url.into());
+
+        self
+    }
+
+    /// Sets the number of times each request of a batch operation - such as
+    /// [`OwoClient::upload_all`] - is retried after failing to reach the
+    /// service, or after being rate limited.
+    ///
+    /// When the service gives a hint of when to retry a rate limited request,
+    /// the retry waits until then. Otherwise, it waits according to the
+    /// [`backoff`] strategy.
+    ///
+    /// Defaults to `0`, meaning requests are not retried.
+    ///
+    /// [`OwoClient::upload_all`]: struct.OwoClient.html#method.upload_all
+    /// [`backoff`]: #method.backoff
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retry.retries = retries;
+
+        self
+    }
+
+    /// Caps the total number of retries made across all requests of a single
+    /// batch operation.
+    ///
+    /// Once the budget is exhausted, the failures of any remaining requests in
+    /// the batch are returned immediately rather than being retried. This
+    /// prevents a misbehaving service from multiplying the number of requests
+    /// made by a large batch, giving a predictable worst case.
+    ///
+    /// Each batch operation starts with a full budget.
+    ///
+    /// By default, the total number of retries is not capped.
+    ///
+    /// # Examples
+    ///
+    /// Retry each upload up to 3 times, but no more than 5 times in total:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::OwoReqwestClientBuilder;
+    /// use std::env;
+    ///
+    /// let client = OwoReqwestClientBuilder::new(env::var("OWO_KEY")?)
+    ///     .retries(3)
+    ///     .retry_budget(5)
+    ///     .build()?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn retry_budget(mut self, total: u32) -> Self {
+        self.retry.budget = Some(total);
+
+        self
+    }
+
+    /// Sets how long to wait before retrying a failed request, when
+    /// [`retries`] are enabled.
+    ///
+    /// Defaults to [`BackoffStrategy::ExponentialJitter`], so that many
+    /// clients which failed at the same time - such as during an outage - do
+    /// not all retry at the same time.
+    ///
+    /// # Examples
+    ///
+    /// Retry up to 3 times, waiting a second before each retry:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::{BackoffStrategy, OwoReqwestClientBuilder};
+    /// use std::env;
+    /// use std::time::Duration;
+    ///
+    /// let client = OwoReqwestClientBuilder::new(env::var("OWO_KEY")?)
+    ///     .retries(3)
+    ///     .backoff(BackoffStrategy::Fixed(Duration::from_secs(1)))
+    ///     .build()?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`BackoffStrategy::ExponentialJitter`]: enum.BackoffStrategy.html#variant.ExponentialJitter
+    /// [`retries`]: #method.retries
+    pub fn backoff(mut self, backoff: BackoffStrategy) -> Self {
+        self.retry.backoff = backoff;
+
+        self
+    }
+
+    /// Adds a root certificate to trust, such as that of the private CA of a
+    /// self-hosted instance.
+    ///
+    /// This can be called multiple times to trust multiple certificates, in
+    /// addition to the system's trusted roots. Unlike
+    /// `danger_disable_hostname_verification`, certificates are still fully
+    /// verified, so this is the safe option for private deployments.
+    ///
+    /// As with [`identity`], this only applies to clients whose reqwest Client
+    /// is built by the builder.
+    ///
+    /// # Examples
+    ///
+    /// Trust the CA of a self-hosted instance. `Certificate` reads the DER
+    /// encoding, so convert a PEM file first, such as with
+    /// `openssl x509 -in ca.pem -outform der -out ca.der`:
+    ///
+    /// ```rust,no_run
+    /// # extern crate owo;
+    /// # extern crate reqwest;
+    /// #
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::OwoReqwestClientBuilder;
+    /// use reqwest::Certificate;
+    /// use std::fs::File;
+    /// use std::io::Read;
+    ///
+    /// let mut der = vec![];
+    /// File::open("ca.der")?.read_to_end(&mut der)?;
+    ///
+    /// let client = OwoReqwestClientBuilder::new("my key")
+    ///     .base_url("https://owo.example.com")
+    ///     .root_certificate(Certificate::from_der(&der)?)
+    ///     .build()?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`identity`]: #method.identity
+    pub fn root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+
+        self
+    }
+
+    /// Sets the path of the endpoint to shorten URLs, relative to the
+    /// [`base_url`], for instances which do not use the official service's
+    /// path.
+    ///
+    /// Defaults to [`constants::SHORTEN_PATH`].
+    ///
+    /// # Errors
+    ///
+    /// [`build`] returns [`Error::Config`] if the path contains a query
+    /// string, a fragment, or whitespace.
+    ///
+    /// [`Error::Config`]: ../../enum.Error.html#variant.Config
+    /// [`base_url`]: #method.base_url
+    /// [`build`]: #method.build
+    /// [`constants::SHORTEN_PATH`]: ../../constants/constant.SHORTEN_PATH.html
+    pub fn shorten_path<S: Into<String>>(mut self, path: S) -> Self {
+        self.shorten_path = Some(path.into());
+
+        self
+    }
+
+    /// Sets the timeout of each request, covering connecting through to
+    /// reading the response.
+    ///
+    /// By default, reqwest's default timeout is used.
+    ///
+    /// Requests which time out fail with [`Error::Timeout`], and are retried
+    /// by batch operations according to the [`retries`].
+    ///
+    /// The timeout applies to each attempt separately, so a batch operation
+    /// with retries may take longer in total. The async `hyper` bridge can
+    /// instead bound a whole operation via
+    /// `OwoHyperClient::with_operation_timeout`.
+    ///
+    /// [`Error::Timeout`]: ../../enum.Error.html#variant.Timeout
+    /// [`retries`]: #method.retries
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+
+        self
+    }
+
+    /// Returns upload responses with `success: false` as they are, as a
+    /// [`FileUploadResponse`] whose [`success`] field the caller must check.
+    ///
+    /// By default, such responses are returned as an [`Error::Api`] if they
+    /// contain an error code, or otherwise as an [`Error::ApiFailure`], so
+    /// that a failed upload can't be mistaken for a successful one.
+    ///
+    /// Responses with an unsuccessful HTTP status are still returned as
+    /// errors, regardless of this setting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::OwoReqwestClientBuilder;
+    /// use std::env;
+    ///
+    /// let client = OwoReqwestClientBuilder::new(env::var("OWO_KEY")?)
+    ///     .allow_unsuccessful()
+    ///     .build()?;
+    ///
+    /// let response = client.upload_file(b"hello".to_vec())?;
+    ///
+    /// if !response.success {
+    ///     println!("Upload failed");
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::ApiFailure`]: ../../enum.Error.html#variant.ApiFailure
+    /// [`FileUploadResponse`]: ../../model/struct.FileUploadResponse.html
+    /// [`success`]: ../../model/struct.FileUploadResponse.html#structfield.success
+    pub fn allow_unsuccessful(mut self) -> Self {
+        self.allow_unsuccessful = true;
+
+        self
+    }
+
+    /// Sets the path of the endpoint to upload files, relative to the
+    /// [`base_url`], for instances which do not use the official service's
+    /// path.
+    ///
+    /// Defaults to [`constants::UPLOAD_PATH`].
+    ///
+    /// # Examples
+    ///
+    /// Upload to a self-hosted instance at `https://example.com/api/v1/upload`:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::OwoReqwestClientBuilder;
+    /// use std::env;
+    ///
+    /// let client = OwoReqwestClientBuilder::new(env::var("OWO_KEY")?)
+    ///     .base_url("https://example.com")
+    ///     .upload_path("/api/v1/upload")
+    ///     .build()?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`build`] returns [`Error::Config`] if the path contains a query
+    /// string, a fragment, or whitespace.
+    ///
+    /// [`Error::Config`]: ../../enum.Error.html#variant.Config
+    /// [`base_url`]: #method.base_url
+    /// [`build`]: #method.build
+    /// [`constants::UPLOAD_PATH`]: ../../constants/constant.UPLOAD_PATH.html
+    pub fn upload_path<S: Into<String>>(mut self, path: S) -> Self {
+        self.upload_path = Some(path.into());
+
+        self
+    }
+
+    /// Controls whether the client skips checking that the server's TLS
+    /// certificate was issued for the host being connected to.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// This is the only relaxation of certificate verification offered by
+    /// the builder. To point the client at a local HTTPS mock server with a
+    /// self-signed certificate, trust the certificate via
+    /// [`root_certificate`], and disable hostname verification if the
+    /// certificate wasn't issued for the mock's host.
+    ///
+    /// # Warning
+    ///
+    /// **This is only intended for testing**. Enabling this means that a
+    /// valid certificate for *any* site will be trusted for the API's host,
+    /// so anyone able to intercept your traffic can read your key and
+    /// uploads.
+    ///
+    /// This is only available with the `dangerous-config` feature, to prevent
+    /// accidental use in production, and with the `reqwest-support` feature,
+    /// as rustls always verifies hostnames. It has no effect if the
+    /// `reqwest-rustls` feature is also enabled.
+    ///
+    /// [`root_certificate`]: #method.root_certificate
+    #[cfg(all(feature = "dangerous-config", feature = "reqwest-native-tls"))]
+    pub fn danger_disable_hostname_verification(mut self, disable: bool)
+        -> Self {
+        self.disable_hostname_verification = disable;
+
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming
+    /// surrounding whitespace.
+    ///
+    /// Returns [`Error::InvalidBoundary`] if a configured multipart boundary is
+    /// invalid.
+    ///
+    /// Returns [`Error::Config`] if the base URL, a mirror, an endpoint path,
+    /// or the locale is invalid.
+    ///
+    /// Returns [`Error::UnknownDomain`] if a [`preferred_domain`] is not one of
+    /// the service's known domains.
+    ///
+    /// Returns [`Error::Reqwest`] if there was an error building the
+    /// underlying `reqwest` Client, such as if the TLS backend could not be
+    /// initialized.
+    ///
+    /// [`Error::Config`]: ../../enum.Error.html#variant.Config
+    /// [`Error::InvalidBoundary`]: ../../enum.Error.html#variant.InvalidBoundary
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
+    /// [`Error::UnknownDomain`]: ../../enum.Error.html#variant.UnknownDomain
+    /// [`preferred_domain`]: #method.preferred_domain
+    pub fn build(self) -> Result<OwoClient> {
+        self.build_with(None)
+    }
+
+    /// Builds the client, using the given reqwest Client - if any - rather
+    /// than building one from the builder's reqwest settings.
+    pub(crate) fn build_with(self, client: Option<Client>)
+        -> Result<OwoClient> {
+        let key = Secret::key(self.key.as_str().to_owned())?;
+
+        if let Some(boundary) = self.boundary.as_ref() {
+            if !multipart::is_valid_boundary(boundary) {
+                return Err(Error::InvalidBoundary(boundary.clone()));
+            }
+        }
+
+        let domain = match self.domain {
+            Some(ref domain) if self.validate_domain => {
+                domains::validate(domain)?.to_owned()
+            },
+            Some(domain) => domain,
+            None => constants::DEFAULT_DOMAIN.to_owned(),
+        };
+
+        let mut base_urls = vec![endpoints::base_url(self.base_url)?];
+
+        for mirror in self.mirrors {
+            base_urls.push(endpoints::check_base_url("mirror", mirror)?);
+        }
+
+        let shorten_path = endpoints::path(
+            "shorten_path",
+            self.shorten_path,
+            constants::SHORTEN_PATH,
+        )?;
+        let upload_path = endpoints::path(
+            "upload_path",
+            self.upload_path,
+            constants::UPLOAD_PATH,
+        )?;
+
+        let client = match client {
+            Some(client) => client,
+            None => {
+                let mut builder = Client::builder();
+
+                // Both backends are compiled in if both features are enabled,
+                // in which case reqwest would otherwise use `native-tls`.
+                #[cfg(feature = "reqwest-rustls")]
+                {
+                    builder = builder.use_rustls_tls();
+                }
+
+                if let Some(locale) = self.locale {
+                    let value = HeaderValue::from_str(&locale)
+                        .map_err(|why| Error::Config {
+                            field: "locale".to_owned(),
+                            reason: why.to_string(),
+                        })?;
+                    let mut headers = HeaderMap::new();
+                    headers.insert(header::ACCEPT_LANGUAGE, value);
+                    builder = builder.default_headers(headers);
+                }
+
+                if let Some(identity) = self.identity {
+                    builder = builder.identity(identity);
+                }
+
+                for certificate in self.root_certificates {
+                    builder = builder.add_root_certificate(certificate);
+                }
+
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+
+                #[cfg(all(
+                    feature = "dangerous-config",
+                    feature = "reqwest-native-tls",
+                ))]
+                {
+                    if self.disable_hostname_verification {
+                        builder = builder
+                            .danger_accept_invalid_hostnames(true);
+                    }
+                }
+
+                builder.build()?
+            },
+        };
+
+        let inner = Inner {
+            allow_unsuccessful: self.allow_unsuccessful,
+            boundary: self.boundary,
+            client,
+            domain,
+            failover: Failover::new(base_urls),
+            limits: self.limits,
+            max_response_bytes: self.max_response_bytes,
+            retry: self.retry,
+            shorten_path,
+            upload_path,
+        };
+
+        Ok(OwoClient {
+            inner: Arc::new(inner),
+            key,
+        })
+    }
+}
+
+/// Turns a config into a builder, to change settings which configs don't
+/// support.
+///
+/// The config's [`key`] is used as-is, without falling back to its
+/// [`key_var`]. Resolve the key via [`Config::resolve_key`] first if the
+/// config may name a variable instead.
+///
+/// [`Config::resolve_key`]: ../../config/struct.Config.html#method.resolve_key
+/// [`key`]: ../../config/struct.Config.html#structfield.key
+/// [`key_var`]: ../../config/struct.Config.html#structfield.key_var
+impl From<Config> for OwoClientBuilder {
+    fn from(config: Config) -> Self {
+        let mut builder = Self::new(config.key);
+        builder.base_url = config.base_url;
+        builder.domain = config.domain;
+        builder.shorten_path = config.shorten_path;
+        builder.timeout = config.timeout;
+        builder.upload_path = config.upload_path;
+
+        builder
+    }
+}
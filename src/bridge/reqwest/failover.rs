@@ -0,0 +1,89 @@
+//! Failover of requests across mirrors of the API.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use super::timed;
+use ::{Error, NetworkErrorKind, Result};
+
+/// The base URLs of the API and its mirrors, remembering which one last
+/// responded.
+pub(crate) struct Failover {
+    active: AtomicUsize,
+    base_urls: Vec<String>,
+}
+
+impl Failover {
+    /// Creates a failover across the given base URLs, in order of preference.
+    ///
+    /// At least one base URL must be given.
+    pub(crate) fn new(base_urls: Vec<String>) -> Self {
+        debug_assert!(!base_urls.is_empty());
+
+        Self {
+            active: AtomicUsize::new(0),
+            base_urls,
+        }
+    }
+
+    /// The base URL which requests are currently made to.
+    pub(crate) fn active(&self) -> &str {
+        &self.base_urls[self.active.load(Ordering::Relaxed)]
+    }
+
+    /// Makes a request to the active base URL, moving on to the next one each
+    /// time the service could not be reached.
+    ///
+    /// The first base URL to respond - successfully or not - becomes the
+    /// active one for subsequent requests. A request timing out is returned
+    /// as an `Error::Timeout`, without moving on.
+    pub(crate) fn run<T, F>(&self, mut request: F) -> Result<T>
+        where F: FnMut(&str) -> Result<T> {
+        let start = self.active.load(Ordering::Relaxed);
+        let count = self.base_urls.len();
+        let mut attempt = 0;
+
+        loop {
+            let index = (start + attempt) % count;
+            let base_url = &self.base_urls[index];
+
+            match timed(|| request(base_url)) {
+                Err(ref why) if attempt + 1 < count && is_unreachable(why) => {
+                    #[cfg(feature = "log")]
+                    warn!("Failing over from {}: {}", base_url, why);
+
+                    attempt += 1;
+                },
+                result => {
+                    if index != start && !is_unreachable_result(&result) {
+                        #[cfg(feature = "log")]
+                        info!("Failed over to {}", base_url);
+
+                        self.active.store(index, Ordering::Relaxed);
+                    }
+
+                    return result;
+                },
+            }
+        }
+    }
+}
+
+fn is_unreachable_result<T>(result: &Result<T>) -> bool {
+    match *result {
+        Err(ref why) => is_unreachable(why),
+        Ok(_) => false,
+    }
+}
+
+/// Whether an error is due to the service not being reachable, such as a
+/// failure to connect, as opposed to the service responding with an error.
+///
+/// Errors after connecting - such as the connection being reset - are not
+/// failed over from, as the service may have already handled the request.
+fn is_unreachable(error: &Error) -> bool {
+    match error.network_kind() {
+        Some(NetworkErrorKind::Connect)
+            | Some(NetworkErrorKind::Dns)
+            | Some(NetworkErrorKind::Tls) => true,
+        _ => false,
+    }
+}
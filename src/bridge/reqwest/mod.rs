@@ -6,13 +6,61 @@
 //!
 //! [`OwoRequester`]: trait.OwoRequester.html
 
-use reqwest::header::{Headers, UserAgent};
-use reqwest::multipart::{Form, Part};
-use reqwest::Client;
+mod builder;
+mod failover;
+mod retry;
+
+pub use self::builder::OwoClientBuilder;
+pub use self::retry::BackoffStrategy;
+
+use reqwest::header;
+use reqwest::{Client, Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
 use serde_json;
-use std::io::{Cursor, Read};
-use ::model::FileUploadResponse;
-use ::{Error, Result, constants};
+use std::convert::TryFrom;
+use std::env;
+use std::fs::{self, File};
+use std::io::{Error as IoError, Read};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use ::bridge::multipart::Multipart;
+use ::bridge::response::{
+    MAX_SNIPPET_LEN,
+    body_error,
+    html_response,
+    rate_limited,
+    snippet,
+    status_error,
+};
+use ::error::redact_key;
+use ::model::{FileUploadResponse, ShortenResponse, UploadedFile};
+use ::prepared::PreparedRequest;
+use ::secret::Secret;
+use self::failover::Failover;
+use self::retry::{RetryBudget, RetryPolicy};
+use ::Config;
+#[cfg(feature = "keyring")]
+use ::credentials;
+#[cfg(feature = "img-parts")]
+use ::metadata;
+use ::{
+    ApiErrorCode,
+    Error,
+    NetworkErrorKind,
+    Result,
+    ServiceLimits,
+    constants,
+    endpoints,
+};
+
+/// The maximum length of a response body which is read, in bytes.
+///
+/// Responses from the service are small, so longer ones fail rather than
+/// letting a misbehaving server use up memory. Only the snippet kept in the
+/// error of an unsuccessful deletion is read up to this and cut short.
+const MAX_BODY_LEN: u64 = 1024 * 1024;
 
 /// A light wrapper around a reqwest Client, containing the client and the
 /// key to use in requests.
@@ -22,18 +70,52 @@ use ::{Error, Result, constants};
 /// the best performance on memory, manage your own reqwest Client for re-use
 /// across multiple services and key.
 ///
+/// To configure the client, use an [`OwoClientBuilder`].
+///
+/// Cloning the client is cheap, as clones share the same underlying reqwest
+/// Client - and so the same connection pool - and the rest of the client's
+/// configuration, only copying the key. This makes it suitable for handing to
+/// multiple threads.
+///
 /// Refer to [`OwoRequester`] for more information.
 ///
+/// [`OwoClientBuilder`]: struct.OwoClientBuilder.html
 /// [`OwoRequester`]: trait.OwoRequester.html
+#[derive(Clone)]
 pub struct OwoClient {
+    inner: Arc<Inner>,
+    key: Secret,
+}
+
+/// The configuration of a client, shared between its clones so that cloning
+/// stays cheap however much configuration there is.
+///
+/// The key is kept outside of this, as clones may use other keys.
+struct Inner {
+    allow_unsuccessful: bool,
+    boundary: Option<String>,
     client: Client,
-    /// The key in use by the client.
-    pub key: String,
+    domain: String,
+    failover: Failover,
+    limits: ServiceLimits,
+    max_response_bytes: Option<usize>,
+    retry: RetryPolicy,
+    shorten_path: String,
+    upload_path: String,
 }
 
 impl OwoClient {
     /// Creates a new client.
     ///
+    /// The key is leniently only trimmed of surrounding whitespace, so that
+    /// one read from a file or the environment can be passed as-is. To reject
+    /// keys containing any whitespace instead, use the [`TryFrom`]
+    /// implementation.
+    ///
+    /// Creating a client is fallible regardless of the key - such as when the
+    /// `OWO_API_URL` environment variable is invalid - so this returns a
+    /// `Result` rather than there being an infallible `From` implementation.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
@@ -44,45 +126,70 @@ impl OwoClient {
     /// use owo::OwoReqwestClient;
     /// use std::env;
     ///
-    /// let client = OwoReqwestClient::new(env::var("OWO_KEY")?);
+    /// let client = OwoReqwestClient::new(env::var("OWO_KEY")?)?;
     /// #     Ok(())
     /// # }
     /// #
     /// # fn main() {
     /// #     try_main().unwrap();
     /// # }
-    pub fn new<S: Into<String>>(key: S) -> Self {
-        Self {
-            client: Client::new(),
-            key: key.into(),
-        }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming
+    /// surrounding whitespace.
+    ///
+    /// Returns [`Error::Config`] if the `OWO_API_URL` environment variable is
+    /// set, but is not an absolute `http` or `https` URL.
+    ///
+    /// Returns [`Error::Reqwest`] if there was an error building the
+    /// underlying `reqwest` Client.
+    ///
+    /// [`Error::Config`]: ../../enum.Error.html#variant.Config
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
+    /// [`TryFrom`]: #impl-TryFrom
+    pub fn new<S: Into<String>>(key: S) -> Result<Self> {
+        OwoClientBuilder::new(key).build()
     }
 
-    /// Shortcut for uploading a file.
+    /// Creates a new client which makes requests through an existing reqwest
+    /// Client, using the given key.
     ///
-    /// Refer to [`OwoRequester::upload_file`] for more information.
+    /// Creating a reqwest Client is comparatively expensive, as it sets up
+    /// TLS and a connection pool. Create one up front and share it - across
+    /// other services, and across threads by cloning it - rather than
+    /// creating one per upload. Clones of a reqwest Client share the same
+    /// connection pool.
+    ///
+    /// Settings of the client other than the key are left as their defaults.
+    /// Settings which configure the reqwest Client - such as
+    /// [`OwoClientBuilder::timeout`] - should be set on the reqwest Client
+    /// itself.
     ///
     /// # Examples
     ///
-    /// Upload a file from the CWD, using a key from the environment:
+    /// Share one reqwest Client between the client and another service:
     ///
     /// ```rust,no_run
     /// # use std::error::Error;
     /// #
-    /// # fn try_main() -> Result<(), Box<Error>> {
-    /// #
+    /// extern crate owo;
+    /// extern crate reqwest;
+    ///
     /// use owo::OwoReqwestClient;
+    /// use reqwest::Client;
     /// use std::env;
-    /// use std::fs::File;
-    /// use std::io::Read;
     ///
-    /// let client = OwoReqwestClient::new(env::var("OWO_KEY")?);
-    ///
-    /// let mut buffer = vec![];
-    /// let mut file = File::open("./file.png")?;
-    /// file.read_to_end(&mut buffer)?;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// let http = Client::new();
+    /// let key = env::var("OWO_KEY")?;
+    /// let owo = OwoReqwestClient::from_client(http.clone(), key)?;
     ///
-    /// println!("Response: {:?}", client.upload_file(buffer)?);
+    /// let status = http.get("https://example.com").send()?.status();
+    /// let short = owo.shorten_url("https://example.com")?;
     /// #     Ok(())
     /// # }
     /// #
@@ -93,46 +200,29 @@ impl OwoClient {
     ///
     /// # Errors
     ///
-    /// Returns an [`Error::Reqwest`] if building the request fails.
+    /// Returns the same errors as [`new`], other than [`Error::Reqwest`].
     ///
     /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
-    /// [`OwoRequester::upload_file`]: trait.OwoRequester.html#tymethod.upload_file
-    #[inline]
-    pub fn upload_file(&self, file: Vec<u8>) -> Result<FileUploadResponse> {
-        self.client.upload_file(&self.key, file)
+    /// [`OwoClientBuilder::timeout`]: struct.OwoClientBuilder.html#method.timeout
+    /// [`new`]: #method.new
+    pub fn from_client<S: Into<String>>(client: Client, key: S)
+        -> Result<Self> {
+        OwoClientBuilder::new(key).build_with(Some(client))
     }
 
-    /// Shortcut for uploading multiple files.
+    /// Creates a new client using the key from the `OWO_KEY` environment
+    /// variable.
     ///
-    /// Refer to [`OwoRequester::upload_files`] for more information.
+    /// As with [`new`], the `OWO_API_URL` environment variable overrides the
+    /// base URL of the API if present.
     ///
     /// # Examples
     ///
-    /// Upload two files from the CWD, using a key from the environment:
-    ///
     /// ```rust,no_run
-    /// # use std::error::Error;
-    /// #
-    /// # fn try_main() -> Result<(), Box<Error>> {
-    /// #
+    /// # fn try_main() -> owo::Result<()> {
     /// use owo::OwoReqwestClient;
-    /// use std::env;
-    /// use std::fs::File;
-    /// use std::io::Read;
-    ///
-    /// let client = OwoReqwestClient::new(env::var("OWO_KEY")?);
-    ///
-    /// let mut buffer1 = vec![];
-    /// let mut file1 = File::open("./file1.png")?;
-    /// file1.read_to_end(&mut buffer1)?;
-    ///
-    /// let mut buffer2 = vec![];
-    /// let mut file2 = File::open("./file2.png")?;
-    /// file2.read_to_end(&mut buffer2)?;
     ///
-    /// let buffers = vec![buffer1, buffer2];
-    ///
-    /// println!("Response: {:?}", client.upload_files(buffers));
+    /// let client = OwoReqwestClient::from_env()?;
     /// #     Ok(())
     /// # }
     /// #
@@ -143,24 +233,34 @@ impl OwoClient {
     ///
     /// # Errors
     ///
-    /// Returns an [`Error::Reqwest`] if building the request fails.
+    /// Returns [`Error::Config`] if the `OWO_KEY` environment variable is not
+    /// present or is not valid unicode.
     ///
-    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
-    /// [`OwoRequester::upload_files`]: trait.OwoRequester.html#tymethod.upload_files
-    #[inline]
-    pub fn upload_files(&self, files: Vec<Vec<u8>>)
-        -> Result<FileUploadResponse> {
-        self.client.upload_files(&self.key, files)
+    /// Otherwise, returns the same errors as [`new`].
+    ///
+    /// [`Error::Config`]: ../../enum.Error.html#variant.Config
+    /// [`new`]: #method.new
+    pub fn from_env() -> Result<Self> {
+        let key = env::var("OWO_KEY").map_err(|why| Error::Config {
+            field: "OWO_KEY".to_owned(),
+            reason: why.to_string(),
+        })?;
+
+        Self::new(key)
     }
 
-    /// Shortcut for shortening a URL.
+    /// Creates a new client from the settings in a TOML configuration file.
     ///
-    /// Refer to [`OwoRequester::upload_files`] for more information.
+    /// Refer to [`Config`] for the supported fields. To change settings not
+    /// supported by configuration files, parse the file via
+    /// [`Config::from_toml`] and then turn it into an [`OwoClientBuilder`].
     ///
-    /// # Examples
+    /// Refer to [`from_settings`] for creating a client from a config read by
+    /// other means, such as from an application's own config file.
     ///
-    /// Shorten the URL `"https://google.com"`, using a key from the
-    /// environment:
+    /// This is only available with the `config` feature.
+    ///
+    /// # Examples
     ///
     /// ```rust,no_run
     /// # use std::error::Error;
@@ -168,11 +268,8 @@ impl OwoClient {
     /// # fn try_main() -> Result<(), Box<Error>> {
     /// #
     /// use owo::OwoReqwestClient;
-    /// use std::env;
-    ///
-    /// let client = OwoReqwestClient::new(env::var("OWO_KEY")?);
     ///
-    /// println!("Response: {:?}", client.shorten_url("https://google.com")?);
+    /// let client = OwoReqwestClient::from_config("./owo.toml")?;
     /// #     Ok(())
     /// # }
     /// #
@@ -181,70 +278,93 @@ impl OwoClient {
     /// # }
     /// ```
     ///
-    /// [`OwoRequester::upload_files`]: trait.OwoRequester.html#tymethod.upload_files
-    #[inline]
-    pub fn shorten_url(&self, url: &str) -> Result<String> {
-        self.client.shorten_url(&self.key, url)
+    /// # Errors
+    ///
+    /// Returns [`Error::File`] if the file could not be read.
+    ///
+    /// Returns [`Error::Toml`] if the file is not valid TOML, or
+    /// [`Error::Config`] if a field is missing or invalid.
+    ///
+    /// Otherwise, returns the same errors as [`from_settings`].
+    ///
+    /// [`Config`]: ../../config/struct.Config.html
+    /// [`Config::from_toml`]: ../../config/struct.Config.html#method.from_toml
+    /// [`Error::Config`]: ../../enum.Error.html#variant.Config
+    /// [`Error::File`]: ../../enum.Error.html#variant.File
+    /// [`Error::Toml`]: ../../enum.Error.html#variant.Toml
+    /// [`OwoClientBuilder`]: struct.OwoClientBuilder.html
+    /// [`from_settings`]: #method.from_settings
+    #[cfg(feature = "toml")]
+    pub fn from_config<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let mut contents = String::new();
+        File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut contents))
+            .map_err(|why| Error::File {
+                path: path.to_owned(),
+                error: why,
+            })?;
+
+        Self::from_settings(Config::from_toml(&contents)?)
     }
-}
 
-/// Trait which defines the methods necessary to interact with the service.
-///
-/// # Examples
-///
-/// To bring in the implementation for the `reqwest` Client, simply use the
-/// trait:
-///
-/// ```rust,no_run
-/// use owo::OwoReqwestRequester;
-/// ```
-///
-/// At this point, the methods will be on your Reqwest Client.
-pub trait OwoRequester {
-    /// Uploads a single file to the service.
+    /// Creates a new client from a config, such as one deserialized from a
+    /// section of an application's own config file.
     ///
-    /// # Examples
+    /// Unlike turning the config into an [`OwoClientBuilder`], this reads the
+    /// key from the environment if the config names a variable via
+    /// [`Config::key_var`] rather than containing the key itself.
     ///
-    /// Upload a file by its filepath as a string taken from user input, with a
-    /// key taken from an environment variable:
+    /// # Examples
     ///
     /// ```rust,no_run
-    /// # use std::error::Error;
-    /// #
-    /// extern crate owo;
-    /// extern crate reqwest;
+    /// # fn try_main() -> owo::Result<()> {
+    /// use owo::{Config, OwoReqwestClient};
     ///
-    /// use owo::OwoReqwestRequester;
-    /// use reqwest::Client;
-    /// use std::fs::File;
-    /// use std::io::{self, Read, Write};
-    /// use std::env;
+    /// let mut config = Config::new("");
+    /// config.key_var = Some("OWO_KEY".to_owned());
     ///
-    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// let client = OwoReqwestClient::from_settings(config)?;
+    /// #     Ok(())
+    /// # }
     /// #
-    /// // Read a filepath to upload from user input:
-    /// let mut input = String::new();
-    /// print!("Enter the path on the filesystem to upload:\n>");
-    /// io::stdout().flush()?;
-    /// io::stdin().read_to_string(&mut input)?;
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
     ///
-    /// // Read the content of the file to a buffer.
-    /// let mut file = File::open(&input)?;
-    /// let mut buffer = vec![];
-    /// file.read_to_end(&mut buffer)?;
+    /// # Errors
     ///
-    /// // Retrieve the key from an environment variable named "OWO_KEY".
-    /// let key = env::var("OWO_KEY")?;
+    /// Returns [`Error::Config`] if the key could not be resolved, as
+    /// described by [`Config::resolve_key`].
     ///
-    /// // Create the reqwest Client.
-    /// //
-    /// // In this case, we're using the client for a oneshot request, but in
-    /// // normal cases a client can be re-used.
-    /// let client = Client::new();
+    /// Otherwise, returns the same errors as [`OwoClientBuilder::build`].
     ///
-    /// let response = client.upload_file(&key, buffer);
+    /// [`Config::key_var`]: ../../config/struct.Config.html#structfield.key_var
+    /// [`Config::resolve_key`]: ../../config/struct.Config.html#method.resolve_key
+    /// [`Error::Config`]: ../../enum.Error.html#variant.Config
+    /// [`OwoClientBuilder`]: struct.OwoClientBuilder.html
+    /// [`OwoClientBuilder::build`]: struct.OwoClientBuilder.html#method.build
+    pub fn from_settings(mut config: Config) -> Result<Self> {
+        config.key = config.resolve_key()?;
+
+        OwoClientBuilder::from(config).build()
+    }
+
+    /// Creates a new client using the key stored in the operating system's
+    /// credential store for the given service and account.
     ///
-    /// println!("Response: {:?}", response);
+    /// Refer to the [`credentials`] module for storing the key.
+    ///
+    /// This is only available with the `keyring` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # fn try_main() -> owo::Result<()> {
+    /// use owo::OwoReqwestClient;
+    ///
+    /// let client = OwoReqwestClient::from_keyring("owo", "alice")?;
     /// #     Ok(())
     /// # }
     /// #
@@ -255,154 +375,2537 @@ pub trait OwoRequester {
     ///
     /// # Errors
     ///
-    /// Returns an [`Error::Reqwest`] if building the request fails.
+    /// Returns [`Error::KeyNotFound`] if no key is stored for the service and
+    /// account, or [`Error::Keyring`] if the credential store could not be
+    /// accessed.
     ///
-    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
-    fn upload_file(&self, key: &str, file: Vec<u8>)
-        -> Result<FileUploadResponse>;
-
-    /// Uploads an array of files to the service, one-by-one.
+    /// Otherwise, returns the same errors as [`new`].
     ///
-    /// # Examples
+    /// [`Error::KeyNotFound`]: ../../enum.Error.html#variant.KeyNotFound
+    /// [`Error::Keyring`]: ../../enum.Error.html#variant.Keyring
+    /// [`credentials`]: ../../credentials/index.html
+    /// [`new`]: #method.new
+    #[cfg(feature = "keyring")]
+    pub fn from_keyring(service: &str, account: &str) -> Result<Self> {
+        Self::new(credentials::load_key(service, account)?)
+    }
+
+    /// Retrieves the key in use by the client.
+    pub fn key(&self) -> &str {
+        self.key.as_str()
+    }
+
+    /// Retrieves the base URL of the API which requests are made to.
     ///
-    /// Upload two files to the service, one as a slice of bytes and the other
-    /// as a path on the filesystem, using a variable from the environment:
+    /// If mirrors were configured, this is the base URL which most recently
+    /// responded. Otherwise, defaults to [`constants::API_URL`].
     ///
-    /// ```rust,no_run
-    /// # use std::error::Error;
-    /// #
-    /// extern crate owo;
-    /// extern crate reqwest;
+    /// [`constants::API_URL`]: ../../constants/constant.API_URL.html
+    pub fn base_url(&self) -> &str {
+        self.inner.failover.active()
+    }
+
+    /// Retrieves the fixed boundary used to separate the parts of multipart
+    /// upload bodies, if one was configured.
     ///
-    /// use owo::OwoReqwestRequester;
-    /// use reqwest::Client;
-    /// use std::env;
-    /// use std::fs::File;
-    /// use std::io::Read;
+    /// If none was configured, a unique boundary is generated for each upload.
+    pub fn boundary(&self) -> Option<&str> {
+        self.inner.boundary.as_deref()
+    }
+
+    /// Retrieves the path of the endpoint to shorten URLs, relative to the
+    /// [`base_url`].
     ///
-    /// # fn try_main() -> Result<(), Box<Error>> {
-    /// #
-    /// let key = env::var("OWO_KEY")?;
-    /// let client = Client::new();
+    /// Defaults to [`constants::SHORTEN_PATH`].
     ///
-    /// let mut cat1 = File::open("/mnt/media/images/cat1.png")?;
-    /// let mut buffer1 = vec![];
-    /// cat1.read_to_end(&mut buffer1)?;
+    /// [`base_url`]: #method.base_url
+    /// [`constants::SHORTEN_PATH`]: ../../constants/constant.SHORTEN_PATH.html
+    pub fn shorten_path(&self) -> &str {
+        &self.inner.shorten_path
+    }
+
+    /// Retrieves the path of the endpoint to upload files, relative to the
+    /// [`base_url`].
     ///
-    /// let mut cat2 = File::open("/mnt/media/images/cat2.png")?;
-    /// let mut buffer2 = vec![];
-    /// cat2.read_to_end(&mut buffer2)?;
+    /// Defaults to [`constants::UPLOAD_PATH`].
     ///
-    /// let responses = client.upload_files(&key, vec![buffer1, buffer2]);
+    /// [`base_url`]: #method.base_url
+    /// [`constants::UPLOAD_PATH`]: ../../constants/constant.UPLOAD_PATH.html
+    pub fn upload_path(&self) -> &str {
+        &self.inner.upload_path
+    }
+
+    /// Retrieves the limits of the service which requests are checked against
+    /// before being made.
+    pub fn limits(&self) -> &ServiceLimits {
+        &self.inner.limits
+    }
+
+    /// Retrieves the domain which uploaded files are linked to via
+    /// [`full_url`].
     ///
-    /// for (idx, response) in responses.iter().enumerate() {
-    ///     println!("#{} response: {:?}", idx, response);
-    /// }
-    /// #     Ok(())
-    /// # }
-    /// #
-    /// # fn main() {
-    /// #     try_main().unwrap();
-    /// # }
-    /// ```
+    /// Defaults to [`constants::DEFAULT_DOMAIN`].
     ///
-    /// # Errors
+    /// [`constants::DEFAULT_DOMAIN`]: ../../constants/constant.DEFAULT_DOMAIN.html
+    /// [`full_url`]: #method.full_url
+    pub fn domain(&self) -> &str {
+        &self.inner.domain
+    }
+
+    /// Produces the full URL to an uploaded file, on the client's [`domain`].
     ///
-    /// Returns an [`Error::Reqwest`] if building the request fails.
+    /// If the service returned a full URL rather than a fragment, it is
+    /// returned unchanged.
     ///
-    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
-    fn upload_files(&self, key: &str, files: Vec<Vec<u8>>)
-        -> Result<FileUploadResponse>;
+    /// [`domain`]: #method.domain
+    pub fn full_url(&self, file: &UploadedFile) -> String {
+        file.full_url_on(&self.inner.domain)
+    }
 
-    /// Shortens a URL via the service, returning a URL to the shortened link.
+    /// Computes the number of bytes the client sends as the body of a request
+    /// uploading the files, without sending it.
     ///
-    /// # Examples
+    /// This includes the multipart encoding around each file, so is larger
+    /// than the files themselves. To verify an upload, compare the length of
+    /// each local file with the [`size`] the service reports for it instead.
     ///
-    /// Shorten the URL `"https://google.com"` via the service, using an
-    /// environment variable for the key:
+    /// # Examples
     ///
     /// ```rust,no_run
     /// # use std::error::Error;
     /// #
-    /// extern crate owo;
-    /// extern crate reqwest;
-    ///
-    /// use owo::OwoReqwestRequester;
-    /// use reqwest::Client;
-    /// use std::env;
-    ///
     /// # fn try_main() -> Result<(), Box<Error>> {
     /// #
-    /// let key = env::var("OWO_KEY")?;
-    /// let client = Client::new();
+    /// use owo::OwoReqwestClient;
+    /// use std::env;
     ///
-    /// let url_to_shorten = "https://google.com";
+    /// let client = OwoReqwestClient::new(env::var("OWO_KEY")?)?;
+    /// let file = b"hello".to_vec();
     ///
-    /// let url = client.shorten_url(&key, url_to_shorten)?;
+    /// println!("Sending {} bytes", client.upload_body_len(&[&file[..]]));
     ///
-    /// println!("url: {}", url);
+    /// let response = client.upload_file(file.clone())?;
+    /// assert_eq!(response[0].size, file.len() as u64);
     /// #     Ok(())
     /// # }
     /// #
     /// # fn main() {
     /// #     try_main().unwrap();
     /// # }
-    fn shorten_url(&self, key: &str, url: &str) -> Result<String>;
-}
-
-impl OwoRequester for Client {
-    fn upload_file(&self, key: &str, file: Vec<u8>)
-        -> Result<FileUploadResponse> {
-        let uri = format!("https://api.awau.moe/upload/pomf?key={}", key);
-
-        let part = Part::reader(Cursor::new(file));
-        let form = Form::new().part("files[]", part);
-
-        upload(self, &uri, form)
-    }
-
-    fn upload_files(&self, key: &str, files: Vec<Vec<u8>>)
-        -> Result<FileUploadResponse> {
-        // Check that the number of requested files to upload is not too many.
-        if files.len() > constants::MAX_FILES {
-            return Err(Error::TooManyFiles);
-        }
-
-        let uri = format!("https://api.awau.moe/upload/pomf?key={}", key);
-
-        let mut form = Form::new();
-
-        for file in files {
-            form = form.part("files[]", Part::reader(Cursor::new(file)));
-        }
+    /// ```
+    ///
+    /// [`size`]: ../../model/struct.UploadedFile.html#structfield.size
+    pub fn upload_body_len(&self, files: &[&[u8]]) -> u64 {
+        let parts = files.iter()
+            .map(|file| (None, *file))
+            .collect::<Vec<_>>();
 
-        upload(self, &uri, form)
+        Multipart::with_boundary(self.boundary()).body_len("files[]", &parts)
     }
 
-    fn shorten_url(&self, key: &str, url: &str) -> Result<String> {
-        let uri = format!(
-            "https://api.awau.moe/shorten/polr?action=shorten&url={}&key={}",
+    /// Builds the request which [`upload_files`] would send for the files,
+    /// without sending it.
+    ///
+    /// The returned [`PreparedRequest`] describes the method, URL, headers
+    /// and body of the request, which allows checking an integration - such
+    /// as in unit tests - without a server. The key is redacted from the URL.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn try_main() -> owo::Result<()> {
+    /// use owo::OwoReqwestClient;
+    ///
+    /// let client = OwoReqwestClient::new("my key")?;
+    /// let request = client.prepare_upload_files(&[b"hello".to_vec()])?;
+    ///
+    /// assert_eq!(request.method, "POST");
+    /// assert!(request.body_len() > 5);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TooManyFiles`] or [`Error::FileTooLarge`] if the files
+    /// exceed the client's [`limits`].
+    ///
+    /// [`Error::FileTooLarge`]: ../../enum.Error.html#variant.FileTooLarge
+    /// [`Error::TooManyFiles`]: ../../enum.Error.html#variant.TooManyFiles
+    /// [`PreparedRequest`]: ../../prepared/struct.PreparedRequest.html
+    /// [`limits`]: struct.OwoClientBuilder.html#method.limits
+    /// [`upload_files`]: #method.upload_files
+    pub fn prepare_upload_files(&self, files: &[Vec<u8>])
+        -> Result<PreparedRequest> {
+        self.inner.limits.check_files(files)?;
+
+        let uri = endpoints::upload_at(
+            self.base_url(),
+            &self.inner.upload_path,
+            self.key(),
+        );
+        let multipart = Multipart::with_boundary(self.boundary());
+        let mut request = self.prepared("POST", &uri);
+        request.headers.push((
+            "Content-Type".to_owned(),
+            multipart.content_type(),
+        ));
+        request.body = multipart_body(&multipart, &unnamed(files));
+
+        Ok(request)
+    }
+
+    /// Builds the request which [`shorten_url`] would send for the URL,
+    /// without sending it.
+    ///
+    /// The key is redacted from the URL of the returned
+    /// [`PreparedRequest`], which has no body.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UrlTooLong`] if the URL exceeds the client's
+    /// [`limits`].
+    ///
+    /// [`Error::UrlTooLong`]: ../../enum.Error.html#variant.UrlTooLong
+    /// [`PreparedRequest`]: ../../prepared/struct.PreparedRequest.html
+    /// [`limits`]: struct.OwoClientBuilder.html#method.limits
+    /// [`shorten_url`]: #method.shorten_url
+    pub fn prepare_shorten_url(&self, url: &str) -> Result<PreparedRequest> {
+        self.inner.limits.check_url(url)?;
+
+        let uri = endpoints::shorten_at(
+            self.base_url(),
+            &self.inner.shorten_path,
+            self.key(),
             url,
-            key,
         );
 
-        let mut headers = Headers::new();
-        headers.set(UserAgent::new(constants::USER_AGENT));
-        let mut response = self.get(&uri).headers(headers).send()?;
-        let mut buffer = String::new();
-        response.read_to_string(&mut buffer)?;
+        Ok(self.prepared("GET", &uri))
+    }
+
+    /// Replaces the key in use by the client.
+    ///
+    /// The key is trimmed of surrounding whitespace. With the `zeroize`
+    /// feature enabled, the old key is wiped from memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty, in which case the
+    /// old key is kept.
+    ///
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    pub fn set_key<S: Into<String>>(&mut self, key: S) -> Result<()> {
+        self.key = Secret::key(key.into())?;
+
+        Ok(())
+    }
+
+    /// Creates a new client which shares this client's underlying reqwest
+    /// Client - and so its connection pool - but uses a different key.
+    ///
+    /// This is useful when making requests on behalf of many users, each with
+    /// their own key, as a single pool of connections can serve all of them.
+    ///
+    /// The key is trimmed of surrounding whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use owo::OwoReqwestClient;
+    /// #
+    /// # fn tenants(client: OwoReqwestClient) -> owo::Result<()> {
+    /// let alice = client.clone_with_key("alice's key")?;
+    /// let bob = client.clone_with_key("bob's key")?;
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty.
+    ///
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    pub fn clone_with_key<S: Into<String>>(&self, key: S) -> Result<Self> {
+        Ok(Self {
+            inner: Arc::clone(&self.inner),
+            key: Secret::key(key.into())?,
+        })
+    }
+
+    /// Establishes a connection to the API ahead of time, so that the first
+    /// real request does not have to wait on DNS resolution and the TCP and
+    /// TLS handshakes.
+    ///
+    /// This makes a `HEAD` request to the [`base_url`], which does not use the
+    /// client's key. Any response from the service - whatever its status - is
+    /// considered a success, with the connection being kept in the client's
+    /// pool. As such, this is cheap to call repeatedly, such as whenever an
+    /// application regains focus.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # fn try_main() -> owo::Result<()> {
+    /// use owo::OwoReqwestClient;
+    ///
+    /// let client = OwoReqwestClient::from_env()?;
+    ///
+    /// // Surface network problems at startup, rather than on first upload.
+    /// client.warm_up()?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Reqwest`] if a connection to the service could not be
+    /// established.
+    ///
+    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
+    /// [`base_url`]: #method.base_url
+    pub fn warm_up(&self) -> Result<()> {
+        self.inner.failover.run(|base_url| {
+            self.inner.client
+                .head(base_url)
+                .header(header::USER_AGENT, constants::USER_AGENT)
+                .send()?;
+
+            Ok(())
+        })
+    }
+
+    /// Shortcut for uploading a file.
+    ///
+    /// Refer to [`OwoRequester::upload_file`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Upload a file from the CWD, using a key from the environment:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::OwoReqwestClient;
+    /// use std::env;
+    /// use std::fs::File;
+    /// use std::io::Read;
+    ///
+    /// let client = OwoReqwestClient::new(env::var("OWO_KEY")?)?;
+    ///
+    /// let mut buffer = vec![];
+    /// let mut file = File::open("./file.png")?;
+    /// file.read_to_end(&mut buffer)?;
+    ///
+    /// println!("Response: {:?}", client.upload_file(buffer)?);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Reqwest`] if building the request fails.
+    ///
+    /// Returns [`Error::InvalidKey`] if the service rejected the key, or
+    /// [`Error::Api`] if it otherwise rejected the request.
+    ///
+    /// Returns [`Error::Status`] if the service otherwise responded with an
+    /// unsuccessful status.
+    ///
+    /// Returns [`Error::NoFilesReturned`] if the service reported the upload
+    /// as successful but returned no files.
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::NoFilesReturned`]: ../../enum.Error.html#variant.NoFilesReturned
+    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
+    /// [`Error::Status`]: ../../enum.Error.html#variant.Status
+    /// [`OwoRequester::upload_file`]: trait.OwoRequester.html#tymethod.upload_file
+    #[inline]
+    pub fn upload_file(&self, file: Vec<u8>) -> Result<FileUploadResponse> {
+        self.upload_file_as(file)
+            .and_then(|response| self.check_single(response))
+    }
+
+    /// Shortcut for uploading a file, deserializing the response into a
+    /// type of your choosing.
+    ///
+    /// Refer to [`OwoRequester::upload_file_as`] for more information.
+    ///
+    /// [`OwoRequester::upload_file_as`]: trait.OwoRequester.html#tymethod.upload_file_as
+    pub fn upload_file_as<T>(&self, file: Vec<u8>) -> Result<T>
+        where T: DeserializeOwned {
+        let files = [file];
+        self.inner.limits.check_files(&files)?;
+
+        self.inner.failover.run(|base_url| {
+            let uri = endpoints::upload_at(
+                base_url,
+                &self.inner.upload_path,
+                self.key(),
+            );
+            self.send_upload(uri, &unnamed(&files))
+        })
+    }
+
+    /// Shortcut for uploading a file which expires after the given duration.
+    ///
+    /// Refer to [`OwoRequester::upload_file_with_expiry`] for more
+    /// information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Reqwest`] if building the request fails.
+    ///
+    /// Returns [`Error::InvalidKey`] if the service rejected the key, or
+    /// [`Error::Api`] if it otherwise rejected the request.
+    ///
+    /// Returns [`Error::Status`] if the service otherwise responded with an
+    /// unsuccessful status.
+    ///
+    /// Returns [`Error::NoFilesReturned`] if the service reported the upload
+    /// as successful but returned no files.
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::NoFilesReturned`]: ../../enum.Error.html#variant.NoFilesReturned
+    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
+    /// [`Error::Status`]: ../../enum.Error.html#variant.Status
+    /// [`OwoRequester::upload_file_with_expiry`]: trait.OwoRequester.html#tymethod.upload_file_with_expiry
+    #[inline]
+    pub fn upload_file_with_expiry(&self, file: Vec<u8>, expiry: Duration)
+        -> Result<FileUploadResponse> {
+        let files = [file];
+        self.inner.limits.check_files(&files)?;
+
+        let response = self.inner.failover.run(|base_url| {
+            let uri = endpoints::upload_with_expiry_at(
+                base_url,
+                &self.inner.upload_path,
+                self.key(),
+                expiry,
+            );
+            self.send_upload(uri, &unnamed(&files))
+        })?;
+
+        self.check_single(response)
+    }
+
+    /// Shortcut for uploading multiple files.
+    ///
+    /// Refer to [`OwoRequester::upload_files`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Upload two files from the CWD, using a key from the environment:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::OwoReqwestClient;
+    /// use std::env;
+    /// use std::fs::File;
+    /// use std::io::Read;
+    ///
+    /// let client = OwoReqwestClient::new(env::var("OWO_KEY")?)?;
+    ///
+    /// let mut buffer1 = vec![];
+    /// let mut file1 = File::open("./file1.png")?;
+    /// file1.read_to_end(&mut buffer1)?;
+    ///
+    /// let mut buffer2 = vec![];
+    /// let mut file2 = File::open("./file2.png")?;
+    /// file2.read_to_end(&mut buffer2)?;
+    ///
+    /// let buffers = vec![buffer1, buffer2];
+    ///
+    /// println!("Response: {:?}", client.upload_files(buffers));
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Reqwest`] if building the request fails.
+    ///
+    /// Returns [`Error::InvalidKey`] if the service rejected the key, or
+    /// [`Error::Api`] if it otherwise rejected the request.
+    ///
+    /// Returns [`Error::Status`] if the service otherwise responded with an
+    /// unsuccessful status.
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
+    /// [`Error::Status`]: ../../enum.Error.html#variant.Status
+    /// [`OwoRequester::upload_files`]: trait.OwoRequester.html#tymethod.upload_files
+    #[inline]
+    pub fn upload_files(&self, files: Vec<Vec<u8>>)
+        -> Result<FileUploadResponse> {
+        self.inner.limits.check_files(&files)?;
+
+        self.inner.failover.run(|base_url| {
+            let uri = endpoints::upload_at(
+                base_url,
+                &self.inner.upload_path,
+                self.key(),
+            );
+            self.send_upload(uri, &unnamed(&files))
+        }).and_then(|response| self.check_success(response))
+    }
+
+    /// Shortcut for uploading an image with its metadata removed.
+    ///
+    /// Refer to [`OwoRequester::upload_image_stripped`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Reqwest`] if building the request fails.
+    ///
+    /// Returns [`Error::InvalidKey`] if the service rejected the key, or
+    /// [`Error::Api`] if it otherwise rejected the request.
+    ///
+    /// Returns [`Error::Status`] if the service otherwise responded with an
+    /// unsuccessful status.
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
+    /// [`Error::Status`]: ../../enum.Error.html#variant.Status
+    /// [`OwoRequester::upload_image_stripped`]: trait.OwoRequester.html#tymethod.upload_image_stripped
+    #[cfg(feature = "img-parts")]
+    #[inline]
+    pub fn upload_image_stripped(&self, image: Vec<u8>)
+        -> Result<FileUploadResponse> {
+        self.upload_file(metadata::strip(image))
+    }
+
+    /// Shortcut for downloading the file at a remote URL and uploading it to
+    /// the service.
+    ///
+    /// Downloading stops as soon as the file exceeds the client's
+    /// [`limits`].
+    ///
+    /// Refer to [`OwoRequester::mirror_url`] for more information.
+    ///
+    /// [`OwoRequester::mirror_url`]: trait.OwoRequester.html#tymethod.mirror_url
+    /// [`limits`]: #method.limits
+    pub fn mirror_url(&self, remote_url: &str) -> Result<FileUploadResponse> {
+        let inner = &self.inner;
+        let file = download(&inner.client, remote_url, &inner.limits)?;
+        let filename = mirror_filename(remote_url);
+
+        self.upload_named(filename.as_deref(), &file)
+    }
+
+    /// Shortcut for deleting an uploaded file.
+    ///
+    /// Refer to [`OwoRequester::delete_file`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Upload a file, and then delete it again:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::OwoReqwestClient;
+    /// use std::env;
+    ///
+    /// let client = OwoReqwestClient::new(env::var("OWO_KEY")?)?;
+    ///
+    /// let response = client.upload_file(b"hello".to_vec())?;
+    ///
+    /// if let Some(ref delete_url) = response[0].delete_url {
+    ///     client.delete_file(delete_url)?;
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`OwoRequester::delete_file`]: trait.OwoRequester.html#tymethod.delete_file
+    #[inline]
+    pub fn delete_file(&self, delete_url: &str) -> Result<()> {
+        self.inner.client.delete_file(delete_url)
+    }
+
+    /// Uploads each of the given files in its own request, one-by-one.
+    ///
+    /// Unlike [`upload_files`], there is no limit on the number of files, and
+    /// the result of each upload is returned separately - in the same order as
+    /// the files - so that one failed upload does not fail the others.
+    ///
+    /// Failed uploads are retried according to the [`retries`] and
+    /// [`retry_budget`] the client was built with, with the budget shared
+    /// across all of the files.
+    ///
+    /// The uploads are made sequentially by design, as the client is
+    /// blocking: each waits for the previous one - including its retries -
+    /// to finish, which also keeps within the service's rate limits. To
+    /// upload several files at once, use the hyper bridge's
+    /// `OwoClient::upload_files_stream`, which takes a limit on the number of
+    /// concurrent uploads.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::OwoReqwestClientBuilder;
+    /// use std::env;
+    ///
+    /// let client = OwoReqwestClientBuilder::new(env::var("OWO_KEY")?)
+    ///     .retries(2)
+    ///     .retry_budget(10)
+    ///     .build()?;
+    ///
+    /// # let files = vec![];
+    /// #
+    /// for (idx, result) in client.upload_all(files).iter().enumerate() {
+    ///     println!("#{} result: {:?}", idx, result);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`retries`]: struct.OwoClientBuilder.html#method.retries
+    /// [`retry_budget`]: struct.OwoClientBuilder.html#method.retry_budget
+    /// [`upload_files`]: #method.upload_files
+    #[must_use = "failures of the batch are only reported in the results"]
+    pub fn upload_all(&self, files: Vec<Vec<u8>>)
+        -> Vec<Result<FileUploadResponse>> {
+        let mut budget = self.inner.retry.start();
+
+        files.into_iter().map(|file| {
+            self.inner.limits.check_file_size(file.len() as u64)?;
+
+            budget.run(|| self.upload_named(None, &file))
+        }).collect()
+    }
+
+    /// Uploads each file directly within a directory, keeping the name of
+    /// each file.
+    ///
+    /// Subdirectories are skipped; use [`upload_dir_recursive`] to upload
+    /// their files too.
+    ///
+    /// The files are uploaded in batches, each within the client's
+    /// [`limits`] on the number of files per request and - taking the
+    /// maximum file size as that of a request - their total size. If the
+    /// service rejects a batch for being too large, its files are uploaded
+    /// one-by-one instead, while other failures of a batch are returned for
+    /// each of its files as an [`Error::Batch`].
+    ///
+    /// As with [`upload_all`], the batches are uploaded sequentially, the
+    /// result of each upload is returned separately - alongside the path of
+    /// the file, sorted by path - and failed uploads are retried according to
+    /// the client's retries. Files larger than the client's [`limits`] fail
+    /// with [`Error::FileTooLarge`] without being read, and files which can't
+    /// be read fail with [`Error::File`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::OwoReqwestClient;
+    /// use std::env;
+    ///
+    /// let client = OwoReqwestClient::new(env::var("OWO_KEY")?)?;
+    ///
+    /// for (path, result) in client.upload_dir("screenshots")? {
+    ///     match result {
+    ///         Ok(response) => println!("{}: {}", path.display(), response),
+    ///         Err(why) => println!("{}: {}", path.display(), why),
+    ///     }
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::File`] if the directory can't be read.
+    ///
+    /// [`Error::Batch`]: ../../enum.Error.html#variant.Batch
+    /// [`Error::File`]: ../../enum.Error.html#variant.File
+    /// [`Error::FileTooLarge`]: ../../enum.Error.html#variant.FileTooLarge
+    /// [`limits`]: #method.limits
+    /// [`upload_all`]: #method.upload_all
+    /// [`upload_dir_recursive`]: #method.upload_dir_recursive
+    pub fn upload_dir<P: AsRef<Path>>(&self, dir: P)
+        -> Result<Vec<(PathBuf, Result<FileUploadResponse>)>> {
+        self.upload_paths(dir_files(dir.as_ref(), false)?)
+    }
+
+    /// Uploads each file within a directory and its subdirectories, keeping
+    /// the name of each file.
+    ///
+    /// Symbolic links to directories are not followed, so that a link cycle
+    /// can't make this run forever.
+    ///
+    /// Refer to [`upload_dir`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::File`] if the directory or one of its subdirectories
+    /// can't be read.
+    ///
+    /// [`Error::File`]: ../../enum.Error.html#variant.File
+    /// [`upload_dir`]: #method.upload_dir
+    pub fn upload_dir_recursive<P: AsRef<Path>>(&self, dir: P)
+        -> Result<Vec<(PathBuf, Result<FileUploadResponse>)>> {
+        self.upload_paths(dir_files(dir.as_ref(), true)?)
+    }
+
+    /// Uploads the files at the given paths in batches within the client's
+    /// limits on the number and total size of files per request, sharing a
+    /// retry budget.
+    fn upload_paths(&self, paths: Vec<PathBuf>)
+        -> Result<Vec<(PathBuf, Result<FileUploadResponse>)>> {
+        let limits = &self.inner.limits;
+        let mut budget = self.inner.retry.start();
+        let mut results = Vec::with_capacity(paths.len());
+        let mut batch = vec![];
+        let mut batch_size = 0;
+
+        for path in paths {
+            let size = match self.file_size(&path) {
+                Ok(size) => size,
+                Err(why) => {
+                    results.push((path, Err(why)));
+
+                    continue;
+                },
+            };
+            let full = limits.max_files
+                .map_or(false, |limit| batch.len() >= limit);
+            let too_large = limits.max_file_size
+                .map_or(false, |limit| batch_size + size > limit);
+
+            if !batch.is_empty() && (full || too_large) {
+                let paths = mem::take(&mut batch);
+                results.extend(self.upload_batch(paths, &mut budget));
+                batch_size = 0;
+            }
+
+            batch.push(path);
+            batch_size += size;
+        }
+
+        if !batch.is_empty() {
+            results.extend(self.upload_batch(batch, &mut budget));
+        }
+
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Ok(results)
+    }
+
+    /// Uploads the files at the given paths in one request, splitting the
+    /// response into one for each file.
+    ///
+    /// If the service rejected the request for being too large - having
+    /// stored none of the files - they're instead uploaded one-by-one. Any
+    /// other failure of the request is shared by each of the files as an
+    /// [`Error::Batch`], and a response which doesn't contain a file for each
+    /// of the files is an [`Error::UnexpectedResponse`] for each of them, as
+    /// the files may have been stored regardless.
+    ///
+    /// [`Error::Batch`]: ../../enum.Error.html#variant.Batch
+    /// [`Error::UnexpectedResponse`]: ../../enum.Error.html#variant.UnexpectedResponse
+    fn upload_batch(&self, paths: Vec<PathBuf>, budget: &mut RetryBudget)
+        -> Vec<(PathBuf, Result<FileUploadResponse>)> {
+        let mut results = vec![];
+        let mut files = vec![];
+
+        for path in paths {
+            match self.read_file(&path) {
+                Ok(file) => files.push((path, file)),
+                Err(why) => results.push((path, Err(why))),
+            }
+        }
+
+        if files.len() > 1 {
+            let result = {
+                let parts = files.iter()
+                    .map(|(path, file)| (path_filename(path), &file[..]))
+                    .collect::<Vec<_>>();
+
+                budget.run(|| self.send_parts(&parts))
+            }.and_then(|response| self.check_success(response));
+
+            let paths = files.iter().map(|(path, _)| path.clone());
+
+            match result {
+                Err(ref why) if is_too_large(why) => {},
+                Err(why) => {
+                    let why = Arc::new(why);
+                    results.extend(paths.map(|path| {
+                        (path, Err(Error::Batch(Arc::clone(&why))))
+                    }));
+
+                    return results;
+                },
+                Ok(response) => {
+                    results.extend(paths.zip(self.split_batch(
+                        response,
+                        files.len(),
+                    )));
+
+                    return results;
+                },
+            }
+        }
+
+        for (path, file) in files {
+            let filename = path_filename(&path);
+            let result = budget.run(|| self.upload_named(filename, &file));
+
+            results.push((path, result));
+        }
+
+        results
+    }
+
+    /// Splits the response to a batch of files into one for each of the
+    /// given number of files.
+    ///
+    /// Unsuccessful responses - which are only returned if the client allows
+    /// them - are given to each of the files as they are.
+    fn split_batch(&self, response: FileUploadResponse, count: usize)
+        -> Vec<Result<FileUploadResponse>> {
+        if !response.success {
+            return (0..count).map(|_| Ok(response.clone())).collect();
+        }
+
+        if response.files.len() != count {
+            let body = serde_json::to_string(&response).unwrap_or_default();
+            let body = snippet(self.key.redact(&body));
+
+            return (0..count)
+                .map(|_| Err(Error::UnexpectedResponse(body.clone())))
+                .collect();
+        }
+
+        response.files.into_iter().map(|file| {
+            Ok(FileUploadResponse {
+                description: None,
+                errorcode: None,
+                files: vec![file],
+                success: true,
+            })
+        }).collect()
+    }
+
+    /// Retrieves the size of a file to upload, checking it against the
+    /// limits.
+    fn file_size(&self, path: &Path) -> Result<u64> {
+        let size = fs::metadata(path).map_err(|why| Error::File {
+            path: path.to_owned(),
+            error: why,
+        })?.len();
+        self.inner.limits.check_file_size(size)?;
+
+        Ok(size)
+    }
+
+    /// Reads a file to upload, checking its size against the limits first.
+    fn read_file(&self, path: &Path) -> Result<Vec<u8>> {
+        let size = self.file_size(path)?;
+
+        let mut file = Vec::with_capacity(size as usize);
+        File::open(path)
+            .and_then(|mut handle| handle.read_to_end(&mut file))
+            .map_err(|why| Error::File {
+                path: path.to_owned(),
+                error: why,
+            })?;
+
+        Ok(file)
+    }
+
+    /// Uploads a single file in its own request, with a filename if given.
+    fn upload_named(&self, filename: Option<&str>, file: &[u8])
+        -> Result<FileUploadResponse> {
+        self.send_parts(&[(filename, file)])
+            .and_then(|response| self.check_single(response))
+    }
+
+    /// Uploads files - each with an optional filename - in one request,
+    /// without checking the response.
+    fn send_parts(&self, parts: &[(Option<&str>, &[u8])])
+        -> Result<FileUploadResponse> {
+        self.inner.failover.run(|base_url| {
+            let uri = endpoints::upload_at(
+                base_url,
+                &self.inner.upload_path,
+                self.key(),
+            );
+            self.send_upload(uri, parts)
+        })
+    }
+
+    /// Shortcut for shortening a URL.
+    ///
+    /// Refer to [`OwoRequester::upload_files`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Shorten the URL `"https://google.com"`, using a key from the
+    /// environment:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// use owo::OwoReqwestClient;
+    /// use std::env;
+    ///
+    /// let client = OwoReqwestClient::new(env::var("OWO_KEY")?)?;
+    ///
+    /// println!("Response: {:?}", client.shorten_url("https://google.com")?);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`OwoRequester::upload_files`]: trait.OwoRequester.html#tymethod.upload_files
+    #[inline]
+    pub fn shorten_url(&self, url: &str) -> Result<String> {
+        self.inner.limits.check_url(url)?;
+
+        self.inner.failover.run(|base_url| {
+            let uri = endpoints::shorten_at(
+                base_url,
+                &self.inner.shorten_path,
+                self.key(),
+                url,
+            );
+
+            self.send_shorten(uri)
+        }).and_then(|response| {
+            short_url(response, &self.key, self.inner.max_response_bytes)
+        })
+    }
+
+    /// Shortcut for shortening a URL, returning only the slug of the short
+    /// URL.
+    ///
+    /// Refer to [`OwoRequester::shorten_url_slug`] for more information.
+    ///
+    /// [`OwoRequester::shorten_url_slug`]: trait.OwoRequester.html#tymethod.shorten_url_slug
+    #[inline]
+    pub fn shorten_url_slug(&self, url: &str) -> Result<String> {
+        self.inner.limits.check_url(url)?;
+
+        self.inner.failover.run(|base_url| {
+            let uri = endpoints::shorten_at(
+                base_url,
+                &self.inner.shorten_path,
+                self.key(),
+                url,
+            );
+
+            self.send_shorten(uri)
+        }).and_then(|response| {
+            short_url_slug(response, &self.key, self.inner.max_response_bytes)
+        })
+    }
+
+    /// Shortcut for shortening a URL with a description attached to the short
+    /// link.
+    ///
+    /// Refer to [`OwoRequester::shorten_url_with_description`] for more
+    /// information.
+    ///
+    /// [`OwoRequester::shorten_url_with_description`]: trait.OwoRequester.html#tymethod.shorten_url_with_description
+    #[inline]
+    pub fn shorten_url_with_description(&self, url: &str, description: &str)
+        -> Result<String> {
+        self.inner.limits.check_url(url)?;
+
+        self.inner.failover.run(|base_url| {
+            let uri = endpoints::shorten_with_description_at(
+                base_url,
+                &self.inner.shorten_path,
+                self.key(),
+                url,
+                description,
+            );
+
+            self.send_shorten(uri)
+        }).and_then(|response| {
+            short_url(response, &self.key, self.inner.max_response_bytes)
+        })
+    }
+
+    /// Shortcut for shortening a URL, parsing the service's JSON response.
+    ///
+    /// Refer to [`OwoRequester::shorten_url_json`] for more information.
+    ///
+    /// [`OwoRequester::shorten_url_json`]: trait.OwoRequester.html#tymethod.shorten_url_json
+    #[inline]
+    pub fn shorten_url_json(&self, url: &str) -> Result<ShortenResponse> {
+        self.inner.limits.check_url(url)?;
+
+        self.inner.failover.run(|base_url| {
+            let uri = endpoints::shorten_json_at(
+                base_url,
+                &self.inner.shorten_path,
+                self.key(),
+                url,
+            );
+
+            self.send_shorten(uri)
+        }).and_then(|response| {
+            shorten_response(response, &self.key, self.inner.max_response_bytes)
+        })
+    }
+
+    /// Shortcut for uploading a file and shortening the URL to it, on the
+    /// client's [`domain`].
+    ///
+    /// Refer to [`OwoRequester::upload_and_shorten`] for more information.
+    ///
+    /// [`OwoRequester::upload_and_shorten`]: trait.OwoRequester.html#tymethod.upload_and_shorten
+    /// [`domain`]: #method.domain
+    pub fn upload_and_shorten(&self, file: Vec<u8>) -> Result<String> {
+        let file = self.upload_file(file)?.into_single()?;
+        let url = self.full_url(&file);
+
+        self.shorten_url(&url)
+    }
+
+    /// Checks the `success` flag of an upload response, unless the client was
+    /// built to allow unsuccessful responses.
+    fn check_success(&self, response: FileUploadResponse)
+        -> Result<FileUploadResponse> {
+        if self.inner.allow_unsuccessful {
+            return Ok(response);
+        }
+
+        succeeded(response, &self.key)
+    }
+
+    /// Checks the response to a single-file upload, which must contain the
+    /// file unless unsuccessful responses are allowed and the upload failed.
+    fn check_single(&self, response: FileUploadResponse)
+        -> Result<FileUploadResponse> {
+        if self.inner.allow_unsuccessful && !response.success {
+            return Ok(response);
+        }
+
+        let response = self.check_success(response)?;
+        response.single()?;
+
+        Ok(response)
+    }
+
+    /// Describes a request without a body, with the key redacted from its URL.
+    ///
+    /// The value of the URL's `key` parameter is redacted, rather than the
+    /// key itself, as the URL holds the key percent-encoded.
+    fn prepared(&self, method: &str, url: &str) -> PreparedRequest {
+        PreparedRequest {
+            body: vec![],
+            headers: vec![(
+                "User-Agent".to_owned(),
+                constants::USER_AGENT.to_owned(),
+            )],
+            method: method.to_owned(),
+            url: redact_key(url),
+        }
+    }
+
+    /// Makes a shortening request to the URI.
+    fn send_shorten(&self, uri: String) -> Result<Response> {
+        shorten(&self.inner.client, &Secret::new(uri))
+    }
+
+    /// Uploads files to the URI.
+    fn send_upload<T>(
+        &self,
+        uri: String,
+        files: &[(Option<&str>, &[u8])],
+    ) -> Result<T> where T: DeserializeOwned {
+        upload_parts(
+            &self.inner.client,
+            &self.key,
+            &Secret::new(uri),
+            files,
+            self.boundary(),
+            self.inner.max_response_bytes,
+            self.inner.allow_unsuccessful,
+        )
+    }
+
+    /// Shortens each of the given URLs, one-by-one.
+    ///
+    /// The result of each URL is returned separately, in the same order as
+    /// the URLs.
+    ///
+    /// Failed requests are retried according to the [`retries`] and
+    /// [`retry_budget`] the client was built with, with the budget shared
+    /// across all of the URLs.
+    ///
+    /// [`retries`]: struct.OwoClientBuilder.html#method.retries
+    /// [`retry_budget`]: struct.OwoClientBuilder.html#method.retry_budget
+    #[must_use = "failures of the batch are only reported in the results"]
+    pub fn shorten_urls(&self, urls: &[&str]) -> Vec<Result<String>> {
+        let mut budget = self.inner.retry.start();
+
+        urls.iter().map(|url| {
+            budget.run(|| self.shorten_url(url))
+        }).collect()
+    }
+}
+
+/// Creates a client using a key, strictly validating it first.
+///
+/// Unlike [`OwoClient::new`] - which leniently trims surrounding whitespace -
+/// this rejects keys containing any whitespace, such as a newline pasted along
+/// with the key, so that the mistake is reported immediately rather than as
+/// the service rejecting the key. The key is validated before anything else,
+/// after which the client is created as by [`OwoClient::new`], which can
+/// still fail for reasons unrelated to the key.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn try_main() -> owo::Result<()> {
+/// use owo::{Error, OwoReqwestClient};
+/// use std::convert::TryFrom;
+///
+/// let client = OwoReqwestClient::try_from("my-key")?;
+/// assert_eq!(client.key(), "my-key");
+///
+/// match OwoReqwestClient::try_from("my-key\n") {
+///     Err(Error::InvalidKey) => {},
+///     other => panic!("expected an invalid key: {:?}", other.map(|_| ())),
+/// }
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidKey`] if the key is empty or contains whitespace.
+///
+/// Otherwise, returns the same errors as [`OwoClient::new`].
+///
+/// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+/// [`OwoClient::new`]: struct.OwoClient.html#method.new
+impl<'a> TryFrom<&'a str> for OwoClient {
+    type Error = Error;
+
+    fn try_from(key: &'a str) -> Result<Self> {
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(Error::InvalidKey);
+        }
+
+        Self::new(key)
+    }
+}
+
+/// Trait which defines the methods necessary to interact with the service.
+///
+/// # Examples
+///
+/// To bring in the implementation for the `reqwest` Client, simply use the
+/// trait:
+///
+/// ```rust,no_run
+/// use owo::OwoReqwestRequester;
+/// ```
+///
+/// At this point, the methods will be on your Reqwest Client.
+///
+/// Requests are checked against the [`ServiceLimits`] of the official service
+/// before being made, failing with an error such as [`Error::TooManyFiles`] if
+/// they are exceeded. To use other limits, such as those of a self-hosted
+/// instance, use an [`OwoClient`] built with [`OwoClientBuilder::limits`].
+///
+/// [`Error::TooManyFiles`]: ../../enum.Error.html#variant.TooManyFiles
+/// [`OwoClient`]: struct.OwoClient.html
+/// [`OwoClientBuilder::limits`]: struct.OwoClientBuilder.html#method.limits
+/// [`ServiceLimits`]: ../../limits/struct.ServiceLimits.html
+pub trait OwoRequester {
+    /// Uploads a single file to the service.
+    ///
+    /// # Examples
+    ///
+    /// Upload a file by its filepath as a string taken from user input, with a
+    /// key taken from an environment variable:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// extern crate owo;
+    /// extern crate reqwest;
+    ///
+    /// use owo::OwoReqwestRequester;
+    /// use reqwest::Client;
+    /// use std::fs::File;
+    /// use std::io::{self, Read, Write};
+    /// use std::env;
+    ///
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// // Read a filepath to upload from user input:
+    /// let mut input = String::new();
+    /// print!("Enter the path on the filesystem to upload:\n>");
+    /// io::stdout().flush()?;
+    /// io::stdin().read_to_string(&mut input)?;
+    ///
+    /// // Read the content of the file to a buffer.
+    /// let mut file = File::open(&input)?;
+    /// let mut buffer = vec![];
+    /// file.read_to_end(&mut buffer)?;
+    ///
+    /// // Retrieve the key from an environment variable named "OWO_KEY".
+    /// let key = env::var("OWO_KEY")?;
+    ///
+    /// // Create the reqwest Client.
+    /// //
+    /// // In this case, we're using the client for a oneshot request, but in
+    /// // normal cases a client can be re-used.
+    /// let client = Client::new();
+    ///
+    /// let response = client.upload_file(&key, buffer);
+    ///
+    /// println!("Response: {:?}", response);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Reqwest`] if building the request fails.
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming or the
+    /// service rejected it, or [`Error::Api`] if the service otherwise
+    /// rejected the request.
+    ///
+    /// Returns [`Error::Status`] if the service otherwise responded with an
+    /// unsuccessful status.
+    ///
+    /// Returns [`Error::ApiFailure`] if the service responded with
+    /// `success: false` without describing the error.
+    ///
+    /// Returns [`Error::NoFilesReturned`] if the service reported the upload
+    /// as successful but returned no files.
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::ApiFailure`]: ../../enum.Error.html#variant.ApiFailure
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::NoFilesReturned`]: ../../enum.Error.html#variant.NoFilesReturned
+    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
+    /// [`Error::Status`]: ../../enum.Error.html#variant.Status
+    fn upload_file(&self, key: &str, file: Vec<u8>)
+        -> Result<FileUploadResponse>;
+
+    /// Uploads a single file to the service, deserializing the response into
+    /// a type of your choosing rather than a [`FileUploadResponse`].
+    ///
+    /// This is useful for self-hosted instances whose responses differ from
+    /// those of the official service, such as by naming fields differently.
+    ///
+    /// # Examples
+    ///
+    /// Upload a file to an instance which names the hash of files `sha1`:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// extern crate owo;
+    /// extern crate reqwest;
+    /// #[macro_use]
+    /// extern crate serde_derive;
+    ///
+    /// use owo::OwoReqwestRequester;
+    /// use reqwest::Client;
+    /// use std::env;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct ForkResponse {
+    ///     files: Vec<ForkFile>,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct ForkFile {
+    ///     sha1: String,
+    ///     url: String,
+    /// }
+    ///
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// let key = env::var("OWO_KEY")?;
+    /// let client = Client::new();
+    ///
+    /// let res: ForkResponse = client.upload_file_as(&key, b"hi".to_vec())?;
+    ///
+    /// for file in res.files {
+    ///     println!("{}: {}", file.sha1, file.url);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`upload_file`].
+    ///
+    /// Returns [`Error::Json`] if the response could not be deserialized into
+    /// the given type.
+    ///
+    /// [`Error::Json`]: ../../enum.Error.html#variant.Json
+    /// [`FileUploadResponse`]: ../../model/struct.FileUploadResponse.html
+    /// [`upload_file`]: #tymethod.upload_file
+    fn upload_file_as<T>(&self, key: &str, file: Vec<u8>) -> Result<T>
+        where T: DeserializeOwned;
+
+    /// Uploads a single file to the service, requesting that it be deleted
+    /// after the given duration.
+    ///
+    /// The expiry is sent in whole seconds as the `expiry` query parameter.
+    /// Services which do not support expiring uploads ignore the parameter, in
+    /// which case the file is kept as normal.
+    ///
+    /// # Examples
+    ///
+    /// Upload a file which is deleted after an hour, using a key taken from an
+    /// environment variable:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// extern crate owo;
+    /// extern crate reqwest;
+    ///
+    /// use owo::OwoReqwestRequester;
+    /// use reqwest::Client;
+    /// use std::env;
+    /// use std::fs::File;
+    /// use std::io::Read;
+    /// use std::time::Duration;
+    ///
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// let key = env::var("OWO_KEY")?;
+    /// let client = Client::new();
+    ///
+    /// let mut file = File::open("./file.png")?;
+    /// let mut buffer = vec![];
+    /// file.read_to_end(&mut buffer)?;
+    ///
+    /// let expiry = Duration::from_secs(60 * 60);
+    /// let response = client.upload_file_with_expiry(&key, buffer, expiry)?;
+    ///
+    /// println!("Response: {:?}", response);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Reqwest`] if building the request fails.
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming or the
+    /// service rejected it, or [`Error::Api`] if the service otherwise
+    /// rejected the request.
+    ///
+    /// Returns [`Error::Status`] if the service otherwise responded with an
+    /// unsuccessful status.
+    ///
+    /// Returns [`Error::ApiFailure`] if the service responded with
+    /// `success: false` without describing the error.
+    ///
+    /// Returns [`Error::NoFilesReturned`] if the service reported the upload
+    /// as successful but returned no files.
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::ApiFailure`]: ../../enum.Error.html#variant.ApiFailure
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::NoFilesReturned`]: ../../enum.Error.html#variant.NoFilesReturned
+    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
+    /// [`Error::Status`]: ../../enum.Error.html#variant.Status
+    fn upload_file_with_expiry(
+        &self,
+        key: &str,
+        file: Vec<u8>,
+        expiry: Duration,
+    ) -> Result<FileUploadResponse>;
+
+    /// Uploads an array of files to the service, one-by-one.
+    ///
+    /// # Examples
+    ///
+    /// Upload two files to the service, one as a slice of bytes and the other
+    /// as a path on the filesystem, using a variable from the environment:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// extern crate owo;
+    /// extern crate reqwest;
+    ///
+    /// use owo::OwoReqwestRequester;
+    /// use reqwest::Client;
+    /// use std::env;
+    /// use std::fs::File;
+    /// use std::io::Read;
+    ///
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// let key = env::var("OWO_KEY")?;
+    /// let client = Client::new();
+    ///
+    /// let mut cat1 = File::open("/mnt/media/images/cat1.png")?;
+    /// let mut buffer1 = vec![];
+    /// cat1.read_to_end(&mut buffer1)?;
+    ///
+    /// let mut cat2 = File::open("/mnt/media/images/cat2.png")?;
+    /// let mut buffer2 = vec![];
+    /// cat2.read_to_end(&mut buffer2)?;
+    ///
+    /// let responses = client.upload_files(&key, vec![buffer1, buffer2]);
+    ///
+    /// for (idx, response) in responses.iter().enumerate() {
+    ///     println!("#{} response: {:?}", idx, response);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Reqwest`] if building the request fails.
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming or the
+    /// service rejected it, or [`Error::Api`] if the service otherwise
+    /// rejected the request.
+    ///
+    /// Returns [`Error::Status`] if the service otherwise responded with an
+    /// unsuccessful status.
+    ///
+    /// Returns [`Error::ApiFailure`] if the service responded with
+    /// `success: false` without describing the error.
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::ApiFailure`]: ../../enum.Error.html#variant.ApiFailure
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
+    /// [`Error::Status`]: ../../enum.Error.html#variant.Status
+    fn upload_files(&self, key: &str, files: Vec<Vec<u8>>)
+        -> Result<FileUploadResponse>;
+
+    /// Uploads a single image to the service, after removing its metadata -
+    /// such as the location a photo was taken at, or the device it was taken
+    /// with.
+    ///
+    /// JPEG and PNG images are supported. Refer to [`metadata::strip`] for
+    /// what is removed. If the bytes are not a recognized image, they are
+    /// uploaded unchanged.
+    ///
+    /// This is only available with the `exif-strip` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// extern crate owo;
+    /// extern crate reqwest;
+    ///
+    /// use owo::OwoReqwestRequester;
+    /// use reqwest::Client;
+    /// use std::env;
+    /// use std::fs::File;
+    /// use std::io::Read;
+    ///
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// let key = env::var("OWO_KEY")?;
+    /// let client = Client::new();
+    ///
+    /// let mut photo = vec![];
+    /// File::open("./photo.jpg")?.read_to_end(&mut photo)?;
+    ///
+    /// let response = client.upload_image_stripped(&key, photo)?;
+    ///
+    /// println!("Response: {:?}", response);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Reqwest`] if building the request fails.
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming or the
+    /// service rejected it, or [`Error::Api`] if the service otherwise
+    /// rejected the request.
+    ///
+    /// Returns [`Error::Status`] if the service otherwise responded with an
+    /// unsuccessful status.
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
+    /// [`Error::Status`]: ../../enum.Error.html#variant.Status
+    /// [`metadata::strip`]: ../../metadata/fn.strip.html
+    #[cfg(feature = "img-parts")]
+    fn upload_image_stripped(&self, key: &str, image: Vec<u8>)
+        -> Result<FileUploadResponse>;
+
+    /// Downloads the file at a remote URL and uploads it to the service,
+    /// such as to archive an image hosted elsewhere.
+    ///
+    /// The file is uploaded with a filename taken from the last segment of
+    /// the URL's path, if it has one. Downloading stops as soon as the file
+    /// exceeds the service's [`max_file_size`].
+    ///
+    /// # Examples
+    ///
+    /// Mirror an image to the service, using an environment variable for the
+    /// key:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// extern crate owo;
+    /// extern crate reqwest;
+    ///
+    /// use owo::OwoReqwestRequester;
+    /// use reqwest::Client;
+    /// use std::env;
+    ///
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// let key = env::var("OWO_KEY")?;
+    /// let client = Client::new();
+    ///
+    /// let url = "https://example.com/cat.png";
+    /// let response = client.mirror_url(&key, url)?;
+    ///
+    /// println!("Response: {:?}", response);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Reqwest`] if the remote URL is invalid, or if
+    /// either request fails.
+    ///
+    /// Returns [`Error::Status`] if the remote server responded with an
+    /// unsuccessful status.
+    ///
+    /// Returns [`Error::FileTooLarge`] if the file is larger than the service
+    /// allows. The size is that given by the remote server if it gave one, or
+    /// otherwise the number of bytes downloaded before stopping.
+    ///
+    /// Otherwise returns the same errors as [`upload_file`].
+    ///
+    /// [`Error::FileTooLarge`]: ../../enum.Error.html#variant.FileTooLarge
+    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
+    /// [`Error::Status`]: ../../enum.Error.html#variant.Status
+    /// [`max_file_size`]: ../../limits/struct.ServiceLimits.html#structfield.max_file_size
+    /// [`upload_file`]: #tymethod.upload_file
+    fn mirror_url(&self, key: &str, remote_url: &str)
+        -> Result<FileUploadResponse>;
+
+    /// Deletes an uploaded file via its [`UploadedFile::delete_url`].
+    ///
+    /// The delete URL itself identifies and authorizes the deletion, so no key
+    /// is needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Reqwest`] if the request fails.
+    ///
+    /// Returns an [`Error::UnexpectedResponse`] containing the start of the
+    /// response body - with the delete URL redacted - if the service did not
+    /// respond with a successful status, such as when the file was already
+    /// deleted.
+    ///
+    /// [`Error::Reqwest`]: ../../enum.Error.html#variant.Reqwest
+    /// [`Error::UnexpectedResponse`]: ../../enum.Error.html#variant.UnexpectedResponse
+    /// [`UploadedFile::delete_url`]: ../../model/struct.UploadedFile.html#structfield.delete_url
+    fn delete_file(&self, delete_url: &str) -> Result<()>;
+
+    /// Shortens a URL via the service, returning a URL to the shortened link.
+    ///
+    /// # Examples
+    ///
+    /// Shorten the URL `"https://google.com"` via the service, using an
+    /// environment variable for the key:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// extern crate owo;
+    /// extern crate reqwest;
+    ///
+    /// use owo::OwoReqwestRequester;
+    /// use reqwest::Client;
+    /// use std::env;
+    ///
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// let key = env::var("OWO_KEY")?;
+    /// let client = Client::new();
+    ///
+    /// let url_to_shorten = "https://google.com";
+    ///
+    /// let url = client.shorten_url(&key, url_to_shorten)?;
+    ///
+    /// println!("url: {}", url);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming or the
+    /// service rejected it, or [`Error::Api`] if the service otherwise
+    /// rejected the request.
+    ///
+    /// Returns an [`Error::UnexpectedResponse`] if the service responded with
+    /// something other than a URL, such as an error page.
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::UnexpectedResponse`]: ../../enum.Error.html#variant.UnexpectedResponse
+    fn shorten_url(&self, key: &str, url: &str) -> Result<String>;
+
+    /// Shortens a URL via the service, returning only the slug of the short
+    /// URL.
+    ///
+    /// For example, if the short URL is `"https://awau.moe/abc123"`, then
+    /// `"abc123"` is returned.
+    ///
+    /// The short URL is taken from the response body. If the service instead
+    /// responds with a redirect to the short URL - and the client does not
+    /// follow redirects - then the short URL is taken from the `Location`
+    /// header.
+    ///
+    /// # Examples
+    ///
+    /// Shorten the URL `"https://google.com"` via the service, using an
+    /// environment variable for the key:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// extern crate owo;
+    /// extern crate reqwest;
+    ///
+    /// use owo::OwoReqwestRequester;
+    /// use reqwest::Client;
+    /// use std::env;
+    ///
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// let key = env::var("OWO_KEY")?;
+    /// let client = Client::new();
+    ///
+    /// let slug = client.shorten_url_slug(&key, "https://google.com")?;
+    ///
+    /// println!("slug: {}", slug);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming or the
+    /// service rejected it, or [`Error::Api`] if the service otherwise
+    /// rejected the request.
+    ///
+    /// Returns [`Error::Status`] if the service otherwise responded with an
+    /// unsuccessful status.
+    ///
+    /// Returns an [`Error::UnexpectedResponse`] if no slug could be found in
+    /// the response.
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::Status`]: ../../enum.Error.html#variant.Status
+    /// [`Error::UnexpectedResponse`]: ../../enum.Error.html#variant.UnexpectedResponse
+    fn shorten_url_slug(&self, key: &str, url: &str) -> Result<String>;
+
+    /// Shortens a URL via the service, asking for a JSON response and parsing
+    /// it into a [`ShortenResponse`].
+    ///
+    /// This is more robust than [`shorten_url`], which takes the plain body of
+    /// the response as the short URL.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// extern crate owo;
+    /// extern crate reqwest;
+    ///
+    /// use owo::OwoReqwestRequester;
+    /// use reqwest::Client;
+    /// use std::env;
+    ///
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// let key = env::var("OWO_KEY")?;
+    /// let client = Client::new();
+    ///
+    /// let res = client.shorten_url_json(&key, "https://google.com")?;
+    ///
+    /// println!("url: {}", res.result);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming or the
+    /// service rejected it, or [`Error::Api`] if the service otherwise
+    /// rejected the request.
+    ///
+    /// Returns [`Error::InvalidJson`] if the response could not be parsed.
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::InvalidJson`]: ../../enum.Error.html#variant.InvalidJson
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`ShortenResponse`]: ../../model/struct.ShortenResponse.html
+    /// [`shorten_url`]: #tymethod.shorten_url
+    fn shorten_url_json(&self, key: &str, url: &str)
+        -> Result<ShortenResponse>;
+
+    /// Shortens a URL via the service with a description attached to the
+    /// short link, returning a URL to the shortened link.
+    ///
+    /// The description - such as the original filename of an uploaded file -
+    /// is stored by polr instances which support it, to keep context about
+    /// the link in their dashboard. Instances which don't support it ignore
+    /// it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// extern crate owo;
+    /// extern crate reqwest;
+    ///
+    /// use owo::OwoReqwestRequester;
+    /// use reqwest::Client;
+    /// use std::env;
+    ///
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// let key = env::var("OWO_KEY")?;
+    /// let client = Client::new();
+    ///
+    /// let url = client.shorten_url_with_description(
+    ///     &key,
+    ///     "https://owo.whats-th.is/a1b2c3.png",
+    ///     "holiday.png",
+    /// )?;
+    ///
+    /// println!("url: {}", url);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming or the
+    /// service rejected it, or [`Error::Api`] if the service otherwise
+    /// rejected the request.
+    ///
+    /// Returns an [`Error::UnexpectedResponse`] if the service responded with
+    /// something other than a URL, such as an error page.
+    ///
+    /// [`Error::Api`]: ../../enum.Error.html#variant.Api
+    /// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+    /// [`Error::UnexpectedResponse`]: ../../enum.Error.html#variant.UnexpectedResponse
+    fn shorten_url_with_description(
+        &self,
+        key: &str,
+        url: &str,
+        description: &str,
+    ) -> Result<String>;
+
+    /// Uploads a single file to the service, and then shortens the URL to it,
+    /// returning the short URL.
+    ///
+    /// # Examples
+    ///
+    /// Upload a file and print its short URL, using an environment variable
+    /// for the key:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// extern crate owo;
+    /// extern crate reqwest;
+    ///
+    /// use owo::OwoReqwestRequester;
+    /// use reqwest::Client;
+    /// use std::env;
+    ///
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// #
+    /// let key = env::var("OWO_KEY")?;
+    /// let client = Client::new();
+    ///
+    /// let url = client.upload_and_shorten(&key, b"hello".to_vec())?;
+    ///
+    /// println!("url: {}", url);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`upload_file`] if the upload fails, and
+    /// the same errors as [`shorten_url`] if shortening fails.
+    ///
+    /// [`shorten_url`]: #tymethod.shorten_url
+    /// [`upload_file`]: #tymethod.upload_file
+    fn upload_and_shorten(&self, key: &str, file: Vec<u8>) -> Result<String>;
+}
+
+impl OwoRequester for Client {
+    fn upload_file(&self, key: &str, file: Vec<u8>)
+        -> Result<FileUploadResponse> {
+        let secret = Secret::key(key.to_owned())?;
+
+        self.upload_file_as(key, file)
+            .and_then(|response| succeeded(response, &secret))
+            .and_then(|response| {
+                response.single()?;
+
+                Ok(response)
+            })
+    }
+
+    fn upload_file_as<T>(&self, key: &str, file: Vec<u8>) -> Result<T>
+        where T: DeserializeOwned {
+        let files = [file];
+        ServiceLimits::default().check_files(&files)?;
+
+        let key = Secret::key(key.to_owned())?;
+        let uri = Secret::new(endpoints::upload(key.as_str()));
+
+        upload(self, &key, &uri, &files)
+    }
+
+    fn upload_file_with_expiry(
+        &self,
+        key: &str,
+        file: Vec<u8>,
+        expiry: Duration,
+    ) -> Result<FileUploadResponse> {
+        let files = [file];
+        ServiceLimits::default().check_files(&files)?;
+
+        let key = Secret::key(key.to_owned())?;
+        let uri = Secret::new(
+            endpoints::upload_with_expiry(key.as_str(), expiry),
+        );
+
+        upload(self, &key, &uri, &files)
+            .and_then(|response| succeeded(response, &key))
+            .and_then(|response| {
+                response.single()?;
+
+                Ok(response)
+            })
+    }
+
+    fn upload_files(&self, key: &str, files: Vec<Vec<u8>>)
+        -> Result<FileUploadResponse> {
+        ServiceLimits::default().check_files(&files)?;
+
+        let key = Secret::key(key.to_owned())?;
+        let uri = Secret::new(endpoints::upload(key.as_str()));
+
+        upload(self, &key, &uri, &files)
+            .and_then(|response| succeeded(response, &key))
+    }
+
+    #[cfg(feature = "img-parts")]
+    fn upload_image_stripped(&self, key: &str, image: Vec<u8>)
+        -> Result<FileUploadResponse> {
+        self.upload_file(key, metadata::strip(image))
+    }
+
+    fn mirror_url(&self, key: &str, remote_url: &str)
+        -> Result<FileUploadResponse> {
+        let file = download(self, remote_url, &ServiceLimits::default())?;
+        let filename = mirror_filename(remote_url);
+        let parts = [(filename.as_deref(), &file[..])];
+
+        let key = Secret::key(key.to_owned())?;
+        let uri = Secret::new(endpoints::upload(key.as_str()));
+
+        upload_parts(self, &key, &uri, &parts, None, None, false)
+            .and_then(|response| succeeded(response, &key))
+            .and_then(|response| {
+                response.single()?;
+
+                Ok(response)
+            })
+    }
+
+    fn delete_file(&self, delete_url: &str) -> Result<()> {
+        let uri = Secret::new(delete_url.to_owned());
+
+        timed(|| {
+            let mut response = self
+                .get(uri.as_str())
+                .header(header::USER_AGENT, constants::USER_AGENT)
+                .send()?;
+
+            check_rate_limit(&response)?;
+
+            if response.status().is_success() {
+                return Ok(());
+            }
+
+            let mut body = vec![];
+            (&mut response).take(MAX_BODY_LEN).read_to_end(&mut body)?;
+
+            let body = String::from_utf8_lossy(&body);
+
+            Err(Error::UnexpectedResponse(snippet(uri.redact(&body))))
+        })
+    }
+
+    fn shorten_url(&self, key: &str, url: &str) -> Result<String> {
+        ServiceLimits::default().check_url(url)?;
+
+        let key = Secret::key(key.to_owned())?;
+        let uri = Secret::new(endpoints::shorten(key.as_str(), url));
+
+        timed(|| {
+            shorten(self, &uri)
+                .and_then(|response| short_url(response, &key, None))
+        })
+    }
+
+    fn shorten_url_slug(&self, key: &str, url: &str) -> Result<String> {
+        ServiceLimits::default().check_url(url)?;
+
+        let key = Secret::key(key.to_owned())?;
+        let uri = Secret::new(endpoints::shorten(key.as_str(), url));
+
+        timed(|| {
+            shorten(self, &uri)
+                .and_then(|response| short_url_slug(response, &key, None))
+        })
+    }
+
+    fn shorten_url_json(&self, key: &str, url: &str)
+        -> Result<ShortenResponse> {
+        ServiceLimits::default().check_url(url)?;
+
+        let key = Secret::key(key.to_owned())?;
+        let uri = Secret::new(endpoints::shorten_json(key.as_str(), url));
+
+        timed(|| {
+            shorten(self, &uri)
+                .and_then(|response| shorten_response(response, &key, None))
+        })
+    }
+
+    fn shorten_url_with_description(
+        &self,
+        key: &str,
+        url: &str,
+        description: &str,
+    ) -> Result<String> {
+        ServiceLimits::default().check_url(url)?;
+
+        let key = Secret::key(key.to_owned())?;
+        let uri = Secret::new(
+            endpoints::shorten_with_description(key.as_str(), url, description),
+        );
+
+        timed(|| {
+            shorten(self, &uri)
+                .and_then(|response| short_url(response, &key, None))
+        })
+    }
+
+    fn upload_and_shorten(&self, key: &str, file: Vec<u8>) -> Result<String> {
+        let url = self.upload_file(key, file)?.into_single()?.full_url();
 
-        Ok(buffer)
+        self.shorten_url(key, &url)
     }
 }
 
-fn upload(client: &Client, uri: &str, form: Form)
-    -> Result<FileUploadResponse> {
-        let reader = client
-            .post(uri)
-            .multipart(form)
-            .header(UserAgent::new(constants::USER_AGENT))
+fn shorten(client: &Client, uri: &Secret) -> Result<Response> {
+    client
+        .get(uri.as_str())
+        .header(header::USER_AGENT, constants::USER_AGENT)
+        .send()
+        .map_err(From::from)
+}
+
+/// Reads the short URL from the response to a shortening request, trimmed of
+/// surrounding whitespace.
+///
+/// # Errors
+///
+/// Returns an [`Error::UnexpectedResponse`] containing the start of the body
+/// if it is not an absolute `http` or `https` URL, such as when it is an
+/// error page.
+///
+/// [`Error::UnexpectedResponse`]: ../../enum.Error.html#variant.UnexpectedResponse
+fn short_url(response: Response, key: &Secret, max_len: Option<usize>)
+    -> Result<String> {
+    let body = read_body(response, key, max_len, false)?;
+    let url = body.trim();
+
+    if !endpoints::is_absolute_http_url(url)
+        || url.contains(char::is_whitespace) {
+        return Err(Error::UnexpectedResponse(snippet(key.redact(&body))));
+    }
+
+    Ok(url.to_owned())
+}
+
+/// Parses the response to a shortening request which asked for a JSON
+/// response.
+fn shorten_response(
+    response: Response,
+    key: &Secret,
+    max_len: Option<usize>,
+) -> Result<ShortenResponse> {
+    parse_json(&read_body(response, key, max_len, false)?, key)
+}
+
+/// Reads the slug of the short URL from the response to a shortening request.
+///
+/// The short URL is taken from the `Location` header if the service responded
+/// with a redirect, or otherwise from the body.
+fn short_url_slug(
+    response: Response,
+    key: &Secret,
+    max_len: Option<usize>,
+) -> Result<String> {
+    let location = response.headers()
+        .get(header::LOCATION)
+        .map(|location| {
+            String::from_utf8_lossy(location.as_bytes()).into_owned()
+        });
+    let url = match location {
+        Some(location) => location,
+        None => short_url(response, key, max_len)?,
+    };
+
+    match endpoints::parse_short_url(&url) {
+        Some((_, slug)) => Ok(slug),
+        None => Err(Error::UnexpectedResponse(snippet(key.redact(&url)))),
+    }
+}
+
+fn upload<T: DeserializeOwned>(
+    client: &Client,
+    key: &Secret,
+    uri: &Secret,
+    files: &[Vec<u8>],
+) -> Result<T> {
+    upload_parts(client, key, uri, &unnamed(files), None, None, false)
+}
+
+/// Pairs each file with no filename, for uploading via [`upload_parts`].
+///
+/// [`upload_parts`]: fn.upload_parts.html
+fn unnamed(files: &[Vec<u8>]) -> Vec<(Option<&str>, &[u8])> {
+    files.iter().map(|file| (None, &file[..])).collect()
+}
+
+/// Encodes files - each with an optional filename - as a multipart body.
+fn multipart_body(multipart: &Multipart, files: &[(Option<&str>, &[u8])])
+    -> Vec<u8> {
+    let mut body = Vec::with_capacity(
+        multipart.body_len("files[]", files) as usize,
+    );
+
+    for &(filename, file) in files {
+        body.extend(multipart.file_start("files[]", filename));
+        body.extend(file);
+        body.extend(multipart.file_end());
+    }
+
+    body.extend(multipart.finish());
+
+    body
+}
+
+/// Uploads files, each with an optional filename.
+///
+/// Refer to [`read_body`] for the meaning of `allow_unsuccessful`.
+///
+/// [`read_body`]: fn.read_body.html
+fn upload_parts<T: DeserializeOwned>(
+    client: &Client,
+    key: &Secret,
+    uri: &Secret,
+    files: &[(Option<&str>, &[u8])],
+    boundary: Option<&str>,
+    max_len: Option<usize>,
+    allow_unsuccessful: bool,
+) -> Result<T> {
+    // The body is encoded by hand rather than via reqwest's multipart support,
+    // which does not allow for setting the boundary.
+    let multipart = Multipart::with_boundary(boundary);
+    let body = multipart_body(&multipart, files);
+
+    timed(|| {
+        let response = client
+            .post(uri.as_str())
+            .header(header::USER_AGENT, constants::USER_AGENT)
+            .header(header::CONTENT_TYPE, multipart.content_type())
+            .body(body)
             .send()?;
 
-    serde_json::from_reader(reader).map_err(From::from)
+        if response.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            let size = files.iter().map(|&(_, file)| file.len() as u64).sum();
+
+            return Err(Error::PayloadTooLarge {
+                size: Some(size),
+            });
+        }
+
+        let body = read_body(response, key, max_len, allow_unsuccessful)?;
+
+        parse_json(&body, key)
+    })
+}
+
+/// Runs a request, converting it timing out into an [`Error::Timeout`]
+/// containing how long was spent on it.
+///
+/// [`Error::Timeout`]: ../../enum.Error.html#variant.Timeout
+fn timed<T, F>(request: F) -> Result<T> where F: FnOnce() -> Result<T> {
+    let start = Instant::now();
+
+    request().map_err(|why| if is_timeout(&why) {
+        Error::Timeout {
+            elapsed: start.elapsed(),
+        }
+    } else {
+        why
+    })
+}
+
+/// Whether an error is due to a request timing out which has not yet been
+/// converted into an [`Error::Timeout`].
+///
+/// [`Error::Timeout`]: ../../enum.Error.html#variant.Timeout
+fn is_timeout(error: &Error) -> bool {
+    match *error {
+        Error::Timeout { .. } => false,
+        _ => error.network_kind() == Some(NetworkErrorKind::Timeout),
+    }
+}
+
+/// Downloads the file at a remote URL to be mirrored, stopping once it exceeds
+/// the maximum file size of the given limits.
+fn download(client: &Client, remote_url: &str, limits: &ServiceLimits)
+    -> Result<Vec<u8>> {
+    timed(|| download_limited(client, remote_url, limits))
+}
+
+/// Performs the download of [`download`], without converting timeouts.
+///
+/// [`download`]: fn.download.html
+fn download_limited(client: &Client, remote_url: &str, limits: &ServiceLimits)
+    -> Result<Vec<u8>> {
+    let mut response = client
+        .get(remote_url)
+        .header(header::USER_AGENT, constants::USER_AGENT)
+        .send()?;
+    let status = response.status();
+
+    if !status.is_success() {
+        let mut body = vec![];
+        (&mut response).take(MAX_SNIPPET_LEN as u64).read_to_end(&mut body)?;
+
+        return Err(Error::Status {
+            status: status.as_u16(),
+            body: snippet(String::from_utf8_lossy(&body).into_owned()),
+        });
+    }
+
+    let limit = match limits.max_file_size {
+        Some(limit) => limit,
+        None => {
+            let mut file = vec![];
+            response.read_to_end(&mut file)?;
+
+            return Ok(file);
+        },
+    };
+
+    if let Some(size) = response.content_length() {
+        if size > limit {
+            return Err(Error::FileTooLarge { size, limit });
+        }
+    }
+
+    // Read one byte past the limit to tell whether the file exceeds it,
+    // without trusting the remote server's `Content-Length`.
+    let mut file = vec![];
+    (&mut response).take(limit + 1).read_to_end(&mut file)?;
+
+    if file.len() as u64 > limit {
+        return Err(Error::FileTooLarge {
+            size: file.len() as u64,
+            limit,
+        });
+    }
+
+    Ok(file)
+}
+
+/// Derives the filename of a file to mirror from the last segment of its
+/// URL's path, if there is one which can be used in a multipart form.
+fn mirror_filename(remote_url: &str) -> Option<String> {
+    let url = Url::parse(remote_url).ok()?;
+    let name = url.path_segments()?.next_back()?;
+
+    part_filename(name).map(str::to_owned)
+}
+
+/// Checks that a name can be sent as the filename of a multipart part, which
+/// can't be empty or contain quotes, backslashes or control characters.
+fn part_filename(name: &str) -> Option<&str> {
+    let invalid = |c: char| c == '"' || c == '\\' || c.is_control();
+
+    if name.is_empty() || name.contains(invalid) {
+        return None;
+    }
+
+    Some(name)
+}
+
+/// Whether the service rejected a request for being too large, in which case
+/// none of its files were stored.
+fn is_too_large(error: &Error) -> bool {
+    match *error {
+        Error::Api { kind: ApiErrorCode::FileTooLarge, .. }
+        | Error::PayloadTooLarge { .. }
+        | Error::TooManyFiles { .. } => true,
+        _ => false,
+    }
+}
+
+/// The filename of a file to upload, if it can be sent as the filename of
+/// its part.
+fn path_filename(path: &Path) -> Option<&str> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(part_filename)
+}
+
+/// Lists the files within a directory, sorted by path, including those within
+/// its subdirectories if recursive.
+///
+/// Symbolic links to files are included, but those to directories are not
+/// followed.
+fn dir_files(dir: &Path, recursive: bool) -> Result<Vec<PathBuf>> {
+    let to_error = |path: &Path| {
+        let path = path.to_owned();
+
+        move |why: IoError| Error::File {
+            path,
+            error: why,
+        }
+    };
+
+    let mut files = vec![];
+    let mut dirs = vec![dir.to_owned()];
+
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir).map_err(to_error(&dir))? {
+            let path = entry.map_err(to_error(&dir))?.path();
+            let link = fs::symlink_metadata(&path).map_err(to_error(&path))?;
+
+            if link.is_dir() {
+                if recursive {
+                    dirs.push(path);
+                }
+            } else if link.file_type().is_symlink() {
+                // Links to directories - or broken links - are skipped.
+                let target = fs::metadata(&path);
+
+                if target.map(|meta| meta.is_file()).unwrap_or(false) {
+                    files.push(path);
+                }
+            } else if link.is_file() {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+
+    Ok(files)
+}
+
+/// Deserializes a response body, keeping the start of the body in the error
+/// if it could not be.
+///
+/// # Errors
+///
+/// Returns [`Error::HtmlResponse`] if the body is an HTML page, or
+/// [`Error::InvalidJson`] if it otherwise could not be deserialized.
+///
+/// [`Error::HtmlResponse`]: ../../enum.Error.html#variant.HtmlResponse
+/// [`Error::InvalidJson`]: ../../enum.Error.html#variant.InvalidJson
+fn parse_json<T: DeserializeOwned>(body: &str, key: &Secret) -> Result<T> {
+    if let Some(why) = html_response(body, key) {
+        return Err(why);
+    }
+
+    serde_json::from_str(body).map_err(|why| Error::InvalidJson {
+        body: snippet(key.redact(body)),
+        error: why,
+    })
+}
+
+/// Reads the body of a response, failing if the service did not respond
+/// successfully.
+///
+/// The body is read up to the given maximum length, or up to
+/// [`MAX_BODY_LEN`] bytes if none is given.
+///
+/// # Errors
+///
+/// Returns [`Error::ResponseTooLarge`] if the body is longer than the maximum
+/// length.
+///
+/// Returns [`Error::RateLimited`] if the response has a
+/// `429 Too Many Requests` status.
+///
+/// Returns [`Error::InvalidKey`] if the response has a `401 Unauthorized` or
+/// `403 Forbidden` status, whatever the body.
+///
+/// Returns [`Error::PayloadTooLarge`] if the response has a
+/// `413 Payload Too Large` status.
+///
+/// Returns [`Error::Api`] if the body contains an error from the service,
+/// unless unsuccessful responses are allowed and the response has a
+/// successful status, in which case the body is returned for the caller to
+/// check.
+///
+/// Returns [`Error::ServiceUnavailable`] if the response otherwise has an
+/// unsuccessful status and is an HTML page, or [`Error::Status`] if it is
+/// not. Both contain the start of the body with the key redacted.
+///
+/// [`Error::Api`]: ../../enum.Error.html#variant.Api
+/// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+/// [`Error::PayloadTooLarge`]: ../../enum.Error.html#variant.PayloadTooLarge
+/// [`Error::RateLimited`]: ../../enum.Error.html#variant.RateLimited
+/// [`Error::ResponseTooLarge`]: ../../enum.Error.html#variant.ResponseTooLarge
+/// [`Error::ServiceUnavailable`]: ../../enum.Error.html#variant.ServiceUnavailable
+/// [`Error::Status`]: ../../enum.Error.html#variant.Status
+/// [`MAX_BODY_LEN`]: constant.MAX_BODY_LEN.html
+fn read_body(
+    mut response: Response,
+    key: &Secret,
+    max_len: Option<usize>,
+    allow_unsuccessful: bool,
+) -> Result<String> {
+    let retry_after = header_str(&response, header::RETRY_AFTER);
+    let status = response.status().as_u16();
+
+    if let Some(why) = status_error(status, retry_after) {
+        return Err(why);
+    }
+
+    let max_len = max_len.unwrap_or(MAX_BODY_LEN as usize);
+    let too_large = Error::ResponseTooLarge {
+        limit: max_len,
+    };
+
+    // Bail before reading anything if the service said how long the body is,
+    // and otherwise once a byte past the limit has been read.
+    if let Some(len) = response.content_length() {
+        if len > max_len as u64 {
+            return Err(too_large);
+        }
+    }
+
+    let mut bytes = vec![];
+    (&mut response).take(max_len as u64 + 1).read_to_end(&mut bytes)?;
+
+    if bytes.len() > max_len {
+        return Err(too_large);
+    }
+
+    // Error pages in front of the service aren't always UTF-8, so replace
+    // invalid sequences rather than failing with an encoding error.
+    let body = String::from_utf8_lossy(&bytes).into_owned();
+    let content_type = header_str(&response, header::CONTENT_TYPE);
+
+    match body_error(status, content_type, &body, key, allow_unsuccessful) {
+        Some(why) => Err(why),
+        None => Ok(body),
+    }
+}
+
+/// Retrieves the value of a header of a response, if it is present and valid
+/// UTF-8.
+fn header_str(response: &Response, name: header::HeaderName) -> Option<&str> {
+    response.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+}
+
+/// Fails if the service rate limited a request, taking the time to retry
+/// after from the `Retry-After` header if present.
+///
+/// # Errors
+///
+/// Returns [`Error::RateLimited`] if the response has a
+/// `429 Too Many Requests` status.
+///
+/// [`Error::RateLimited`]: ../../enum.Error.html#variant.RateLimited
+fn check_rate_limit(response: &Response) -> Result<()> {
+    if response.status() != StatusCode::TOO_MANY_REQUESTS {
+        return Ok(());
+    }
+
+    Err(rate_limited(header_str(response, header::RETRY_AFTER)))
+}
+
+/// Checks the `success` flag of an upload response.
+///
+/// # Errors
+///
+/// Returns [`Error::Api`] if the service responded with `success: false` and
+/// an error code, or otherwise [`Error::ApiFailure`] - containing the start of
+/// the response - if it responded with `success: false`.
+///
+/// [`Error::Api`]: ../../enum.Error.html#variant.Api
+/// [`Error::ApiFailure`]: ../../enum.Error.html#variant.ApiFailure
+fn succeeded(response: FileUploadResponse, key: &Secret)
+    -> Result<FileUploadResponse> {
+    if response.success {
+        return Ok(response);
+    }
+
+    if let Some(code) = response.errorcode {
+        let description = response.description
+            .as_ref()
+            .map_or_else(String::new, |description| key.redact(description));
+
+        return Err(Error::Api {
+            code,
+            description,
+            kind: ApiErrorCode::from(code),
+        });
+    }
+
+    let body = serde_json::to_string(&response).unwrap_or_default();
+
+    Err(Error::ApiFailure {
+        body: snippet(key.redact(&body)),
+    })
 }
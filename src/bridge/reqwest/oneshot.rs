@@ -45,9 +45,9 @@ pub fn upload_files(files: Vec<Vec<u8>>) -> Result<FileUploadResponse> {
 ///
 /// # Errors
 ///
-/// Returns [`Error::NativeTls`] if there was an error instantiating the client.
+/// Returns [`Error::Reqwest`] if there was an error instantiating the client.
 ///
-/// [`Error::NativeTls`]: ../../../enum.Error.html#variant.NativeTls
+/// [`Error::Reqwest`]: ../../../enum.Error.html#variant.Reqwest
 /// [`OwoReqwestRequester`]: ../trait.OwoRequester.html
 #[inline]
 pub fn shorten_url(key: &str, url: &str) -> Result<String> {
@@ -0,0 +1,226 @@
+//! Retrying of failed requests within batch operations.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::u32;
+use ::{Error, NetworkErrorKind, Result};
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// How long to wait before retrying a failed request, as set via
+/// `OwoReqwestClientBuilder::backoff`.
+///
+/// Waiting longer after each failure gives a struggling service time to
+/// recover, while randomizing the wait - jitter - stops many clients which
+/// failed at the same time from all retrying at the same time again.
+///
+/// Rate limited requests for which the service said when to retry wait for
+/// that long instead.
+///
+/// The [`Default`] is `ExponentialJitter` starting from 100 milliseconds, up
+/// to 10 seconds.
+///
+/// [`Default`]: #impl-Default
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BackoffStrategy {
+    /// Waits the same length of time before each retry.
+    Fixed(Duration),
+    /// Waits twice as long before each retry as before the previous one.
+    Exponential {
+        /// The length of time to wait before the first retry.
+        base: Duration,
+        /// The longest length of time to wait before a retry.
+        max: Duration,
+    },
+    /// Waits a random length of time before each retry, of up to what
+    /// `Exponential` would wait, known as "full jitter".
+    ExponentialJitter {
+        /// The longest length of time to wait before the first retry.
+        base: Duration,
+        /// The longest length of time to wait before a retry.
+        max: Duration,
+    },
+}
+
+impl BackoffStrategy {
+    /// Calculates how long to wait before a retry, given the number of
+    /// retries of the request made before it and a random fraction in the
+    /// range `[0, 1)`.
+    ///
+    /// The fraction is only used by `ExponentialJitter`, for which the
+    /// client picks it randomly. Taking it as a parameter keeps the
+    /// calculation deterministic, such as for testing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use owo::BackoffStrategy;
+    /// use std::time::Duration;
+    ///
+    /// let backoff = BackoffStrategy::ExponentialJitter {
+    ///     base: Duration::from_millis(100),
+    ///     max: Duration::from_secs(10),
+    /// };
+    ///
+    /// // The third retry waits for up to 400 milliseconds.
+    /// assert_eq!(backoff.delay(2, 0.5), Duration::from_millis(200));
+    /// ```
+    pub fn delay(&self, retry: u32, fraction: f64) -> Duration {
+        match *self {
+            BackoffStrategy::Fixed(delay) => delay,
+            BackoffStrategy::Exponential { base, max } => {
+                exponential(base, max, retry)
+            },
+            BackoffStrategy::ExponentialJitter { base, max } => {
+                scale(exponential(base, max, retry), fraction)
+            },
+        }
+    }
+}
+
+impl Default for BackoffStrategy {
+    fn default() -> Self {
+        BackoffStrategy::ExponentialJitter {
+            base: Duration::from_millis(100),
+            max: Duration::from_secs(10),
+        }
+    }
+}
+
+/// The retry configuration of a client, as set via its builder.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct RetryPolicy {
+    /// How long to wait before each retry.
+    pub(crate) backoff: BackoffStrategy,
+    /// The number of times each request may be retried.
+    pub(crate) retries: u32,
+    /// The total number of retries allowed across a batch operation, if
+    /// limited.
+    pub(crate) budget: Option<u32>,
+}
+
+impl RetryPolicy {
+    /// Creates the budget for a new batch operation.
+    pub(crate) fn start(&self) -> RetryBudget {
+        RetryBudget {
+            backoff: self.backoff,
+            remaining: self.budget,
+            retries: self.retries,
+        }
+    }
+}
+
+/// The retries remaining for a single batch operation.
+pub(crate) struct RetryBudget {
+    backoff: BackoffStrategy,
+    remaining: Option<u32>,
+    retries: u32,
+}
+
+impl RetryBudget {
+    /// Runs a request, retrying it on network errors and rate limiting while
+    /// both the request's own retries and the shared budget allow.
+    ///
+    /// Retries wait according to the backoff strategy, except for those of
+    /// rate limited requests, which wait for as long as the service asked if
+    /// it did.
+    pub(crate) fn run<T, F>(&mut self, mut request: F) -> Result<T>
+        where F: FnMut() -> Result<T> {
+        let mut attempts = 0;
+
+        loop {
+            match request() {
+                Err(ref why) if attempts < self.retries
+                    && is_retryable(why)
+                    && self.take() => {
+                    let delay = retry_after(why).unwrap_or_else(|| {
+                        self.backoff.delay(attempts, random_fraction())
+                    });
+
+                    thread::sleep(delay);
+
+                    attempts += 1;
+                },
+                result => return result,
+            }
+        }
+    }
+
+    /// Takes a retry from the budget, returning whether one was available.
+    fn take(&mut self) -> bool {
+        match self.remaining {
+            Some(0) => false,
+            Some(ref mut remaining) => {
+                *remaining -= 1;
+
+                true
+            },
+            None => true,
+        }
+    }
+}
+
+/// Whether an error may be resolved by making the request again.
+///
+/// Only errors occurring while talking to the service - or the service asking
+/// to slow down - are retried; errors such as too many files being provided
+/// would fail the same way again, as would a failed TLS handshake.
+fn is_retryable(error: &Error) -> bool {
+    match error.network_kind() {
+        Some(NetworkErrorKind::Tls) => false,
+        Some(_) => true,
+        None => error.is_rate_limited(),
+    }
+}
+
+/// How long to wait before retrying a request which failed with an error, if
+/// the service said.
+fn retry_after(error: &Error) -> Option<Duration> {
+    match *error {
+        Error::RateLimited { retry_after } => retry_after,
+        _ => None,
+    }
+}
+
+/// The delay before a retry, doubling from the base with each retry up to the
+/// maximum.
+fn exponential(base: Duration, max: Duration, retry: u32) -> Duration {
+    let factor = 1u32.checked_shl(retry).unwrap_or(u32::MAX);
+
+    match base.checked_mul(factor) {
+        Some(delay) if delay < max => delay,
+        _ => max,
+    }
+}
+
+/// Scales a duration by a fraction in the range `[0, 1)`.
+fn scale(duration: Duration, fraction: f64) -> Duration {
+    let fraction = fraction.max(0.0).min(1.0);
+    let nanos = duration.as_secs() as f64 * 1e9
+        + f64::from(duration.subsec_nanos());
+    let scaled = (nanos * fraction) as u64;
+
+    Duration::new(scaled / 1_000_000_000, (scaled % 1_000_000_000) as u32)
+}
+
+/// Generates a random fraction in the range `[0, 1)` to jitter a delay by.
+///
+/// This only needs to differ between clients and between retries, so it
+/// mixes the current time with a counter rather than pulling in a random
+/// number generator.
+fn random_fraction() -> f64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or(0);
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed) as u64;
+
+    // The finalizer of splitmix64, spreading the bits of the seed.
+    let mut bits = u64::from(nanos) ^ count.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    bits = (bits ^ (bits >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    bits = (bits ^ (bits >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    bits ^= bits >> 31;
+
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
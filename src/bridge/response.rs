@@ -0,0 +1,165 @@
+//! Handling of response bodies shared between the bridges.
+
+use httpdate;
+use serde_json;
+use std::time::{Duration, SystemTime};
+use ::secret::Secret;
+use ::{ApiErrorCode, Error};
+
+/// The maximum length of a response body kept in an error such as
+/// [`Error::Status`], in bytes.
+///
+/// [`Error::Status`]: ../../enum.Error.html#variant.Status
+pub(crate) const MAX_SNIPPET_LEN: usize = 512;
+
+/// Truncates a response body to at most [`MAX_SNIPPET_LEN`] bytes, so that
+/// errors containing it stay a reasonable size.
+///
+/// [`MAX_SNIPPET_LEN`]: constant.MAX_SNIPPET_LEN.html
+pub(crate) fn snippet(mut body: String) -> String {
+    if body.len() > MAX_SNIPPET_LEN {
+        let mut end = MAX_SNIPPET_LEN;
+
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        body.truncate(end);
+    }
+
+    body
+}
+
+/// Detects a response body which is an HTML page rather than JSON, such as a
+/// gateway error or captcha page from a proxy in front of the service, going
+/// by its first non-whitespace character.
+///
+/// Otherwise, such a body fails to deserialize with a misleading error at its
+/// first byte.
+pub(crate) fn html_response(body: &str, key: &Secret) -> Option<Error> {
+    if body.trim_start().starts_with('<') {
+        Some(Error::HtmlResponse(snippet(key.redact(body))))
+    } else {
+        None
+    }
+}
+
+/// The body of a response to a request which the service rejected.
+#[derive(Deserialize)]
+struct ErrorResponse {
+    description: String,
+    errorcode: i64,
+    success: bool,
+}
+
+/// Parses a response body as an error from the service, if it is one, with the
+/// key redacted from its description.
+fn api_error(body: &str, key: &Secret) -> Option<Error> {
+    match serde_json::from_str::<ErrorResponse>(body) {
+        Ok(ref response) if response.success => None,
+        Ok(response) => Some(Error::Api {
+            code: response.errorcode,
+            description: key.redact(&response.description),
+            kind: ApiErrorCode::from(response.errorcode),
+        }),
+        Err(_) => None,
+    }
+}
+
+/// Maps the status of a response which indicates an error on its own -
+/// whatever the body - to that error, taking the time to retry after from the
+/// value of the response's `Retry-After` header if rate limited.
+///
+/// `401 Unauthorized` and `403 Forbidden` become an [`Error::InvalidKey`],
+/// `413 Payload Too Large` an [`Error::PayloadTooLarge`], and
+/// `429 Too Many Requests` an [`Error::RateLimited`].
+///
+/// [`Error::InvalidKey`]: ../../enum.Error.html#variant.InvalidKey
+/// [`Error::PayloadTooLarge`]: ../../enum.Error.html#variant.PayloadTooLarge
+/// [`Error::RateLimited`]: ../../enum.Error.html#variant.RateLimited
+pub(crate) fn status_error(status: u16, retry_after: Option<&str>)
+    -> Option<Error> {
+    match status {
+        401 | 403 => Some(Error::InvalidKey),
+        413 => Some(Error::PayloadTooLarge {
+            size: None,
+        }),
+        429 => Some(rate_limited(retry_after)),
+        _ => None,
+    }
+}
+
+/// The error of a rate limited request, given the value of the response's
+/// `Retry-After` header, which may be a number of seconds or a date.
+pub(crate) fn rate_limited(retry_after: Option<&str>) -> Error {
+    let retry_after = retry_after.and_then(|value| {
+        let value = value.trim();
+
+        if let Ok(secs) = value.parse() {
+            return Some(Duration::from_secs(secs));
+        }
+
+        // A date in the past means the request can be retried right away.
+        httpdate::parse_http_date(value).ok().map(|date| {
+            date.duration_since(SystemTime::now())
+                .unwrap_or_else(|_| Duration::from_secs(0))
+        })
+    });
+
+    Error::RateLimited { retry_after }
+}
+
+/// Maps the body of a response to the error it describes, if any, given the
+/// response's status and `Content-Type`.
+///
+/// A body containing an error from the service becomes an [`Error::Api`],
+/// unless unsuccessful responses are allowed and the status is successful.
+/// Otherwise, a response with an unsuccessful status becomes an
+/// [`Error::ServiceUnavailable`] if it is an HTML page, or an
+/// [`Error::Status`] if it is not. Both contain the start of the body with
+/// the key redacted.
+///
+/// [`Error::Api`]: ../../enum.Error.html#variant.Api
+/// [`Error::ServiceUnavailable`]: ../../enum.Error.html#variant.ServiceUnavailable
+/// [`Error::Status`]: ../../enum.Error.html#variant.Status
+pub(crate) fn body_error(
+    status: u16,
+    content_type: Option<&str>,
+    body: &str,
+    key: &Secret,
+    allow_unsuccessful: bool,
+) -> Option<Error> {
+    let success = (200..300).contains(&status);
+
+    if !allow_unsuccessful || !success {
+        if let Some(why) = api_error(body, key) {
+            return Some(why);
+        }
+    }
+
+    if success {
+        return None;
+    }
+
+    let body = snippet(key.redact(body));
+
+    if is_html(content_type, &body) {
+        Some(Error::ServiceUnavailable { status, body })
+    } else {
+        Some(Error::Status { status, body })
+    }
+}
+
+/// Whether a response is an HTML page, such as an error page from a proxy in
+/// front of the service, going by its `Content-Type` or the start of its body.
+fn is_html(content_type: Option<&str>, body: &str) -> bool {
+    let html_type = content_type
+        .map_or(false, |mime| mime.starts_with("text/html"));
+    let start = body.trim_start()
+        .chars()
+        .take(9)
+        .collect::<String>()
+        .to_lowercase();
+
+    html_type || start.starts_with("<!doctype") || start.starts_with("<html")
+}
@@ -0,0 +1,292 @@
+//! Settings for creating a client, which may be read from a configuration
+//! file.
+
+use std::env;
+use std::time::Duration;
+#[cfg(feature = "toml")]
+use toml::value::{Table, Value};
+#[cfg(feature = "toml")]
+use toml;
+use ::{Error, Result};
+
+/// The settings of a client.
+///
+/// A config can be constructed programmatically, or - with the `config`
+/// feature enabled - parsed from a TOML file via [`from_toml`]. It can then be
+/// turned into a client, such as via `OwoReqwestClient::from_settings`, or
+/// into a client builder, such as via `OwoReqwestClientBuilder::from(config)`.
+///
+/// With the `serde-items` feature enabled, a config can also be deserialized
+/// from - and serialized into - any format supported by serde, such as a
+/// section of an application's own config file. The timeout is given as a
+/// whole number of seconds. The key is never serialized, so that it doesn't
+/// end up in files; set [`key_var`] to read it from the environment instead.
+///
+/// # Examples
+///
+/// A config file containing all of the supported fields:
+///
+/// ```toml
+/// # The key to use in requests. Either this or `key_var` is required.
+/// key = "my key"
+///
+/// # The environment variable to read the key from, instead of `key`.
+/// key_var = "OWO_KEY"
+///
+/// # The base URL of the API, for self-hosted instances.
+/// base_url = "https://api.awau.moe"
+///
+/// # The domain which uploaded files are linked to.
+/// domain = "owo.whats-th.is"
+///
+/// # The paths of the endpoints, for self-hosted instances using other paths.
+/// shorten_path = "/shorten/polr"
+/// upload_path = "/upload/pomf"
+///
+/// # The timeout of each request, in seconds.
+/// timeout = 30
+/// ```
+///
+/// [`from_toml`]: #method.from_toml
+/// [`key_var`]: #structfield.key_var
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde_derive", derive(Deserialize, Serialize))]
+pub struct Config {
+    /// The base URL of the API to make requests to.
+    #[cfg_attr(
+        feature = "serde_derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub base_url: Option<String>,
+    /// The domain which uploaded files are linked to.
+    #[cfg_attr(
+        feature = "serde_derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub domain: Option<String>,
+    /// The key to use in requests.
+    ///
+    /// If empty, the key is read from the environment variable named by
+    /// [`key_var`].
+    ///
+    /// [`key_var`]: #structfield.key_var
+    #[cfg_attr(feature = "serde_derive", serde(default, skip_serializing))]
+    pub key: String,
+    /// The name of an environment variable to read the key from, if [`key`]
+    /// is empty.
+    ///
+    /// [`key`]: #structfield.key
+    #[cfg_attr(
+        feature = "serde_derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub key_var: Option<String>,
+    /// The path of the endpoint to shorten URLs, relative to the base URL.
+    #[cfg_attr(
+        feature = "serde_derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub shorten_path: Option<String>,
+    /// The timeout of each request.
+    #[cfg_attr(
+        feature = "serde_derive",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "timeout_secs"
+        )
+    )]
+    pub timeout: Option<Duration>,
+    /// The path of the endpoint to upload files, relative to the base URL.
+    #[cfg_attr(
+        feature = "serde_derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub upload_path: Option<String>,
+}
+
+impl Config {
+    /// Creates a new config using the given key, with all other settings left
+    /// as their defaults.
+    pub fn new<S: Into<String>>(key: S) -> Self {
+        Self {
+            base_url: None,
+            domain: None,
+            key: key.into(),
+            key_var: None,
+            shorten_path: None,
+            timeout: None,
+            upload_path: None,
+        }
+    }
+
+    /// Resolves the key to use in requests: the [`key`] if it is not empty,
+    /// or otherwise the value of the environment variable named by
+    /// [`key_var`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use owo::Config;
+    ///
+    /// let config = Config::new("my key");
+    /// assert_eq!(config.resolve_key().unwrap(), "my key");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] if the key is empty and no environment
+    /// variable is named, or if the variable is not present or is not valid
+    /// unicode.
+    ///
+    /// [`Error::Config`]: ../enum.Error.html#variant.Config
+    /// [`key`]: #structfield.key
+    /// [`key_var`]: #structfield.key_var
+    pub fn resolve_key(&self) -> Result<String> {
+        if !self.key.is_empty() {
+            return Ok(self.key.clone());
+        }
+
+        match self.key_var {
+            Some(ref var) => env::var(var).map_err(|why| Error::Config {
+                field: var.clone(),
+                reason: why.to_string(),
+            }),
+            None => Err(Error::Config {
+                field: "key".to_owned(),
+                reason: "missing".to_owned(),
+            }),
+        }
+    }
+
+    /// Parses a config from the contents of a TOML file.
+    ///
+    /// Unknown fields are ignored - with a warning logged via the `log` crate -
+    /// so that a config file can be shared with newer versions of the library,
+    /// or with other programs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate owo;
+    /// #
+    /// # fn try_main() -> owo::Result<()> {
+    /// use owo::Config;
+    /// use std::time::Duration;
+    ///
+    /// let config = Config::from_toml("key = \"my key\"\ntimeout = 30")?;
+    ///
+    /// assert_eq!(config.key, "my key");
+    /// assert_eq!(config.timeout, Some(Duration::from_secs(30)));
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Toml`] if the contents are not valid TOML.
+    ///
+    /// Returns [`Error::Config`] if both the `key` and `key_var` fields are
+    /// missing, or if a field has a value of the wrong type.
+    ///
+    /// [`Error::Config`]: ../enum.Error.html#variant.Config
+    /// [`Error::Toml`]: ../enum.Error.html#variant.Toml
+    #[cfg(feature = "toml")]
+    pub fn from_toml(contents: &str) -> Result<Self> {
+        let table = toml::from_str::<Table>(contents)?;
+
+        let mut config = Config::new(String::new());
+        let mut key = None;
+
+        for (field, value) in table {
+            match field.as_str() {
+                "base_url" => config.base_url = Some(string(&field, value)?),
+                "domain" => config.domain = Some(string(&field, value)?),
+                "key" => key = Some(string(&field, value)?),
+                "key_var" => config.key_var = Some(string(&field, value)?),
+                "shorten_path" => {
+                    config.shorten_path = Some(string(&field, value)?);
+                },
+                "timeout" => config.timeout = Some(seconds(&field, value)?),
+                "upload_path" => {
+                    config.upload_path = Some(string(&field, value)?);
+                },
+                _ => warn!("Ignoring unknown config field `{}`", field),
+            }
+        }
+
+        match key {
+            Some(key) => config.key = key,
+            None if config.key_var.is_some() => {},
+            None => return Err(Error::Config {
+                field: "key".to_owned(),
+                reason: "missing".to_owned(),
+            }),
+        }
+
+        Ok(config)
+    }
+}
+
+/// Takes the value of a field which must be a string.
+#[cfg(feature = "toml")]
+fn string(field: &str, value: Value) -> Result<String> {
+    match value {
+        Value::String(string) => Ok(string),
+        other => Err(Error::Config {
+            field: field.to_owned(),
+            reason: format!("expected a string, found {}", other.type_str()),
+        }),
+    }
+}
+
+/// Takes the value of a field which must be a whole, non-negative number of
+/// seconds.
+#[cfg(feature = "toml")]
+fn seconds(field: &str, value: Value) -> Result<Duration> {
+    match value {
+        Value::Integer(secs) if secs >= 0 => {
+            Ok(Duration::from_secs(secs as u64))
+        },
+        Value::Integer(secs) => Err(Error::Config {
+            field: field.to_owned(),
+            reason: format!("expected a number of seconds, found {}", secs),
+        }),
+        other => Err(Error::Config {
+            field: field.to_owned(),
+            reason: format!(
+                "expected a number of seconds, found {}",
+                other.type_str(),
+            ),
+        }),
+    }
+}
+
+/// (De)serializes an optional timeout as a whole number of seconds, matching
+/// the `timeout` field of TOML config files.
+#[cfg(feature = "serde_derive")]
+mod timeout_secs {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(
+        timeout: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match *timeout {
+            Some(timeout) => serializer.serialize_some(&timeout.as_secs()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D)
+        -> Result<Option<Duration>, D::Error> {
+        let secs = Option::<u64>::deserialize(deserializer)?;
+
+        Ok(secs.map(Duration::from_secs))
+    }
+}
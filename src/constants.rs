@@ -3,14 +3,32 @@
 // not be used to format due to macro rule restrictions, but are here for
 // completion.
 
+/// The base URL of the service's API, which requests are made to by default.
+pub const API_URL: &str = "https://api.awau.moe";
+/// The domain which uploaded files are served from by default.
+///
+/// The [`UploadedFile::url`] fragment is relative to this domain.
+///
+/// [`UploadedFile::url`]: ../model/struct.UploadedFile.html#structfield.url
+pub const DEFAULT_DOMAIN: &str = "owo.whats-th.is";
+/// The maximum size of each uploaded file, in bytes, on the official service.
+pub const MAX_FILE_SIZE: u64 = 80 * 1024 * 1024;
 /// The maximum number of files that may be uploaded in one requests.
 pub const MAX_FILES: usize = 3;
+/// The maximum length of a URL to shorten, in bytes, on the official service.
+pub const MAX_URL_LENGTH: usize = 2048;
+/// The path of the endpoint to shorten URLs, relative to the base URL of the
+/// API.
+pub const SHORTEN_PATH: &str = "/shorten/polr";
 /// The URL to POST to, to request shortened URLs.
-pub const SHORTEN_URL: &'static str = "https://api.awau.moe/shorten/polr?action=shorten&url={}&key={}";
+pub const SHORTEN_URL: &str = "https://api.awau.moe/shorten/polr?action=shorten&url={}&key={}";
+/// The path of the endpoint to upload files, relative to the base URL of the
+/// API.
+pub const UPLOAD_PATH: &str = "/upload/pomf";
 /// The URL to POST to, to upload files.
-pub const UPLOAD_URL: &'static str = "https://api.awau.moe/upload/pomf?key={}";
+pub const UPLOAD_URL: &str = "https://api.awau.moe/upload/pomf?key={}";
 /// The user agent to send along with requests.
-pub const USER_AGENT: &'static str = concat!(
+pub const USER_AGENT: &str = concat!(
     "WhatsThisClient (https://github.com/whats-this/owo.rs, ",
     env!("CARGO_PKG_VERSION"),
     ")",
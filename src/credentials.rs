@@ -0,0 +1,97 @@
+//! Storage of keys in the operating system's credential store, such as the
+//! macOS Keychain, the Windows Credential Manager, or the Secret Service on
+//! Linux.
+//!
+//! This is only available with the `keyring` feature.
+//!
+//! Keys are stored as the password of an entry identified by a service and
+//! account name, such as `"owo"` and the name of the user.
+//!
+//! # Examples
+//!
+//! Store a key once, such as during the setup of a program, and then load it
+//! whenever a client is needed:
+//!
+//! ```rust,no_run
+//! # fn try_main() -> owo::Result<()> {
+//! use owo::credentials;
+//!
+//! credentials::store_key("owo", "alice", "my key")?;
+//!
+//! let key = credentials::load_key("owo", "alice")?;
+//! #     Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! #     try_main().unwrap();
+//! # }
+//! ```
+
+use keyring::{Keyring, KeyringError};
+use ::secret::Secret;
+use ::{Error, Result};
+
+/// Loads the key stored for the given service and account.
+///
+/// # Errors
+///
+/// Returns [`Error::KeyNotFound`] if no key is stored for the service and
+/// account.
+///
+/// Returns [`Error::Keyring`] if the credential store could not be accessed.
+///
+/// [`Error::KeyNotFound`]: ../enum.Error.html#variant.KeyNotFound
+/// [`Error::Keyring`]: ../enum.Error.html#variant.Keyring
+pub fn load_key(service: &str, account: &str) -> Result<String> {
+    match Keyring::new(service, account).get_password() {
+        Ok(key) => Ok(key),
+        Err(KeyringError::NoPasswordFound) => Err(Error::KeyNotFound {
+            account: account.to_owned(),
+            service: service.to_owned(),
+        }),
+        Err(why) => Err(Error::Keyring(why.to_string())),
+    }
+}
+
+/// Stores a key for the given service and account, replacing any key already
+/// stored for them.
+///
+/// The key is trimmed of surrounding whitespace before being stored.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidKey`] if the key is empty.
+///
+/// Returns [`Error::Keyring`] if the credential store could not be accessed.
+///
+/// [`Error::InvalidKey`]: ../enum.Error.html#variant.InvalidKey
+/// [`Error::Keyring`]: ../enum.Error.html#variant.Keyring
+pub fn store_key(service: &str, account: &str, key: &str) -> Result<()> {
+    let key = Secret::key(key.to_owned())?;
+
+    Keyring::new(service, account)
+        .set_password(key.as_str())
+        .map_err(|why| Error::Keyring(why.to_string()))
+}
+
+/// Deletes the key stored for the given service and account.
+///
+/// # Errors
+///
+/// Returns [`Error::KeyNotFound`] if no key is stored for the service and
+/// account.
+///
+/// Returns [`Error::Keyring`] if the credential store could not be accessed.
+///
+/// [`Error::KeyNotFound`]: ../enum.Error.html#variant.KeyNotFound
+/// [`Error::Keyring`]: ../enum.Error.html#variant.Keyring
+pub fn delete_key(service: &str, account: &str) -> Result<()> {
+    match Keyring::new(service, account).delete_password() {
+        Ok(()) => Ok(()),
+        Err(KeyringError::NoPasswordFound) => Err(Error::KeyNotFound {
+            account: account.to_owned(),
+            service: service.to_owned(),
+        }),
+        Err(why) => Err(Error::Keyring(why.to_string())),
+    }
+}
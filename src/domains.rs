@@ -0,0 +1,114 @@
+//! The domains which the official service serves uploaded files from, for
+//! linking to files on a domain other than [`constants::DEFAULT_DOMAIN`].
+//!
+//! [`constants::DEFAULT_DOMAIN`]: ../constants/constant.DEFAULT_DOMAIN.html
+
+use ::model::UploadedFile;
+use ::{Error, Result};
+
+/// The domains which the official service serves uploaded files from, as
+/// published by the service.
+///
+/// Each serves the same files, so an uploaded file can be linked to on any
+/// of them. The service may add domains before this list is updated, so
+/// clients can still be given other domains via `domain`.
+pub const KNOWN: &[&str] = &[
+    "owo.whats-th.is",
+    "i.whats-th.is",
+    "uwu.whats-th.is",
+    "i.am-a.furry",
+    "buttsare.sexy",
+    "nyanyanya.moe",
+    "all-your.data.are.belong.to.us",
+];
+
+/// Whether a domain is one of the [known domains].
+///
+/// The domain may be given with or without an `http` or `https` scheme and a
+/// trailing slash, and is compared case-insensitively.
+///
+/// # Examples
+///
+/// ```rust
+/// use owo::domains;
+///
+/// assert!(domains::is_known("uwu.whats-th.is"));
+/// assert!(domains::is_known("https://UWU.whats-th.is/"));
+/// assert!(!domains::is_known("example.com"));
+/// ```
+///
+/// [known domains]: constant.KNOWN.html
+pub fn is_known(domain: &str) -> bool {
+    find(domain).is_some()
+}
+
+/// Validates that a domain is one of the [known domains], returning it as it
+/// appears in the list.
+///
+/// # Examples
+///
+/// ```rust
+/// use owo::domains;
+///
+/// let domain = domains::validate("https://uwu.whats-th.is/").unwrap();
+/// assert_eq!(domain, "uwu.whats-th.is");
+/// assert!(domains::validate("example.com").is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::UnknownDomain`] if the domain is not a known domain.
+///
+/// [`Error::UnknownDomain`]: ../enum.Error.html#variant.UnknownDomain
+/// [known domains]: constant.KNOWN.html
+pub fn validate(domain: &str) -> Result<&'static str> {
+    find(domain).ok_or_else(|| Error::UnknownDomain(domain.to_owned()))
+}
+
+/// Produces the full URL to an uploaded file on one of the [known domains].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use owo::model::UploadedFile;
+/// #
+/// # fn try_main(file: UploadedFile) -> owo::Result<()> {
+/// use owo::domains;
+///
+/// // Prints a URL such as "https://uwu.whats-th.is/a1b2c3.png".
+/// println!("{}", domains::full_url(&file, "uwu.whats-th.is")?);
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::UnknownDomain`] if the domain is not a known domain.
+///
+/// [`Error::UnknownDomain`]: ../enum.Error.html#variant.UnknownDomain
+/// [known domains]: constant.KNOWN.html
+pub fn full_url(file: &UploadedFile, domain: &str) -> Result<String> {
+    validate(domain).map(|domain| file.full_url_on(domain))
+}
+
+fn find(domain: &str) -> Option<&'static str> {
+    let domain = domain.trim();
+    let domain = strip_scheme(domain).trim_end_matches('/');
+
+    KNOWN.iter()
+        .find(|known| known.eq_ignore_ascii_case(domain))
+        .copied()
+}
+
+fn strip_scheme(domain: &str) -> &str {
+    for scheme in &["https://", "http://"] {
+        match domain.get(..scheme.len()) {
+            Some(start) if start.eq_ignore_ascii_case(scheme) => {
+                return &domain[scheme.len()..];
+            },
+            _ => {},
+        }
+    }
+
+    domain
+}
@@ -0,0 +1,278 @@
+//! Builders for the URLs of the service's endpoints.
+//!
+//! Keys are trimmed of surrounding whitespace and percent-encoded when placed
+//! into a URL, so that a key pasted with a trailing newline - or containing
+//! characters such as `&` - still produces a well-formed URL.
+
+#[cfg(any(feature = "hyper", feature = "reqwest"))]
+use std::env::{self, VarError};
+use std::fmt::{Display, Formatter, Result as FmtResult, Write};
+use std::time::Duration;
+use ::constants;
+#[cfg(any(feature = "hyper", feature = "reqwest"))]
+use ::{Error, Result};
+
+/// The name of the environment variable which overrides the base URL of the
+/// API, such as to redirect requests to a staging instance.
+pub const API_URL_VAR: &str = "OWO_API_URL";
+
+/// Produces the URL to POST to, to upload files.
+///
+/// # Examples
+///
+/// ```rust
+/// use owo::endpoints;
+///
+/// assert_eq!(
+///     endpoints::upload("my key\n"),
+///     "https://api.awau.moe/upload/pomf?key=my%20key",
+/// );
+/// ```
+pub fn upload(key: &str) -> String {
+    upload_at(constants::API_URL, constants::UPLOAD_PATH, key)
+}
+
+/// Produces the URL to POST to, to upload files which expire after the given
+/// duration.
+///
+/// The expiry is given in whole seconds as the `expiry` query parameter.
+pub fn upload_with_expiry(key: &str, expiry: Duration) -> String {
+    upload_with_expiry_at(
+        constants::API_URL,
+        constants::UPLOAD_PATH,
+        key,
+        expiry,
+    )
+}
+
+/// Produces the URL to GET, to shorten the given URL.
+pub fn shorten(key: &str, url: &str) -> String {
+    shorten_at(constants::API_URL, constants::SHORTEN_PATH, key, url)
+}
+
+/// Produces the URL to GET, to shorten the given URL, with the response being a
+/// JSON [`ShortenResponse`] rather than the plain short URL.
+///
+/// [`ShortenResponse`]: ../model/struct.ShortenResponse.html
+pub fn shorten_json(key: &str, url: &str) -> String {
+    shorten_json_at(constants::API_URL, constants::SHORTEN_PATH, key, url)
+}
+
+/// Produces the URL to GET, to shorten the given URL with a description
+/// attached to the short link.
+///
+/// The description is given as the `description` query parameter, which
+/// instances that don't store descriptions ignore.
+///
+/// # Examples
+///
+/// ```rust
+/// use owo::endpoints;
+///
+/// assert_eq!(
+///     endpoints::shorten_with_description("key", "https://google.com", "a&b"),
+///     "https://api.awau.moe/shorten/polr?action=shorten\
+///      &url=https://google.com&key=key&description=a%26b",
+/// );
+/// ```
+pub fn shorten_with_description(key: &str, url: &str, description: &str)
+    -> String {
+    shorten_with_description_at(
+        constants::API_URL,
+        constants::SHORTEN_PATH,
+        key,
+        url,
+        description,
+    )
+}
+
+/// Splits a short URL, such as one returned when shortening a URL, into its
+/// host and its slug - the final path segment.
+///
+/// Any query string, fragment, or trailing slash is ignored. Returns `None`
+/// if the URL has no scheme, host, or slug.
+///
+/// # Examples
+///
+/// ```rust
+/// use owo::endpoints;
+///
+/// assert_eq!(
+///     endpoints::parse_short_url("https://awau.moe/a1b2c3/?ref=x"),
+///     Some(("awau.moe".to_owned(), "a1b2c3".to_owned())),
+/// );
+/// assert_eq!(endpoints::parse_short_url("https://awau.moe/"), None);
+/// ```
+pub fn parse_short_url(url: &str) -> Option<(String, String)> {
+    let url = url.trim();
+    let end = url.find(|c| c == '?' || c == '#').unwrap_or(url.len());
+    let url = url[..end].trim_end_matches('/');
+
+    // Without a scheme separator, a URL has no path to take a slug from.
+    let url = &url[url.find("://")? + 3..];
+    let slash = url.find('/')?;
+    let (host, path) = (&url[..slash], &url[slash + 1..]);
+
+    match path.rsplit('/').next() {
+        Some(slug) if !host.is_empty() && !slug.is_empty() => {
+            Some((host.to_owned(), slug.to_owned()))
+        },
+        _ => None,
+    }
+}
+
+/// Produces the URL to POST to, to upload files, on the API at the given base
+/// URL and upload path.
+pub(crate) fn upload_at(base: &str, path: &str, key: &str) -> String {
+    format!(
+        "{}{}?key={}",
+        base.trim_end_matches('/'),
+        path,
+        QueryValue(key.trim()),
+    )
+}
+
+/// Produces the URL to POST to, to upload files which expire after the given
+/// duration, on the API at the given base URL and upload path.
+pub(crate) fn upload_with_expiry_at(
+    base: &str,
+    path: &str,
+    key: &str,
+    expiry: Duration,
+) -> String {
+    format!("{}&expiry={}", upload_at(base, path, key), expiry.as_secs())
+}
+
+/// Produces the URL to GET, to shorten the given URL, on the API at the given
+/// base URL and shorten path.
+pub(crate) fn shorten_at(base: &str, path: &str, key: &str, url: &str)
+    -> String {
+    format!(
+        "{}{}?action=shorten&url={}&key={}",
+        base.trim_end_matches('/'),
+        path,
+        url,
+        QueryValue(key.trim()),
+    )
+}
+
+/// Produces the URL to GET, to shorten the given URL with a JSON response, on
+/// the API at the given base URL and shorten path.
+pub(crate) fn shorten_json_at(base: &str, path: &str, key: &str, url: &str)
+    -> String {
+    format!("{}&response_type=json", shorten_at(base, path, key, url))
+}
+
+/// Produces the URL to GET, to shorten the given URL with a description, on
+/// the API at the given base URL and shorten path.
+pub(crate) fn shorten_with_description_at(
+    base: &str,
+    path: &str,
+    key: &str,
+    url: &str,
+    description: &str,
+) -> String {
+    format!(
+        "{}&description={}",
+        shorten_at(base, path, key, url),
+        QueryValue(description),
+    )
+}
+
+/// Resolves the base URL of the API to make requests to.
+///
+/// An explicitly configured base URL takes precedence, followed by the
+/// [`API_URL_VAR`] environment variable, followed by [`constants::API_URL`].
+///
+/// [`API_URL_VAR`]: constant.API_URL_VAR.html
+/// [`constants::API_URL`]: ../constants/constant.API_URL.html
+#[cfg(any(feature = "hyper", feature = "reqwest"))]
+pub(crate) fn base_url(explicit: Option<String>) -> Result<String> {
+    let (field, base_url) = match explicit {
+        Some(base_url) => ("base_url", base_url),
+        None => match env::var(API_URL_VAR) {
+            Ok(base_url) => (API_URL_VAR, base_url),
+            Err(VarError::NotPresent) => return Ok(constants::API_URL.into()),
+            Err(VarError::NotUnicode(_)) => return Err(Error::Config {
+                field: API_URL_VAR.to_owned(),
+                reason: "not valid unicode".to_owned(),
+            }),
+        },
+    };
+
+    check_base_url(field, base_url)
+}
+
+/// Checks that a base URL is an absolute `http` or `https` URL, naming the
+/// setting it came from in the error otherwise.
+#[cfg(any(feature = "hyper", feature = "reqwest"))]
+pub(crate) fn check_base_url(field: &str, base_url: String) -> Result<String> {
+    if !is_absolute_http_url(&base_url) {
+        return Err(Error::Config {
+            field: field.to_owned(),
+            reason: format!("expected an absolute http(s) URL: {}", base_url),
+        });
+    }
+
+    Ok(base_url)
+}
+
+/// Resolves the path of an endpoint relative to the base URL of the API,
+/// falling back to the given default if none was configured.
+///
+/// A leading slash is added to the path if it lacks one.
+#[cfg(any(feature = "hyper", feature = "reqwest"))]
+pub(crate) fn path(field: &str, explicit: Option<String>, default: &str)
+    -> Result<String> {
+    let path = match explicit {
+        Some(path) => path,
+        None => return Ok(default.to_owned()),
+    };
+
+    if path.contains(|c: char| c == '?' || c == '#' || c.is_whitespace()) {
+        return Err(Error::Config {
+            field: field.to_owned(),
+            reason: format!("expected a path without a query: {}", path),
+        });
+    }
+
+    if path.starts_with('/') {
+        Ok(path)
+    } else {
+        Ok(format!("/{}", path))
+    }
+}
+
+/// Whether a URL is absolute with an `http` or `https` scheme, and a host.
+#[cfg(any(feature = "hyper", feature = "reqwest"))]
+pub(crate) fn is_absolute_http_url(url: &str) -> bool {
+    let rest = if url.starts_with("https://") {
+        &url[8..]
+    } else if url.starts_with("http://") {
+        &url[7..]
+    } else {
+        return false;
+    };
+    let host = rest.split(|c| c == '/' || c == '?' || c == '#')
+        .next()
+        .unwrap_or("");
+
+    !host.is_empty() && !host.contains(char::is_whitespace)
+}
+
+/// A value to be percent-encoded when formatted into a query string.
+struct QueryValue<'a>(&'a str);
+
+impl<'a> Display for QueryValue<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        for byte in self.0.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9'
+                | b'-' | b'_' | b'.' | b'~' => f.write_char(byte as char)?,
+                _ => write!(f, "%{:02X}", byte)?,
+            }
+        }
+
+        Ok(())
+    }
+}
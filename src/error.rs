@@ -1,17 +1,34 @@
+use std::cmp;
+#[cfg(feature = "backtrace")]
+use std::env;
 use std::error::Error as StdError;
-use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::fmt::{self, DebugTuple, Display, Formatter, Result as FmtResult};
+use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+use std::path::PathBuf;
 use std::result::Result as StdResult;
+use std::sync::Arc;
+use std::time::Duration;
 
+#[cfg(feature = "backtrace")]
+use backtrace::Backtrace;
 #[cfg(feature = "hyper")]
 use hyper::error::UriError;
+#[cfg(feature = "hyper")]
+use hyper::Error as HyperError;
 #[cfg(feature = "native-tls")]
 use native_tls::Error as NativeTlsError;
-#[cfg(feature = "reqwest")]
-use std::io::Error as IoError;
+#[cfg(feature = "reqwest-hyper")]
+use reqwest_hyper::Error as ReqwestHyperError;
+#[cfg(feature = "reqwest-native-tls")]
+use reqwest_native_tls::Error as ReqwestNativeTlsError;
+#[cfg(feature = "rustls")]
+use rustls::TLSError;
 #[cfg(feature = "serde_json")]
 use serde_json::Error as JsonError;
 #[cfg(feature = "reqwest")]
 use reqwest::Error as ReqwestError;
+#[cfg(feature = "toml")]
+use toml::de::Error as TomlError;
 
 /// A result type to compose a successful value and the library's [`Error`]
 /// type.
@@ -19,50 +36,1544 @@ use reqwest::Error as ReqwestError;
 /// [`Error`]: enum.Error.html
 pub type Result<T> = StdResult<T, Error>;
 
+/// The meaning of an error code returned by the service, as contained in
+/// [`Error::Api`].
+///
+/// [`Error::Api`]: enum.Error.html#variant.Api
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ApiErrorCode {
+    /// The request was malformed, such as containing no files.
+    BadRequest,
+    /// The key is banned from using the service.
+    BannedKey,
+    /// The type of a file is not allowed by the service.
+    DisallowedType,
+    /// A file is larger than the service allows.
+    FileTooLarge,
+    /// The service encountered an error of its own.
+    InternalError,
+    /// The key is missing or not recognized by the service.
+    InvalidKey,
+    /// Too many requests have been made in a short period of time.
+    RateLimited,
+    /// An error code which is not known to the library, containing the code.
+    Unknown(i64),
+}
+
+impl ApiErrorCode {
+    /// Retrieves the numeric error code.
+    pub fn code(&self) -> i64 {
+        match *self {
+            ApiErrorCode::BadRequest => 400,
+            ApiErrorCode::BannedKey => 403,
+            ApiErrorCode::DisallowedType => 415,
+            ApiErrorCode::FileTooLarge => 413,
+            ApiErrorCode::InternalError => 500,
+            ApiErrorCode::InvalidKey => 401,
+            ApiErrorCode::RateLimited => 429,
+            ApiErrorCode::Unknown(code) => code,
+        }
+    }
+
+    /// Retrieves a human-readable meaning of the error code, if it is a known
+    /// one.
+    pub fn meaning(&self) -> Option<&'static str> {
+        Some(match *self {
+            ApiErrorCode::BadRequest => "bad request",
+            ApiErrorCode::BannedKey => "banned key",
+            ApiErrorCode::DisallowedType => "disallowed file type",
+            ApiErrorCode::FileTooLarge => "file too large",
+            ApiErrorCode::InternalError => "internal service error",
+            ApiErrorCode::InvalidKey => "invalid key",
+            ApiErrorCode::RateLimited => "rate limited",
+            ApiErrorCode::Unknown(_) => return None,
+        })
+    }
+}
+
+impl From<i64> for ApiErrorCode {
+    fn from(code: i64) -> ApiErrorCode {
+        match code {
+            400 => ApiErrorCode::BadRequest,
+            401 => ApiErrorCode::InvalidKey,
+            403 => ApiErrorCode::BannedKey,
+            413 => ApiErrorCode::FileTooLarge,
+            415 => ApiErrorCode::DisallowedType,
+            429 => ApiErrorCode::RateLimited,
+            500 => ApiErrorCode::InternalError,
+            other => ApiErrorCode::Unknown(other),
+        }
+    }
+}
+
+/// The kind of an [`Error`], as retrieved via [`Error::kind`].
+///
+/// Unlike [`Error`], kinds carry no data and can be compared, such as in
+/// assertions. Every kind is available regardless of the enabled features, so
+/// code comparing kinds compiles with any combination of them.
+///
+/// [`Error`]: enum.Error.html
+/// [`Error::kind`]: enum.Error.html#method.kind
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The kind of [`Error::Api`].
+    ///
+    /// [`Error::Api`]: enum.Error.html#variant.Api
+    Api,
+    /// The kind of [`Error::ApiFailure`].
+    ///
+    /// [`Error::ApiFailure`]: enum.Error.html#variant.ApiFailure
+    ApiFailure,
+    /// The kind of [`Error::Batch`].
+    ///
+    /// [`Error::Batch`]: enum.Error.html#variant.Batch
+    Batch,
+    /// The kind of [`Error::CertificatePinMismatch`].
+    ///
+    /// [`Error::CertificatePinMismatch`]: enum.Error.html#variant.CertificatePinMismatch
+    CertificatePinMismatch,
+    /// The kind of [`Error::Config`].
+    ///
+    /// [`Error::Config`]: enum.Error.html#variant.Config
+    Config,
+    /// The kind of [`Error::File`].
+    ///
+    /// [`Error::File`]: enum.Error.html#variant.File
+    File,
+    /// The kind of [`Error::FileTooLarge`].
+    ///
+    /// [`Error::FileTooLarge`]: enum.Error.html#variant.FileTooLarge
+    FileTooLarge,
+    /// The kind of [`Error::HtmlResponse`].
+    ///
+    /// [`Error::HtmlResponse`]: enum.Error.html#variant.HtmlResponse
+    HtmlResponse,
+    /// The kind of [`Error::Hyper`].
+    ///
+    /// [`Error::Hyper`]: enum.Error.html#variant.Hyper
+    Hyper,
+    /// The kind of [`Error::InvalidBoundary`].
+    ///
+    /// [`Error::InvalidBoundary`]: enum.Error.html#variant.InvalidBoundary
+    InvalidBoundary,
+    /// The kind of [`Error::InvalidJson`].
+    ///
+    /// [`Error::InvalidJson`]: enum.Error.html#variant.InvalidJson
+    InvalidJson,
+    /// The kind of [`Error::InvalidKey`].
+    ///
+    /// [`Error::InvalidKey`]: enum.Error.html#variant.InvalidKey
+    InvalidKey,
+    /// The kind of [`Error::Io`].
+    ///
+    /// [`Error::Io`]: enum.Error.html#variant.Io
+    Io,
+    /// The kind of [`Error::KeyNotFound`].
+    ///
+    /// [`Error::KeyNotFound`]: enum.Error.html#variant.KeyNotFound
+    KeyNotFound,
+    /// The kind of [`Error::Keyring`].
+    ///
+    /// [`Error::Keyring`]: enum.Error.html#variant.Keyring
+    Keyring,
+    /// The kind of [`Error::Json`].
+    ///
+    /// [`Error::Json`]: enum.Error.html#variant.Json
+    Json,
+    /// The kind of [`Error::NativeTls`].
+    ///
+    /// [`Error::NativeTls`]: enum.Error.html#variant.NativeTls
+    NativeTls,
+    /// The kind of [`Error::NoFilesReturned`].
+    ///
+    /// [`Error::NoFilesReturned`]: enum.Error.html#variant.NoFilesReturned
+    NoFilesReturned,
+    /// The kind of [`Error::NoKeyConfigured`].
+    ///
+    /// [`Error::NoKeyConfigured`]: enum.Error.html#variant.NoKeyConfigured
+    NoKeyConfigured,
+    /// The kind of [`Error::PayloadTooLarge`].
+    ///
+    /// [`Error::PayloadTooLarge`]: enum.Error.html#variant.PayloadTooLarge
+    PayloadTooLarge,
+    /// The kind of [`Error::RateLimited`].
+    ///
+    /// [`Error::RateLimited`]: enum.Error.html#variant.RateLimited
+    RateLimited,
+    /// The kind of [`Error::Reqwest`].
+    ///
+    /// [`Error::Reqwest`]: enum.Error.html#variant.Reqwest
+    Reqwest,
+    /// The kind of [`Error::ResponseTooLarge`].
+    ///
+    /// [`Error::ResponseTooLarge`]: enum.Error.html#variant.ResponseTooLarge
+    ResponseTooLarge,
+    /// The kind of [`Error::ServiceUnavailable`].
+    ///
+    /// [`Error::ServiceUnavailable`]: enum.Error.html#variant.ServiceUnavailable
+    ServiceUnavailable,
+    /// The kind of [`Error::Status`].
+    ///
+    /// [`Error::Status`]: enum.Error.html#variant.Status
+    Status,
+    /// The kind of [`Error::Timeout`].
+    ///
+    /// [`Error::Timeout`]: enum.Error.html#variant.Timeout
+    Timeout,
+    /// The kind of [`Error::Toml`].
+    ///
+    /// [`Error::Toml`]: enum.Error.html#variant.Toml
+    Toml,
+    /// The kind of [`Error::TooManyFiles`].
+    ///
+    /// [`Error::TooManyFiles`]: enum.Error.html#variant.TooManyFiles
+    TooManyFiles,
+    /// The kind of [`Error::UnexpectedResponse`].
+    ///
+    /// [`Error::UnexpectedResponse`]: enum.Error.html#variant.UnexpectedResponse
+    UnexpectedResponse,
+    /// The kind of [`Error::UnknownDomain`].
+    ///
+    /// [`Error::UnknownDomain`]: enum.Error.html#variant.UnknownDomain
+    UnknownDomain,
+    /// The kind of [`Error::UrlTooLong`].
+    ///
+    /// [`Error::UrlTooLong`]: enum.Error.html#variant.UrlTooLong
+    UrlTooLong,
+    /// The kind of [`Error::Uri`].
+    ///
+    /// [`Error::Uri`]: enum.Error.html#variant.Uri
+    Uri,
+}
+
+/// The kind of a network error, as retrieved via [`Error::network_kind`].
+///
+/// The kind is taken from the error of the HTTP client, so is the same
+/// regardless of which bridge made the request. Errors from the hyper bridge
+/// can be classified via [`from_hyper`].
+///
+/// [`Error::network_kind`]: enum.Error.html#method.network_kind
+/// [`from_hyper`]: #method.from_hyper
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum NetworkErrorKind {
+    /// A connection to the service could not be established, such as when it
+    /// was refused.
+    Connect,
+    /// The host name of the service could not be resolved.
+    ///
+    /// The HTTP clients in use don't report failures to resolve a host name
+    /// as a distinct error, so these are currently classified as
+    /// [`Connect`].
+    ///
+    /// [`Connect`]: #variant.Connect
+    Dns,
+    /// A network error which could not be classified further.
+    Other,
+    /// An established connection was closed or reset unexpectedly.
+    Reset,
+    /// The request timed out.
+    Timeout,
+    /// The TLS handshake with the service failed, such as when its
+    /// certificate is invalid.
+    Tls,
+}
+
+impl NetworkErrorKind {
+    /// Classifies an IO error, such as one from connecting to the service.
+    pub fn from_io(error: &IoError) -> Self {
+        match error.kind() {
+            IoErrorKind::AddrNotAvailable
+                | IoErrorKind::ConnectionRefused => NetworkErrorKind::Connect,
+            IoErrorKind::BrokenPipe
+                | IoErrorKind::ConnectionAborted
+                | IoErrorKind::ConnectionReset
+                | IoErrorKind::UnexpectedEof => NetworkErrorKind::Reset,
+            // Sockets with a read timeout report it as `WouldBlock` on Unix.
+            IoErrorKind::TimedOut
+                | IoErrorKind::WouldBlock => NetworkErrorKind::Timeout,
+            _ => {
+                // TLS connectors report handshake failures as IO errors
+                // wrapping the error from the TLS backend.
+                if error.get_ref().map_or(false, |inner| is_tls_error(inner)) {
+                    return NetworkErrorKind::Tls;
+                }
+
+                // Connectors may wrap the IO error of the TLS backend - which
+                // itself wraps the TLS error with `rustls` - in another.
+                let nested = error.get_ref()
+                    .and_then(|inner| inner.downcast_ref::<IoError>());
+
+                if let Some(nested) = nested {
+                    return NetworkErrorKind::from_io(nested);
+                }
+
+                #[cfg(feature = "hyper")]
+                {
+                    let connect = error.get_ref()
+                        .map_or(false, |inner| inner.is::<ConnectError>());
+
+                    if connect {
+                        return NetworkErrorKind::Connect;
+                    }
+                }
+
+                NetworkErrorKind::Other
+            },
+        }
+    }
+
+    /// Classifies an error from the `hyper` crate, such as one resolved by a
+    /// future of the hyper bridge, if it is a network error.
+    #[cfg(feature = "hyper")]
+    pub fn from_hyper(error: &HyperError) -> Option<Self> {
+        match *error {
+            HyperError::Io(ref inner) => Some(NetworkErrorKind::from_io(inner)),
+            HyperError::Incomplete => Some(NetworkErrorKind::Reset),
+            HyperError::Timeout => Some(NetworkErrorKind::Timeout),
+            _ => None,
+        }
+    }
+}
+
+/// An error from connecting to the service via the hyper bridge which can't be
+/// classified by its kind, such as a failure to resolve the host name of the
+/// service.
+///
+/// The error is wrapped so that it's classified as
+/// [`NetworkErrorKind::Connect`], rather than as an error from an established
+/// connection.
+///
+/// [`NetworkErrorKind::Connect`]: enum.NetworkErrorKind.html#variant.Connect
+#[cfg(feature = "hyper")]
+#[derive(Debug)]
+pub(crate) struct ConnectError(pub(crate) IoError);
+
+#[cfg(feature = "hyper")]
+impl Display for ConnectError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "hyper")]
+impl StdError for ConnectError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.0)
+    }
+}
+
+/// Whether an error is from one of the TLS backends of the bridges.
+#[allow(unused_variables)]
+fn is_tls_error(error: &(dyn StdError + 'static)) -> bool {
+    #[cfg(feature = "native-tls")]
+    {
+        if error.is::<NativeTlsError>() {
+            return true;
+        }
+    }
+
+    #[cfg(feature = "reqwest-native-tls")]
+    {
+        if error.is::<ReqwestNativeTlsError>() {
+            return true;
+        }
+    }
+
+    #[cfg(feature = "rustls")]
+    {
+        if error.is::<TLSError>() {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Classifies an error from the `reqwest` crate which did not come from a
+/// response, by the errors which it wraps.
+///
+/// The errors of the HTTP client used by `reqwest` wrap the IO or TLS error
+/// which caused them, so the chain of sources is searched for one. Errors from
+/// connecting which can't be classified further - such as failures to resolve
+/// the host name of the service - are classified as
+/// [`NetworkErrorKind::Connect`].
+///
+/// [`NetworkErrorKind::Connect`]: enum.NetworkErrorKind.html#variant.Connect
+#[cfg(feature = "reqwest")]
+fn reqwest_network_kind(error: &ReqwestError) -> NetworkErrorKind {
+    if error.is_timeout() {
+        return NetworkErrorKind::Timeout;
+    }
+
+    let mut kind = NetworkErrorKind::Other;
+    let mut next = error.get_ref()
+        .map(|inner| inner as &(dyn StdError + 'static));
+
+    while let Some(inner) = next {
+        if let Some(io) = inner.downcast_ref::<IoError>() {
+            kind = NetworkErrorKind::from_io(io);
+
+            break;
+        }
+
+        if is_tls_error(inner) {
+            return NetworkErrorKind::Tls;
+        }
+
+        next = inner.source();
+    }
+
+    #[cfg(feature = "reqwest-hyper")]
+    {
+        let connect = error.get_ref()
+            .and_then(|inner| inner.downcast_ref::<ReqwestHyperError>())
+            .map_or(false, ReqwestHyperError::is_connect);
+
+        if connect && kind == NetworkErrorKind::Other {
+            return NetworkErrorKind::Connect;
+        }
+    }
+
+    kind
+}
+
 /// An error type to compose a singular error enum between various dependencies'
 /// errors.
-#[derive(Debug)]
+///
+/// Errors wrapping those of a dependency return them from
+/// `std::error::Error::source`. The error is `Send + Sync + 'static`, and so
+/// can be boxed as a `Box<std::error::Error + Send + Sync>`.
+///
+/// The enum is non-exhaustive: new variants may be added in minor releases,
+/// so matches on it must include a wildcard arm. To check for common kinds of
+/// errors without matching, use helpers such as [`is_network`] and
+/// [`status`].
+///
+/// The key is redacted from the `Display` and `Debug` output of the error and
+/// its source, as errors from the `reqwest` crate otherwise contain the URL of
+/// the request, which includes the key.
+///
+/// # Using with `failure`
+///
+/// As the error is `std::error::Error + Send + Sync + 'static`, it implements
+/// `failure::Fail` through that crate's blanket implementation, and so
+/// converts into a `failure::Error` without any feature being enabled:
+///
+/// ```rust
+/// extern crate failure;
+/// extern crate owo;
+///
+/// fn check(provided: usize) -> Result<(), failure::Error> {
+///     let limit = 3;
+///
+///     if provided > limit {
+///         Err(owo::Error::TooManyFiles { provided, limit })?;
+///     }
+///
+///     Ok(())
+/// }
+/// #
+/// # fn main() {
+/// #     assert!(check(4).is_err());
+/// # }
+/// ```
+///
+/// The blanket implementation does not expose the error's source as its
+/// cause. To inspect it, downcast the `failure::Error` back into an [`Error`].
+///
+/// [`Error`]: enum.Error.html
+/// [`is_network`]: #method.is_network
+/// [`status`]: #method.status
+#[non_exhaustive]
 pub enum Error {
-    /// An error from the `std::io` module.
-    #[cfg(feature = "reqwest")]
-    Io(IoError),
+    /// Indicator that the service rejected a request, containing the error
+    /// code and description from the response body.
+    Api {
+        /// The error code given by the service, usually matching the HTTP
+        /// status of the response.
+        code: i64,
+        /// The service's description of the error.
+        description: String,
+        /// The meaning of the error code, if it is a known one.
+        kind: ApiErrorCode,
+    },
+    /// Indicator that the service responded with `success: false` without
+    /// describing the error, containing the response.
+    ///
+    /// Clients built with `OwoReqwestClientBuilder::allow_unsuccessful` return
+    /// such responses as they are instead.
+    ApiFailure {
+        /// The response body.
+        body: String,
+    },
+    /// Indicator that the request uploading a batch of files failed as a
+    /// whole, containing the error shared by each of the files, such as
+    /// those of `OwoReqwestClient::upload_dir`.
+    ///
+    /// The shared error is also available as the error's source.
+    Batch(Arc<Error>),
+    /// Indicator that the certificate presented by the service did not match
+    /// the certificate pinned via [`OwoHyperClientBuilder::pin_certificate`].
+    ///
+    /// [`OwoHyperClientBuilder::pin_certificate`]: bridge/hyper/struct.OwoClientBuilder.html#method.pin_certificate
+    #[cfg(feature = "hyper")]
+    CertificatePinMismatch,
+    /// Indicator that a setting is invalid, such as a field of a
+    /// configuration file being missing or having a value of the wrong type,
+    /// or an environment variable having an invalid value.
+    Config {
+        /// The name of the offending field or environment variable.
+        field: String,
+        /// A description of what is wrong with the field.
+        reason: String,
+    },
+    /// An error from the `std::io` module when accessing a file at a path
+    /// given to the library, containing the path.
+    ///
+    /// The IO error is also available as the error's source.
+    File {
+        /// The path of the file.
+        path: PathBuf,
+        /// The error accessing the file.
+        error: IoError,
+    },
+    /// Indicator that a file to upload is larger than the service allows.
+    ///
+    /// Refer to [`ServiceLimits::max_file_size`] for the limit.
+    ///
+    /// [`ServiceLimits::max_file_size`]: limits/struct.ServiceLimits.html#structfield.max_file_size
+    FileTooLarge {
+        /// The size of the file, in bytes.
+        size: u64,
+        /// The maximum size of a file, in bytes.
+        limit: u64,
+    },
+    /// Indicator that the service - or a proxy in front of it - responded
+    /// with an HTML page rather than JSON, containing the start of the page
+    /// with the key redacted.
+    ///
+    /// This usually means a gateway error or captcha page was returned, even
+    /// if with a successful status. As with [`ServiceUnavailable`], the page
+    /// is left out of the error's `Display` output.
+    ///
+    /// [`ServiceUnavailable`]: #variant.ServiceUnavailable
+    HtmlResponse(String),
+    /// An error from the `hyper` crate when it is enabled, such as when a
+    /// connection fails during a request which the client runs itself.
+    ///
+    /// The [`Trace`] holds the backtrace of where the error was created, if
+    /// the `backtrace` feature captured one.
+    ///
+    /// [`Trace`]: struct.Trace.html
+    #[cfg(feature = "hyper")]
+    Hyper(HyperError, Trace),
+    /// Indicator that a configured multipart boundary is invalid, containing
+    /// the boundary.
+    ///
+    /// Boundaries must be between 1 and 70 characters long, and consist of
+    /// only alphanumeric characters and the characters `'()+_,-./:=?` and
+    /// space, not ending with a space.
+    InvalidBoundary(String),
+    /// Indicator that a response body could not be deserialized, containing
+    /// the start of the body - with the key redacted - and the error from the
+    /// `serde_json` crate.
+    ///
+    /// The error from the `serde_json` crate is also available as the
+    /// error's source.
+    #[cfg(feature = "serde_json")]
+    InvalidJson {
+        /// The start of the response body.
+        body: String,
+        /// The error deserializing the body.
+        error: JsonError,
+    },
+    /// Indicator that a key is invalid, such as being empty or being rejected
+    /// by the service.
+    InvalidKey,
+    /// An error from the `std::io` module, such as when reading a file or
+    /// when a connection is dropped.
+    ///
+    /// Unlike the variants wrapping errors of optional dependencies, this is
+    /// available regardless of the enabled features.
+    ///
+    /// The [`Trace`] holds the backtrace of where the error was created, if
+    /// the `backtrace` feature captured one.
+    ///
+    /// [`Trace`]: struct.Trace.html
+    Io(IoError, Trace),
+    /// Indicator that no key is stored in the credential store for a service
+    /// and account.
+    #[cfg(feature = "keyring")]
+    KeyNotFound {
+        /// The account name of the missing entry.
+        account: String,
+        /// The service name of the missing entry.
+        service: String,
+    },
+    /// An error from the `keyring` crate, such as when the credential store
+    /// could not be accessed.
+    ///
+    /// This is the message of the error, as the error itself is not
+    /// thread-safe on Linux.
+    #[cfg(feature = "keyring")]
+    Keyring(String),
     /// An error from the `serde_json` crate.
     ///
     /// A potential reason for this is when there is an error deserializing a
     /// JSON response body.
+    ///
+    /// This is available when the `serde-items` feature - which is enabled by
+    /// `reqwest-support` - is enabled, as the crate is otherwise not a
+    /// dependency.
     #[cfg(feature = "serde_json")]
     Json(JsonError),
-    /// An error from the `native-tls` crate.
+    /// An error from the `native-tls` crate, from setting up the TLS
+    /// connector of the hyper bridge.
+    ///
+    /// There is no counterpart for `rustls`, as setting up its connector
+    /// can't fail. Failed TLS handshakes are instead network errors, which
+    /// [`network_kind`] classifies as [`NetworkErrorKind::Tls`] whichever
+    /// backend is in use.
+    ///
+    /// [`network_kind`]: #method.network_kind
+    /// [`NetworkErrorKind::Tls`]: enum.NetworkErrorKind.html#variant.Tls
     #[cfg(feature = "native-tls")]
     NativeTls(NativeTlsError),
+    /// Indicator that the service reported a single-file upload as successful
+    /// but returned no files, such as when it deduplicated the file.
+    NoFilesReturned,
+    /// Indicator that the global client was used before a key was set via
+    /// [`set_key`].
+    ///
+    /// [`set_key`]: fn.set_key.html
+    #[cfg(feature = "reqwest")]
+    NoKeyConfigured,
+    /// Indicator that the service rejected a request for being too large,
+    /// such as when uploading a file larger than the service allows.
+    ///
+    /// Unlike [`FileTooLarge`], this is returned by the service rather than by
+    /// checks made before a request.
+    ///
+    /// [`FileTooLarge`]: #variant.FileTooLarge
+    PayloadTooLarge {
+        /// The total size of the files which were being uploaded, in bytes, if
+        /// known.
+        size: Option<u64>,
+    },
+    /// Indicator that the service rate limited a request, due to too many
+    /// requests being made in a short period of time.
+    ///
+    /// Batch operations such as `OwoReqwestClient::upload_all` retry rate
+    /// limited requests - waiting for the given duration - if the client was
+    /// built with retries.
+    RateLimited {
+        /// How long to wait before retrying the request, if the service said.
+        retry_after: Option<Duration>,
+    },
     /// An error from the `reqwest` crate when it is enabled.
+    ///
+    /// The error may contain the URL of the request - including the key - so
+    /// take care when formatting it directly, rather than via this error's
+    /// `Display` or `Debug` output. The error's source is that of the `reqwest`
+    /// error, which does not contain the URL.
+    ///
+    /// The [`Trace`] holds the backtrace of where the error was created, if
+    /// the `backtrace` feature captured one.
+    ///
+    /// [`Trace`]: struct.Trace.html
     #[cfg(feature = "reqwest")]
-    Reqwest(ReqwestError),
+    Reqwest(ReqwestError, Trace),
+    /// Indicator that a response body is larger than the client allows, as
+    /// set via `OwoReqwestClientBuilder::max_response_bytes` or 1 MiB by
+    /// default.
+    ///
+    /// The rest of the body is not read once the limit is passed.
+    ResponseTooLarge {
+        /// The maximum size of a response body, in bytes.
+        limit: usize,
+    },
+    /// Indicator that the service is unavailable, such as during an outage,
+    /// going by an unsuccessful response containing an HTML error page.
+    ///
+    /// Such pages usually come from a proxy in front of the service, so the
+    /// body is kept out of the error's `Display` output.
+    ServiceUnavailable {
+        /// The HTTP status code of the response.
+        status: u16,
+        /// The start of the HTML page, with the key redacted.
+        body: String,
+    },
+    /// Indicator that the service responded with an unsuccessful HTTP status
+    /// without describing the error, such as when a proxy in front of the
+    /// service fails.
+    Status {
+        /// The HTTP status code of the response.
+        status: u16,
+        /// The start of the response body, with the key redacted.
+        body: String,
+    },
+    /// Indicator that a request timed out, such as after the timeout set via
+    /// `OwoReqwestClientBuilder::timeout`, or that an operation did, such as
+    /// one bounded via `OwoHyperClient::with_operation_timeout`.
+    ///
+    /// Timeouts are transient, so the request may succeed if made again.
+    Timeout {
+        /// How long was spent on the request before it timed out.
+        elapsed: Duration,
+    },
+    /// An error from the `toml` crate when parsing a configuration file.
+    #[cfg(feature = "toml")]
+    Toml(TomlError),
     /// Indicator that a request would have attempted to upload too many files.
     ///
-    /// Refer to [`constants::MAX_FILES`] for the maximum number of allowed
+    /// Refer to [`ServiceLimits::max_files`] for the maximum number of allowed
     /// files per request.
     ///
-    /// [`constants::MAX_FILES`]: constants/const.MAX_FILES.html
-    TooManyFiles,
+    /// [`ServiceLimits::max_files`]: limits/struct.ServiceLimits.html#structfield.max_files
+    TooManyFiles {
+        /// The number of files that were provided.
+        provided: usize,
+        /// The maximum number of files allowed in a single request.
+        limit: usize,
+    },
+    /// Indicator that the service responded with something other than what
+    /// was expected, containing the response.
+    UnexpectedResponse(String),
+    /// Indicator that a domain to link uploaded files to is not one of the
+    /// service's known domains, containing the domain.
+    ///
+    /// Refer to [`domains::KNOWN`] for the known domains.
+    ///
+    /// [`domains::KNOWN`]: domains/constant.KNOWN.html
+    UnknownDomain(String),
+    /// Indicator that a URL to shorten is longer than the service allows.
+    ///
+    /// Refer to [`ServiceLimits::max_url_length`] for the limit.
+    ///
+    /// [`ServiceLimits::max_url_length`]: limits/struct.ServiceLimits.html#structfield.max_url_length
+    UrlTooLong {
+        /// The length of the URL, in bytes.
+        length: usize,
+        /// The maximum length of a URL, in bytes.
+        limit: usize,
+    },
     /// An error when building a request's URI from the `hyper` crate when it is
     /// enabled.
     #[cfg(feature = "hyper")]
     Uri(UriError),
 }
 
-impl Display for Error {
+impl Error {
+    /// Retrieves the kind of the error, which - unlike the error itself - can
+    /// be compared.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use owo::{Error, ErrorKind};
+    ///
+    /// let error = Error::TooManyFiles { provided: 4, limit: 3 };
+    ///
+    /// assert_eq!(error.kind(), ErrorKind::TooManyFiles);
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match *self {
+            Error::Api { .. } => ErrorKind::Api,
+            Error::ApiFailure { .. } => ErrorKind::ApiFailure,
+            Error::Batch(_) => ErrorKind::Batch,
+            #[cfg(feature = "hyper")]
+            Error::CertificatePinMismatch => ErrorKind::CertificatePinMismatch,
+            Error::Config { .. } => ErrorKind::Config,
+            Error::File { .. } => ErrorKind::File,
+            Error::FileTooLarge { .. } => ErrorKind::FileTooLarge,
+            Error::HtmlResponse(_) => ErrorKind::HtmlResponse,
+            #[cfg(feature = "hyper")]
+            Error::Hyper(..) => ErrorKind::Hyper,
+            Error::InvalidBoundary(_) => ErrorKind::InvalidBoundary,
+            #[cfg(feature = "serde_json")]
+            Error::InvalidJson { .. } => ErrorKind::InvalidJson,
+            Error::InvalidKey => ErrorKind::InvalidKey,
+            Error::Io(..) => ErrorKind::Io,
+            #[cfg(feature = "serde_json")]
+            Error::Json(_) => ErrorKind::Json,
+            #[cfg(feature = "keyring")]
+            Error::KeyNotFound { .. } => ErrorKind::KeyNotFound,
+            #[cfg(feature = "keyring")]
+            Error::Keyring(_) => ErrorKind::Keyring,
+            #[cfg(feature = "native-tls")]
+            Error::NativeTls(_) => ErrorKind::NativeTls,
+            Error::NoFilesReturned => ErrorKind::NoFilesReturned,
+            #[cfg(feature = "reqwest")]
+            Error::NoKeyConfigured => ErrorKind::NoKeyConfigured,
+            Error::PayloadTooLarge { .. } => ErrorKind::PayloadTooLarge,
+            Error::RateLimited { .. } => ErrorKind::RateLimited,
+            #[cfg(feature = "reqwest")]
+            Error::Reqwest(..) => ErrorKind::Reqwest,
+            Error::ResponseTooLarge { .. } => ErrorKind::ResponseTooLarge,
+            Error::ServiceUnavailable { .. } => ErrorKind::ServiceUnavailable,
+            Error::Status { .. } => ErrorKind::Status,
+            Error::Timeout { .. } => ErrorKind::Timeout,
+            #[cfg(feature = "toml")]
+            Error::Toml(_) => ErrorKind::Toml,
+            Error::TooManyFiles { .. } => ErrorKind::TooManyFiles,
+            Error::UnexpectedResponse(_) => ErrorKind::UnexpectedResponse,
+            Error::UnknownDomain(_) => ErrorKind::UnknownDomain,
+            Error::UrlTooLong { .. } => ErrorKind::UrlTooLong,
+            #[cfg(feature = "hyper")]
+            Error::Uri(_) => ErrorKind::Uri,
+        }
+    }
+
+    /// Retrieves the backtrace of where the error was created, if one was
+    /// captured.
+    ///
+    /// Backtraces are only captured for the network and IO errors - the
+    /// [`Hyper`], [`Io`] and [`Reqwest`] variants - when created from the
+    /// error they wrap. Capturing is skipped unless backtraces are enabled
+    /// via the `RUST_LIB_BACKTRACE` or `RUST_BACKTRACE` environment variables,
+    /// as with those of the standard library.
+    ///
+    /// A captured backtrace is included in the error's `Debug` output, and in
+    /// its alternate `{:#}` `Display` output.
+    ///
+    /// This is only available with the `backtrace` feature enabled.
+    ///
+    /// [`Hyper`]: #variant.Hyper
+    /// [`Io`]: #variant.Io
+    /// [`Reqwest`]: #variant.Reqwest
+    #[cfg(feature = "backtrace")]
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        match *self {
+            #[cfg(feature = "hyper")]
+            Error::Hyper(_, ref trace) => trace.backtrace.as_deref(),
+            Error::Io(_, ref trace) => trace.backtrace.as_deref(),
+            #[cfg(feature = "reqwest")]
+            Error::Reqwest(_, ref trace) => trace.backtrace.as_deref(),
+            Error::Batch(ref inner) => inner.backtrace(),
+            _ => None,
+        }
+    }
+
+    /// Whether the key was invalid, such as being empty or being rejected by
+    /// the service.
+    pub fn is_invalid_key(&self) -> bool {
+        match *self {
+            Error::Batch(ref inner) => inner.is_invalid_key(),
+            Error::InvalidKey => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the error occurred while communicating with the service, such
+    /// as when it could not be reached, a connection was dropped, or a request
+    /// timed out.
+    ///
+    /// Refer to [`network_kind`] to tell these apart.
+    ///
+    /// [`network_kind`]: #method.network_kind
+    pub fn is_network(&self) -> bool {
+        self.network_kind().is_some()
+    }
+
+    /// Retrieves the kind of network error, if the error occurred while
+    /// communicating with the service.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use owo::{Error, NetworkErrorKind};
+    /// use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+    ///
+    /// let io = IoError::new(IoErrorKind::ConnectionRefused, "refused");
+    /// let error = Error::from(io);
+    ///
+    /// assert_eq!(error.network_kind(), Some(NetworkErrorKind::Connect));
+    /// assert_eq!(Error::InvalidKey.network_kind(), None);
+    /// ```
+    pub fn network_kind(&self) -> Option<NetworkErrorKind> {
+        match *self {
+            Error::Batch(ref inner) => inner.network_kind(),
+            #[cfg(feature = "hyper")]
+            Error::Hyper(ref inner, ..) => NetworkErrorKind::from_hyper(inner),
+            Error::Io(ref inner, ..) => Some(NetworkErrorKind::from_io(inner)),
+            #[cfg(feature = "reqwest")]
+            Error::Reqwest(ref inner, ..) if inner.status().is_none() => {
+                Some(reqwest_network_kind(inner))
+            },
+            Error::Timeout { .. } => Some(NetworkErrorKind::Timeout),
+            _ => None,
+        }
+    }
+
+    /// Whether the service rate limited the request.
+    pub fn is_rate_limited(&self) -> bool {
+        match *self {
+            Error::Batch(ref inner) => inner.is_rate_limited(),
+            Error::RateLimited { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether a request timed out.
+    pub fn is_timeout(&self) -> bool {
+        match *self {
+            Error::Batch(ref inner) => inner.is_timeout(),
+            Error::Timeout { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Whether too many files were given to upload in a single request.
+    pub fn is_too_many_files(&self) -> bool {
+        match *self {
+            Error::Batch(ref inner) => inner.is_too_many_files(),
+            Error::TooManyFiles { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Retrieves the HTTP status code of the response which caused the error,
+    /// if the error was caused by one.
+    pub fn status(&self) -> Option<u16> {
+        match *self {
+            Error::Batch(ref inner) => inner.status(),
+            Error::PayloadTooLarge { .. } => Some(413),
+            Error::RateLimited { .. } => Some(429),
+            #[cfg(feature = "reqwest")]
+            Error::Reqwest(ref inner, ..) => {
+                inner.status().map(|status| status.as_u16())
+            },
+            Error::ServiceUnavailable { status, .. } => Some(status),
+            Error::Status { status, .. } => Some(status),
+            _ => None,
+        }
+    }
+
+    /// Produces a message describing the error for end users, such as in a
+    /// dialog of a graphical application.
+    ///
+    /// Unlike the error's `Display` output, the message leaves out technical
+    /// details - such as response bodies and the errors of other crates - and
+    /// suggests what the user can do about the error where possible. Those
+    /// details remain available via `Display` and [`source`], such as for
+    /// logging.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use owo::Error;
+    ///
+    /// let error = Error::FileTooLarge {
+    ///     size: 200 * 1024 * 1024,
+    ///     limit: 100 * 1024 * 1024,
+    /// };
+    ///
+    /// assert_eq!(
+    ///     error.user_message(),
+    ///     "This file is larger than the 100 MB limit.",
+    /// );
+    /// ```
+    ///
+    /// [`source`]: #method.source
+    pub fn user_message(&self) -> String {
+        let message = match *self {
+            Error::Api { kind, .. } => match kind {
+                ApiErrorCode::BannedKey => {
+                    "Your API key has been banned from the service."
+                },
+                ApiErrorCode::DisallowedType => {
+                    "The service does not allow this type of file."
+                },
+                ApiErrorCode::FileTooLarge => {
+                    "This file is larger than the service allows."
+                },
+                ApiErrorCode::InternalError => {
+                    "The service ran into a problem. Try again later."
+                },
+                ApiErrorCode::InvalidKey => {
+                    "Your API key was rejected. Check that it's correct."
+                },
+                ApiErrorCode::RateLimited => {
+                    "Too many requests were made. Wait a moment and try again."
+                },
+                ApiErrorCode::BadRequest | ApiErrorCode::Unknown(_) => {
+                    "The service couldn't handle the request."
+                },
+            },
+            Error::ApiFailure { .. } | Error::NoFilesReturned => {
+                "The service didn't confirm the upload. Try again later."
+            },
+            Error::Batch(ref inner) => return inner.user_message(),
+            #[cfg(feature = "hyper")]
+            Error::CertificatePinMismatch => {
+                "The service's identity couldn't be verified, so the \
+                 connection was stopped."
+            },
+            Error::Config { ref field, .. } => {
+                return format!(
+                    "The setting `{}` in your configuration is invalid.",
+                    field,
+                );
+            },
+            Error::File { ref path, ref error } => {
+                let path = path.display();
+
+                return match error.kind() {
+                    IoErrorKind::NotFound => {
+                        format!("The file `{}` couldn't be found.", path)
+                    },
+                    IoErrorKind::PermissionDenied => format!(
+                        "You don't have permission to access `{}`.",
+                        path,
+                    ),
+                    _ => format!("The file `{}` couldn't be read.", path),
+                };
+            },
+            Error::FileTooLarge { limit, .. } => {
+                return format!(
+                    "This file is larger than the {} limit.",
+                    format_size(limit),
+                );
+            },
+            Error::HtmlResponse(_) => {
+                "The service sent a web page instead of a response. It may be \
+                 down, or asking to check that you aren't a robot."
+            },
+            Error::InvalidBoundary(_) => {
+                "The upload couldn't be prepared due to a configuration \
+                 problem."
+            },
+            Error::InvalidKey => {
+                "Your API key was rejected. Check that it's correct."
+            },
+            Error::Io(ref inner, ..)
+                if inner.kind() == IoErrorKind::NotFound => {
+                "The file couldn't be found."
+            },
+            Error::Io(ref inner, ..)
+                if inner.kind() == IoErrorKind::PermissionDenied => {
+                "You don't have permission to read the file."
+            },
+            #[cfg(feature = "serde_json")]
+            Error::InvalidJson { .. } | Error::Json(_) => {
+                "The service sent a response which couldn't be understood."
+            },
+            #[cfg(feature = "keyring")]
+            Error::KeyNotFound { .. } => "No saved API key was found.",
+            #[cfg(feature = "keyring")]
+            Error::Keyring(_) => "Your saved API key couldn't be accessed.",
+            #[cfg(feature = "native-tls")]
+            Error::NativeTls(_) => {
+                "A secure connection to the service couldn't be made."
+            },
+            #[cfg(feature = "reqwest")]
+            Error::NoKeyConfigured => "No API key has been set.",
+            Error::PayloadTooLarge { .. } => {
+                "The files are larger than the service allows."
+            },
+            Error::RateLimited { retry_after: Some(delay) } => {
+                return format!(
+                    "Too many requests were made. Try again in {} seconds.",
+                    cmp::max(delay.as_secs(), 1),
+                );
+            },
+            Error::RateLimited { retry_after: None } => {
+                "Too many requests were made. Wait a moment and try again."
+            },
+            Error::ResponseTooLarge { .. } => {
+                "The service sent a response which was too large to read."
+            },
+            Error::ServiceUnavailable { .. } => {
+                "The service appears to be down. Try again later."
+            },
+            Error::Status { status, .. } => match status {
+                401 | 403 => {
+                    "Your API key was rejected. Check that it's correct."
+                },
+                500..=599 => "The service appears to be down. Try again later.",
+                _ => "The service couldn't handle the request.",
+            },
+            #[cfg(feature = "toml")]
+            Error::Toml(_) => "Your configuration file isn't valid.",
+            Error::TooManyFiles { limit, .. } => {
+                return format!(
+                    "Too many files. Only {} can be uploaded at once.",
+                    limit,
+                );
+            },
+            Error::UnexpectedResponse(_) => {
+                "The service sent a response which couldn't be understood."
+            },
+            Error::UnknownDomain(ref domain) => {
+                return format!(
+                    "`{}` isn't one of the service's domains. Check your \
+                     settings.",
+                    domain,
+                );
+            },
+            Error::UrlTooLong { .. } => "This link is too long to shorten.",
+            #[cfg(feature = "hyper")]
+            Error::Uri(_) => {
+                "The address of the service is invalid. Check your settings."
+            },
+            _ => match self.network_kind() {
+                Some(NetworkErrorKind::Connect)
+                    | Some(NetworkErrorKind::Dns) => {
+                    "Couldn't connect to the service. Check your internet \
+                     connection."
+                },
+                Some(NetworkErrorKind::Timeout) => {
+                    "The service took too long to respond. Try again later."
+                },
+                Some(NetworkErrorKind::Tls) => {
+                    "A secure connection to the service couldn't be made."
+                },
+                Some(NetworkErrorKind::Reset) => {
+                    "The connection to the service was interrupted. Try \
+                     again."
+                },
+                Some(NetworkErrorKind::Other) | None => {
+                    "Something went wrong while talking to the service. Try \
+                     again."
+                },
+            },
+        };
+
+        message.to_owned()
+    }
+
+    /// Finishes the `Debug` output of a variant, adding the backtrace of the
+    /// error if one was captured.
+    #[cfg(feature = "backtrace")]
+    fn finish_traced(&self, tuple: &mut DebugTuple) -> FmtResult {
+        if let Some(backtrace) = self.backtrace() {
+            tuple.field(backtrace);
+        }
+
+        tuple.finish()
+    }
+
+    #[cfg(not(feature = "backtrace"))]
+    fn finish_traced(&self, tuple: &mut DebugTuple) -> FmtResult {
+        tuple.finish()
+    }
+
+    /// Writes the backtrace of the error after its `Display` output, if one
+    /// was captured and the alternate `{:#}` form is in use.
+    #[cfg(feature = "backtrace")]
+    fn fmt_backtrace(&self, f: &mut Formatter) -> FmtResult {
+        match self.backtrace() {
+            Some(backtrace) if f.alternate() => {
+                write!(f, "\n\n{:?}", backtrace)
+            },
+            _ => Ok(()),
+        }
+    }
+
+    #[cfg(not(feature = "backtrace"))]
+    fn fmt_backtrace(&self, _: &mut Formatter) -> FmtResult {
+        Ok(())
+    }
+}
+
+/// The backtrace of where a network or IO error was created, carried by the
+/// [`Hyper`], [`Io`] and [`Reqwest`] variants of [`Error`].
+///
+/// This is empty unless the `backtrace` feature is enabled, so that the
+/// variants have the same shape regardless of the enabled features. The
+/// backtrace is retrieved via [`Error::backtrace`].
+///
+/// [`Error`]: enum.Error.html
+/// [`Error::backtrace`]: enum.Error.html#method.backtrace
+/// [`Hyper`]: enum.Error.html#variant.Hyper
+/// [`Io`]: enum.Error.html#variant.Io
+/// [`Reqwest`]: enum.Error.html#variant.Reqwest
+#[derive(Debug, Default)]
+pub struct Trace {
+    #[cfg(feature = "backtrace")]
+    backtrace: Option<Box<Backtrace>>,
+}
+
+impl Trace {
+    /// Captures a backtrace of the current thread, unless the `backtrace`
+    /// feature is disabled or backtraces are disabled via the environment.
+    ///
+    /// As with the standard library, `RUST_LIB_BACKTRACE` takes precedence
+    /// over `RUST_BACKTRACE`, and backtraces are disabled if neither is set or
+    /// if the one in effect is `0`.
+    #[cfg(feature = "backtrace")]
+    pub fn capture() -> Self {
+        let enabled = env::var_os("RUST_LIB_BACKTRACE")
+            .or_else(|| env::var_os("RUST_BACKTRACE"))
+            .map_or(false, |value| value != "0");
+
+        Self {
+            backtrace: if enabled {
+                Some(Box::new(Backtrace::new()))
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Captures a backtrace of the current thread, unless the `backtrace`
+    /// feature is disabled or backtraces are disabled via the environment.
+    #[cfg(not(feature = "backtrace"))]
+    pub fn capture() -> Self {
+        Self {}
+    }
+}
+
+/// Formats a number of bytes for end users, such as `"100 MB"`.
+fn format_size(bytes: u64) -> String {
+    const UNITS: [(u64, &str); 3] = [
+        (1024 * 1024 * 1024, "GB"),
+        (1024 * 1024, "MB"),
+        (1024, "KB"),
+    ];
+
+    for &(scale, unit) in &UNITS {
+        if bytes < scale {
+            continue;
+        }
+
+        return if bytes % scale == 0 {
+            format!("{} {}", bytes / scale, unit)
+        } else {
+            format!("{:.1} {}", bytes as f64 / scale as f64, unit)
+        };
+    }
+
+    format!("{} bytes", bytes)
+}
+
+/// Redacts the value of any `key` query parameter in text, such as in the URL
+/// contained in an error from the `reqwest` crate.
+#[cfg(feature = "reqwest")]
+pub(crate) fn redact_key(text: &str) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(idx) = find_key_param(rest) {
+        let (start, end) = rest.split_at(idx);
+        redacted.push_str(start);
+        redacted.push_str("[redacted]");
+
+        let len = end.find(|c: char| {
+            c == '&' || c == '#' || c == '"' || c == ')' || c.is_whitespace()
+        }).unwrap_or(end.len());
+        rest = &end[len..];
+    }
+
+    redacted.push_str(rest);
+
+    redacted
+}
+
+/// Finds the start of the value of the first `key` query parameter in text.
+#[cfg(feature = "reqwest")]
+fn find_key_param(text: &str) -> Option<usize> {
+    ["?key=", "&key="]
+        .iter()
+        .filter_map(|param| text.find(param).map(|idx| idx + param.len()))
+        .min()
+}
+
+/// Asserts at compile time that [`Error`] can be sent between threads, so
+/// that adding a variant wrapping a non-thread-safe type fails the build.
+///
+/// [`Error`]: enum.Error.html
+#[allow(dead_code)]
+fn assert_error_send_sync() {
+    fn assert<T: Send + Sync + 'static>() {}
+
+    assert::<Error>();
+}
+
+impl fmt::Debug for Error {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         match *self {
+            Error::Api { code, ref description, kind } => f
+                .debug_struct("Api")
+                .field("code", &code)
+                .field("description", description)
+                .field("kind", &kind)
+                .finish(),
+            Error::ApiFailure { ref body } => f
+                .debug_struct("ApiFailure")
+                .field("body", body)
+                .finish(),
+            Error::Batch(ref inner) => {
+                f.debug_tuple("Batch").field(inner).finish()
+            },
+            #[cfg(feature = "hyper")]
+            Error::CertificatePinMismatch => {
+                f.write_str("CertificatePinMismatch")
+            },
+            Error::Config { ref field, ref reason } => f
+                .debug_struct("Config")
+                .field("field", field)
+                .field("reason", reason)
+                .finish(),
+            Error::File { ref path, ref error } => f
+                .debug_struct("File")
+                .field("path", path)
+                .field("error", error)
+                .finish(),
+            Error::FileTooLarge { size, limit } => f
+                .debug_struct("FileTooLarge")
+                .field("size", &size)
+                .field("limit", &limit)
+                .finish(),
+            Error::HtmlResponse(ref body) => {
+                f.debug_tuple("HtmlResponse").field(body).finish()
+            },
+            #[cfg(feature = "hyper")]
+            Error::Hyper(ref inner, ..) => {
+                self.finish_traced(f.debug_tuple("Hyper").field(inner))
+            },
+            Error::InvalidBoundary(ref boundary) => {
+                f.debug_tuple("InvalidBoundary").field(boundary).finish()
+            },
+            #[cfg(feature = "serde_json")]
+            Error::InvalidJson { ref body, ref error } => f
+                .debug_struct("InvalidJson")
+                .field("body", body)
+                .field("error", error)
+                .finish(),
+            Error::InvalidKey => f.write_str("InvalidKey"),
+            Error::Io(ref inner, ..) => {
+                self.finish_traced(f.debug_tuple("Io").field(inner))
+            },
+            #[cfg(feature = "serde_json")]
+            Error::Json(ref inner) => {
+                f.debug_tuple("Json").field(inner).finish()
+            },
+            #[cfg(feature = "keyring")]
+            Error::KeyNotFound { ref account, ref service } => f
+                .debug_struct("KeyNotFound")
+                .field("account", account)
+                .field("service", service)
+                .finish(),
+            #[cfg(feature = "keyring")]
+            Error::Keyring(ref inner) => {
+                f.debug_tuple("Keyring").field(inner).finish()
+            },
+            #[cfg(feature = "native-tls")]
+            Error::NativeTls(ref inner) => {
+                f.debug_tuple("NativeTls").field(inner).finish()
+            },
+            Error::NoFilesReturned => f.write_str("NoFilesReturned"),
+            #[cfg(feature = "reqwest")]
+            Error::NoKeyConfigured => f.write_str("NoKeyConfigured"),
+            Error::PayloadTooLarge { size } => f
+                .debug_struct("PayloadTooLarge")
+                .field("size", &size)
+                .finish(),
+            Error::RateLimited { retry_after } => f
+                .debug_struct("RateLimited")
+                .field("retry_after", &retry_after)
+                .finish(),
             #[cfg(feature = "reqwest")]
-            Error::Io(ref inner) => inner.fmt(f),
+            Error::Reqwest(ref inner, ..) => {
+                let inner = redact_key(&format!("{:?}", inner));
+
+                self.finish_traced(
+                    f.debug_tuple("Reqwest").field(&format_args!("{}", inner)),
+                )
+            },
+            Error::ResponseTooLarge { limit } => f
+                .debug_struct("ResponseTooLarge")
+                .field("limit", &limit)
+                .finish(),
+            Error::ServiceUnavailable { status, ref body } => f
+                .debug_struct("ServiceUnavailable")
+                .field("status", &status)
+                .field("body", body)
+                .finish(),
+            Error::Status { status, ref body } => f
+                .debug_struct("Status")
+                .field("status", &status)
+                .field("body", body)
+                .finish(),
+            Error::Timeout { elapsed } => f
+                .debug_struct("Timeout")
+                .field("elapsed", &elapsed)
+                .finish(),
+            #[cfg(feature = "toml")]
+            Error::Toml(ref inner) => {
+                f.debug_tuple("Toml").field(inner).finish()
+            },
+            Error::TooManyFiles { provided, limit } => f
+                .debug_struct("TooManyFiles")
+                .field("provided", &provided)
+                .field("limit", &limit)
+                .finish(),
+            Error::UnexpectedResponse(ref response) => {
+                f.debug_tuple("UnexpectedResponse").field(response).finish()
+            },
+            Error::UnknownDomain(ref domain) => {
+                f.debug_tuple("UnknownDomain").field(domain).finish()
+            },
+            Error::UrlTooLong { length, limit } => f
+                .debug_struct("UrlTooLong")
+                .field("length", &length)
+                .field("limit", &limit)
+                .finish(),
+            #[cfg(feature = "hyper")]
+            Error::Uri(ref inner) => f.debug_tuple("Uri").field(inner).finish(),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            Error::Api { code, ref description, kind } => {
+                match kind.meaning() {
+                    Some(meaning) => write!(
+                        f,
+                        "Error from the service ({}, {}): {}",
+                        code,
+                        meaning,
+                        description,
+                    ),
+                    None => write!(
+                        f,
+                        "Error from the service ({}): {}",
+                        code,
+                        description,
+                    ),
+                }
+            },
+            Error::ApiFailure { ref body } => {
+                write!(f, "Unsuccessful response from the service: {}", body)
+            },
+            Error::Batch(ref inner) => {
+                write!(f, "Uploading a batch of files failed: {}", inner)
+            },
+            #[cfg(feature = "hyper")]
+            Error::CertificatePinMismatch => {
+                f.write_str("Certificate did not match the pinned certificate")
+            },
+            Error::Config { ref field, ref reason } => {
+                write!(f, "Invalid config field `{}`: {}", field, reason)
+            },
+            Error::File { ref path, ref error } => {
+                write!(f, "Error accessing `{}`: {}", path.display(), error)
+            },
+            Error::FileTooLarge { size, limit } => write!(
+                f,
+                "File too large to upload: {} bytes but the limit is {}",
+                size,
+                limit,
+            ),
+            Error::HtmlResponse(_) => f.write_str(
+                "Received an HTML page instead of JSON, such as from a proxy \
+                 in front of the service",
+            ),
+            #[cfg(feature = "hyper")]
+            Error::Hyper(ref inner, ..) => {
+                inner.fmt(f)?;
+
+                self.fmt_backtrace(f)
+            },
+            Error::InvalidBoundary(ref boundary) => {
+                write!(f, "Invalid multipart boundary: {:?}", boundary)
+            },
+            #[cfg(feature = "serde_json")]
+            Error::InvalidJson { ref body, ref error } => {
+                write!(f, "Invalid response body ({}): {}", error, body)
+            },
+            Error::InvalidKey => f.write_str(
+                "Invalid key: check that it is correct and not revoked",
+            ),
+            Error::Io(ref inner, ..) => {
+                inner.fmt(f)?;
+
+                self.fmt_backtrace(f)
+            },
             #[cfg(feature = "serde_json")]
             Error::Json(ref inner) => inner.fmt(f),
+            #[cfg(feature = "keyring")]
+            Error::KeyNotFound { ref account, ref service } => write!(
+                f,
+                "No key stored for service `{}` and account `{}`",
+                service,
+                account,
+            ),
+            #[cfg(feature = "keyring")]
+            Error::Keyring(ref inner) => f.write_str(inner),
             #[cfg(feature = "native-tls")]
             Error::NativeTls(ref inner) => inner.fmt(f),
+            Error::NoFilesReturned => {
+                f.write_str("The service returned no files for the upload")
+            },
+            #[cfg(feature = "reqwest")]
+            Error::NoKeyConfigured => f.write_str("No key configured"),
+            Error::PayloadTooLarge { size: Some(size) } => write!(
+                f,
+                "Request too large for the service: {} bytes of files",
+                size,
+            ),
+            Error::PayloadTooLarge { size: None } => {
+                f.write_str("Request too large for the service")
+            },
+            Error::RateLimited { retry_after: Some(delay) } => write!(
+                f,
+                "Rate limited by the service: retry after {} seconds",
+                delay.as_secs(),
+            ),
+            Error::RateLimited { retry_after: None } => {
+                f.write_str("Rate limited by the service")
+            },
             #[cfg(feature = "reqwest")]
-            Error::Reqwest(ref inner) => inner.fmt(f),
-            Error::TooManyFiles => f.write_str("Too many files to upload"),
+            Error::Reqwest(ref inner, ..) => {
+                f.write_str(&redact_key(&inner.to_string()))?;
+
+                self.fmt_backtrace(f)
+            },
+            Error::ResponseTooLarge { limit } => write!(
+                f,
+                "Response body too large: the limit is {} bytes",
+                limit,
+            ),
+            Error::ServiceUnavailable { status, .. } => write!(
+                f,
+                "The service is unavailable (status {}); try again later",
+                status,
+            ),
+            Error::Status { status, ref body } => {
+                write!(f, "Unsuccessful response status {}: {}", status, body)
+            },
+            Error::Timeout { elapsed } => write!(
+                f,
+                "Request timed out after {}.{:03} seconds",
+                elapsed.as_secs(),
+                elapsed.subsec_millis(),
+            ),
+            #[cfg(feature = "toml")]
+            Error::Toml(ref inner) => inner.fmt(f),
+            Error::TooManyFiles { provided, limit } => write!(
+                f,
+                "Too many files to upload: provided {} but the limit is {}",
+                provided,
+                limit,
+            ),
+            Error::UnexpectedResponse(ref response) => {
+                write!(f, "Unexpected response from the service: {}", response)
+            },
+            Error::UnknownDomain(ref domain) => {
+                write!(f, "Unknown domain of the service: {}", domain)
+            },
+            Error::UrlTooLong { length, limit } => write!(
+                f,
+                "URL too long to shorten: {} bytes but the limit is {}",
+                length,
+                limit,
+            ),
             #[cfg(feature = "hyper")]
             Error::Uri(ref inner) => inner.fmt(f),
         }
@@ -70,23 +1581,41 @@ impl Display for Error {
 }
 
 impl StdError for Error {
-    fn description(&self) -> &str {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match *self {
-            #[cfg(feature = "reqwest")]
-            Error::Io(ref inner) => inner.description(),
+            Error::Batch(ref inner) => Some(&**inner),
+            Error::File { ref error, .. } => Some(error),
+            #[cfg(feature = "hyper")]
+            Error::Hyper(ref inner, ..) => Some(inner),
+            #[cfg(feature = "serde_json")]
+            Error::InvalidJson { ref error, .. } => Some(error),
+            Error::Io(ref inner, ..) => Some(inner),
             #[cfg(feature = "serde_json")]
-            Error::Json(ref inner) => inner.description(),
+            Error::Json(ref inner) => Some(inner),
             #[cfg(feature = "native-tls")]
-            Error::NativeTls(ref inner) => inner.description(),
+            Error::NativeTls(ref inner) => Some(inner),
+            // The reqwest error itself contains the URL of the request, and so
+            // the key, so skip to its own source.
             #[cfg(feature = "reqwest")]
-            Error::Reqwest(ref inner) => inner.description(),
-            Error::TooManyFiles => "Too many files to upload",
+            Error::Reqwest(ref inner, ..) => {
+                inner.get_ref().map(|inner| inner as &(dyn StdError + 'static))
+            },
+            #[cfg(feature = "toml")]
+            Error::Toml(ref inner) => Some(inner),
             #[cfg(feature = "hyper")]
-            Error::Uri(ref inner) => inner.description(),
+            Error::Uri(ref inner) => Some(inner),
+            _ => None,
         }
     }
 }
 
+#[cfg(feature = "hyper")]
+impl From<HyperError> for Error {
+    fn from(err: HyperError) -> Error {
+        Error::Hyper(err, Trace::capture())
+    }
+}
+
 #[cfg(feature = "native-tls")]
 impl From<NativeTlsError> for Error {
     fn from(err: NativeTlsError) -> Error {
@@ -94,10 +1623,9 @@ impl From<NativeTlsError> for Error {
     }
 }
 
-#[cfg(feature = "reqwest")]
 impl From<IoError> for Error {
     fn from(err: IoError) -> Error {
-        Error::Io(err)
+        Error::Io(err, Trace::capture())
     }
 }
 
@@ -111,7 +1639,14 @@ impl From<JsonError> for Error {
 #[cfg(feature = "reqwest")]
 impl From<ReqwestError> for Error {
     fn from(err: ReqwestError) -> Error {
-        Error::Reqwest(err)
+        Error::Reqwest(err, Trace::capture())
+    }
+}
+
+#[cfg(feature = "toml")]
+impl From<TomlError> for Error {
+    fn from(err: TomlError) -> Error {
+        Error::Toml(err)
     }
 }
 
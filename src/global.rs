@@ -0,0 +1,113 @@
+//! A global client, for quick scripts which would rather not pass a client
+//! around.
+//!
+//! Set the key once via [`set_key`], and then make requests from anywhere via
+//! the free functions of this module, which are also re-exported at the root
+//! of the crate.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # fn try_main() -> owo::Result<()> {
+//! owo::set_key("my key")?;
+//!
+//! let short_url = owo::shorten_url("https://google.com")?;
+//! #     Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! #     try_main().unwrap();
+//! # }
+//! ```
+//!
+//! [`set_key`]: fn.set_key.html
+
+use std::sync::{PoisonError, RwLock};
+use ::bridge::reqwest::OwoClient;
+use ::model::FileUploadResponse;
+use ::{Error, Result};
+
+lazy_static! {
+    static ref CLIENT: RwLock<Option<OwoClient>> = RwLock::new(None);
+}
+
+/// Sets the key used by the global client, creating the client if this is
+/// the first call.
+///
+/// Calling this again swaps the key. The new key shares the global client's
+/// underlying reqwest Client, so its connections are re-used rather than a
+/// new pool being created.
+///
+/// # Errors
+///
+/// Returns the same errors as [`OwoReqwestClient::new`], in which case the
+/// previous key - if any - is kept.
+///
+/// [`OwoReqwestClient::new`]: ../bridge/reqwest/struct.OwoClient.html#method.new
+pub fn set_key<S: Into<String>>(key: S) -> Result<()> {
+    let mut client = CLIENT.write().unwrap_or_else(PoisonError::into_inner);
+
+    let replacement = match *client {
+        Some(ref client) => client.clone_with_key(key)?,
+        None => OwoClient::new(key)?,
+    };
+    *client = Some(replacement);
+
+    Ok(())
+}
+
+/// Uploads a single file via the global client.
+///
+/// Refer to [`OwoReqwestClient::upload_file`] for more information.
+///
+/// # Errors
+///
+/// Returns [`Error::NoKeyConfigured`] if [`set_key`] has not been called.
+///
+/// [`Error::NoKeyConfigured`]: ../enum.Error.html#variant.NoKeyConfigured
+/// [`OwoReqwestClient::upload_file`]: ../bridge/reqwest/struct.OwoClient.html#method.upload_file
+/// [`set_key`]: fn.set_key.html
+pub fn upload_file(file: Vec<u8>) -> Result<FileUploadResponse> {
+    client()?.upload_file(file)
+}
+
+/// Uploads multiple files via the global client.
+///
+/// Refer to [`OwoReqwestClient::upload_files`] for more information.
+///
+/// # Errors
+///
+/// Returns [`Error::NoKeyConfigured`] if [`set_key`] has not been called.
+///
+/// [`Error::NoKeyConfigured`]: ../enum.Error.html#variant.NoKeyConfigured
+/// [`OwoReqwestClient::upload_files`]: ../bridge/reqwest/struct.OwoClient.html#method.upload_files
+/// [`set_key`]: fn.set_key.html
+pub fn upload_files(files: Vec<Vec<u8>>) -> Result<FileUploadResponse> {
+    client()?.upload_files(files)
+}
+
+/// Shortens a URL via the global client.
+///
+/// Refer to [`OwoReqwestClient::shorten_url`] for more information.
+///
+/// # Errors
+///
+/// Returns [`Error::NoKeyConfigured`] if [`set_key`] has not been called.
+///
+/// [`Error::NoKeyConfigured`]: ../enum.Error.html#variant.NoKeyConfigured
+/// [`OwoReqwestClient::shorten_url`]: ../bridge/reqwest/struct.OwoClient.html#method.shorten_url
+/// [`set_key`]: fn.set_key.html
+pub fn shorten_url(url: &str) -> Result<String> {
+    client()?.shorten_url(url)
+}
+
+/// Retrieves a handle to the global client.
+///
+/// The client is cloned - which is cheap - so that the lock is not held for
+/// the duration of a request.
+fn client() -> Result<OwoClient> {
+    CLIENT.read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+        .ok_or(Error::NoKeyConfigured)
+}
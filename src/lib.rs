@@ -7,9 +7,48 @@
 //!
 //! - **hyper-support**: Compiles with `hyper` support
 //! - **reqwest-support**: Compiles with `reqwest` support (*default*)
-
+//! - **hyper-rustls**: Compiles with `hyper` support, using `rustls` for
+//!   TLS rather than `native-tls`
+//! - **reqwest-rustls**: Compiles with `reqwest` support, using `rustls` for
+//!   TLS rather than `native-tls`
+//! - **config**: Enables reading client settings from a TOML configuration
+//!   file
+//! - **exif-strip**: Enables removing metadata - such as the location a photo
+//!   was taken at - from images before uploading them
+//! - **log**: Logs events such as failing over to a mirror of the API via
+//!   the `log` crate
+//! - **keyring**: Enables storing and loading keys via the operating system's
+//!   credential store
+//! - **dangerous-config**: Enables configuration options which weaken the
+//!   security of the client, such as skipping TLS hostname verification. Only
+//!   intended for testing
+//! - **strict-models**: Fails to deserialize responses containing fields which
+//!   the models don't know of, rather than ignoring them. Intended for catching
+//!   changes to the API, such as in staging
+//! - **backtrace**: Captures a backtrace when a network or IO error is
+//!   created, if backtraces are enabled via `RUST_BACKTRACE`
+//! - **zeroize**: Wipes keys - and request URLs containing keys - from memory
+//!   when they are no longer in use
+//!
+//! **note**: By default, TLS is provided by `native-tls` (OpenSSL on Linux)
+//! for both bridges. The `rustls` features trust the Mozilla root
+//! certificates bundled by `webpki-roots` rather than the system's. They are
+//! not mutually exclusive with the `native-tls` ones: if both are enabled for
+//! a bridge, such as by another crate in the dependency tree, it connects via
+//! `rustls`, but `native-tls` is still built and linked. Options which only
+//! `native-tls` supports are then ignored - as with `min_tls_version` and
+//! `danger_disable_hostname_verification` - or fail the build of the client,
+//! as with PKCS #12 identities. Disable the default features to drop
+//! `native-tls` entirely.
+//!
+//! **note**: Neither bridge supports `wasm32-unknown-unknown`, as `reqwest`
+//! v0.9 and `hyper` v0.11 require native sockets. To upload from a browser,
+//! use the types-only build described below alongside your own `fetch`
+//! transport, building requests via the `endpoints` module
+//!
 //! **note**: `hyper` support is minimal due to lack of existing ecosystem
-//! multipart support, and is currently restricted to URL shortening
+//! multipart support, and is currently restricted to URL shortening and
+//! uploading a file from an `AsyncRead`
 //!
 //! ### Installation
 //!
@@ -24,7 +63,7 @@
 //!
 //! ```toml
 //! [dependencies.owo]
-//! features = ["hyper", "reqwest"]
+//! features = ["hyper-support", "reqwest-support"]
 //! version = "~0.2"
 //! ```
 //!
@@ -33,10 +72,23 @@
 //! ```toml
 //! [dependencies.owo]
 //! default-features = false
-//! features = ["reqwest"]
+//! features = ["reqwest-support"]
 //! version = "~0.2"
 //! ```
 //!
+//! To use only the models and URL builders - such as when supplying your own
+//! HTTP transport - without depending on either HTTP client:
+//!
+//! ```toml
+//! [dependencies.owo]
+//! default-features = false
+//! features = ["serde-items"]
+//! version = "~0.2"
+//! ```
+//!
+//! Without `serde-items`, the models are still available, but can't be
+//! deserialized from responses.
+//!
 //! ### Examples
 //!
 //! Using reqwest, upload a file by its filepath as a string taken from user
@@ -95,18 +147,36 @@
 //! [examples]: https://github.com/whats-this/owo.rs/blob/master/examples
 #![deny(missing_docs)]
 
+#[cfg(feature = "backtrace")]
+extern crate backtrace;
+#[cfg(feature = "futures")]
+extern crate futures;
+#[cfg(feature = "httpdate")]
+extern crate httpdate;
 #[cfg(feature = "hyper")]
 extern crate hyper;
 #[cfg(feature = "hyper-tls")]
 extern crate hyper_tls;
-#[cfg(feature = "multipart")]
-extern crate multipart;
-#[cfg(feature = "multipart-async")]
-extern crate multipart_async;
+#[cfg(feature = "img-parts")]
+extern crate img_parts;
+#[cfg(feature = "keyring")]
+extern crate keyring;
+#[cfg(feature = "lazy_static")]
+#[macro_use]
+extern crate lazy_static;
+#[cfg(feature = "log")]
+#[macro_use]
+extern crate log;
 #[cfg(feature = "native-tls")]
 extern crate native_tls;
 #[cfg(feature = "reqwest")]
 extern crate reqwest;
+#[cfg(feature = "reqwest-hyper")]
+extern crate reqwest_hyper;
+#[cfg(feature = "reqwest-native-tls")]
+extern crate reqwest_native_tls;
+#[cfg(feature = "rustls")]
+extern crate rustls;
 #[cfg(feature = "serde")]
 extern crate serde;
 #[cfg(feature = "serde_derive")]
@@ -114,24 +184,84 @@ extern crate serde;
 extern crate serde_derive;
 #[cfg(feature = "serde_json")]
 extern crate serde_json;
+#[cfg(feature = "tokio-codec")]
+extern crate tokio_codec;
 #[cfg(feature = "tokio-core")]
 extern crate tokio_core;
+#[cfg(feature = "tokio-io")]
+extern crate tokio_io;
+#[cfg(feature = "tokio-rustls")]
+extern crate tokio_rustls;
+#[cfg(feature = "tokio-service")]
+extern crate tokio_service;
+#[cfg(feature = "toml")]
+extern crate toml;
+#[cfg(feature = "webpki")]
+extern crate webpki;
+#[cfg(feature = "webpki-roots")]
+extern crate webpki_roots;
+#[cfg(feature = "zeroize")]
+extern crate zeroize;
+
+// The reqwest bridge parses every response, so it can't be built without the
+// models' serde support. Enabling the optional dependency directly - rather
+// than via `reqwest-support` - would otherwise fail with many unrelated
+// errors.
+#[cfg(all(
+    feature = "reqwest",
+    not(all(feature = "serde_derive", feature = "serde_json"))
+))]
+compile_error!(
+    "the `reqwest` bridge requires the `serde-items` feature; enable the \
+     `reqwest-support` feature rather than `reqwest` alone"
+);
 
 pub mod bridge;
+pub mod config;
 pub mod constants;
+pub mod domains;
+pub mod endpoints;
+pub mod limits;
+pub mod prelude;
+pub mod prepared;
 
-#[cfg(feature = "serde_derive")]
+#[cfg(feature = "keyring")]
+pub mod credentials;
+#[cfg(all(feature = "lazy_static", feature = "reqwest"))]
+pub mod global;
+#[cfg(feature = "img-parts")]
+pub mod metadata;
 pub mod model;
 
 mod error;
+#[cfg(any(feature = "hyper", feature = "keyring", feature = "reqwest"))]
+mod secret;
 
-pub use error::{Error, Result};
+pub use config::Config;
+pub use error::{
+    ApiErrorCode,
+    Error,
+    ErrorKind,
+    NetworkErrorKind,
+    Result,
+    Trace,
+};
+pub use limits::ServiceLimits;
+pub use prepared::PreparedRequest;
 
 #[cfg(feature = "hyper")]
 pub use bridge::hyper::OwoRequester as OwoHyperRequester;
 #[cfg(feature = "hyper")]
 pub use bridge::hyper::OwoClient as OwoHyperClient;
+#[cfg(feature = "hyper")]
+pub use bridge::hyper::OwoClientBuilder as OwoHyperClientBuilder;
+#[cfg(feature = "reqwest")]
+pub use bridge::reqwest::BackoffStrategy;
 #[cfg(feature = "reqwest")]
 pub use bridge::reqwest::OwoRequester as OwoReqwestRequester;
 #[cfg(feature = "reqwest")]
 pub use bridge::reqwest::OwoClient as OwoReqwestClient;
+#[cfg(feature = "reqwest")]
+pub use bridge::reqwest::OwoClientBuilder as OwoReqwestClientBuilder;
+#[cfg(all(feature = "lazy_static", feature = "reqwest"))]
+pub use global::{set_key, shorten_url, upload_file, upload_files};
@@ -0,0 +1,117 @@
+//! Limits of the service, which requests are checked against before being
+//! made.
+
+use ::constants;
+#[cfg(feature = "reqwest")]
+use ::{Error, Result};
+
+/// The limits of the service, which requests are checked against before being
+/// made, so that requests which would be rejected fail early.
+///
+/// The [`Default`] limits are those of the official service. Self-hosted
+/// instances often allow more, so each limit can be raised, or disabled
+/// entirely by setting it to `None`.
+///
+/// # Examples
+///
+/// Allow up to 10 files of up to 1 GiB each, with no limit on the length of
+/// URLs to shorten:
+///
+/// ```rust
+/// use owo::ServiceLimits;
+///
+/// let limits = ServiceLimits {
+///     max_file_size: Some(1024 * 1024 * 1024),
+///     max_files: Some(10),
+///     max_url_length: None,
+/// };
+/// ```
+///
+/// [`Default`]: #impl-Default
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ServiceLimits {
+    /// The maximum size of each uploaded file, in bytes.
+    ///
+    /// Defaults to [`constants::MAX_FILE_SIZE`].
+    ///
+    /// [`constants::MAX_FILE_SIZE`]: ../constants/constant.MAX_FILE_SIZE.html
+    pub max_file_size: Option<u64>,
+    /// The maximum number of files that may be uploaded in one request.
+    ///
+    /// Defaults to [`constants::MAX_FILES`].
+    ///
+    /// [`constants::MAX_FILES`]: ../constants/constant.MAX_FILES.html
+    pub max_files: Option<usize>,
+    /// The maximum length of a URL to shorten, in bytes.
+    ///
+    /// Defaults to [`constants::MAX_URL_LENGTH`].
+    ///
+    /// [`constants::MAX_URL_LENGTH`]: ../constants/constant.MAX_URL_LENGTH.html
+    pub max_url_length: Option<usize>,
+}
+
+impl ServiceLimits {
+    /// Creates a set of limits with every limit disabled.
+    pub fn unlimited() -> Self {
+        Self {
+            max_file_size: None,
+            max_files: None,
+            max_url_length: None,
+        }
+    }
+
+    /// Checks that a set of files to upload in one request is within the
+    /// limits.
+    #[cfg(feature = "reqwest")]
+    pub(crate) fn check_files(&self, files: &[Vec<u8>]) -> Result<()> {
+        if let Some(limit) = self.max_files {
+            if files.len() > limit {
+                return Err(Error::TooManyFiles {
+                    provided: files.len(),
+                    limit,
+                });
+            }
+        }
+
+        for file in files {
+            self.check_file_size(file.len() as u64)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the size of a file to upload, in bytes, is within the
+    /// limits.
+    #[cfg(feature = "reqwest")]
+    pub(crate) fn check_file_size(&self, size: u64) -> Result<()> {
+        match self.max_file_size {
+            Some(limit) if size > limit => Err(Error::FileTooLarge {
+                size,
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that a URL to shorten is within the limits.
+    #[cfg(feature = "reqwest")]
+    pub(crate) fn check_url(&self, url: &str) -> Result<()> {
+        match self.max_url_length {
+            Some(limit) if url.len() > limit => Err(Error::UrlTooLong {
+                length: url.len(),
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for ServiceLimits {
+    fn default() -> Self {
+        Self {
+            max_file_size: Some(constants::MAX_FILE_SIZE),
+            max_files: Some(constants::MAX_FILES),
+            max_url_length: Some(constants::MAX_URL_LENGTH),
+        }
+    }
+}
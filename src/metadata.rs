@@ -0,0 +1,76 @@
+//! Removal of metadata from images before they are uploaded.
+//!
+//! This is only available with the `exif-strip` feature.
+//!
+//! Photos taken on phones commonly embed the location they were taken at and
+//! details of the device, and screenshots may embed the name of the program
+//! which took them. Stripping the metadata before uploading prevents these
+//! from being shared along with the image.
+
+use img_parts::jpeg::{Jpeg, markers};
+use img_parts::png::Png;
+use img_parts::Bytes;
+
+/// The kinds of PNG chunks which contain metadata, rather than image data.
+const PNG_METADATA_CHUNKS: [&[u8; 4]; 5] = [
+    b"eXIf",
+    b"iTXt",
+    b"tEXt",
+    b"tIME",
+    b"zTXt",
+];
+
+/// Removes the metadata from a JPEG or PNG image.
+///
+/// For JPEGs, the EXIF, XMP, and IPTC segments and comments are removed. For
+/// PNGs, the EXIF, text, and modification time chunks are removed. Colour
+/// profiles are kept, as they affect how the image is displayed.
+///
+/// If the bytes are not a recognized image, they are returned unchanged.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// #
+/// use owo::metadata;
+/// use std::fs::File;
+/// use std::io::Read;
+///
+/// let mut image = vec![];
+/// File::open("./photo.jpg")?.read_to_end(&mut image)?;
+///
+/// let stripped = metadata::strip(image);
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+pub fn strip(image: Vec<u8>) -> Vec<u8> {
+    let bytes = Bytes::from(image);
+
+    if let Ok(mut jpeg) = Jpeg::from_bytes(bytes.clone()) {
+        jpeg.segments_mut().retain(|segment| {
+            match segment.marker() {
+                markers::APP1 | markers::APP13 | markers::COM => false,
+                _ => true,
+            }
+        });
+
+        return jpeg.encoder().bytes().to_vec();
+    }
+
+    if let Ok(mut png) = Png::from_bytes(bytes.clone()) {
+        png.chunks_mut().retain(|chunk| {
+            !PNG_METADATA_CHUNKS.iter().any(|kind| **kind == chunk.kind())
+        });
+
+        return png.encoder().bytes().to_vec();
+    }
+
+    bytes.to_vec()
+}
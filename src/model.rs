@@ -1,23 +1,884 @@
 //! Models in struct form, parsed out from JSON in response bodies.
+//!
+//! The models are always available, such as for crates which only want the
+//! types. Deserializing them from - and serializing them into - JSON requires
+//! the `serde-items` feature, which the `reqwest-support` feature enables.
+//!
+//! By default, fields of responses which the models don't know of are
+//! ignored, so that the service adding fields doesn't break clients. With the
+//! `strict-models` feature enabled, such responses instead fail to
+//! deserialize, to catch the API changing - such as a field being renamed -
+//! before data is silently dropped. Fields which the service may omit, such
+//! as those of rejected files, remain optional either way.
+
+#[cfg(feature = "serde_derive")]
+use serde::de::{Error as DeError, Unexpected, Visitor};
+#[cfg(feature = "serde_derive")]
+use serde::{Deserialize, Deserializer};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::ops::Index;
+#[cfg(feature = "serde_derive")]
+use std::result::Result as StdResult;
+use std::slice::Iter;
+use std::vec::IntoIter;
+use ::constants;
+#[cfg(feature = "serde_json")]
+use serde_json;
+#[cfg(feature = "serde_json")]
+use ::{ApiErrorCode, Error, Result};
 
 /// Representation of the body response to a file upload request.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+///
+/// Besides the service's own shape of a `files` array, the shape returned by
+/// some older pomf forks - a single file's `url` at the top level, such as
+/// `{"success": true, "url": "https://example.com/a1b2c3.png"}` - is also
+/// accepted. Such a response is normalized into one containing a single file,
+/// with the fields the response lacks left empty.
+///
+/// Unsuccessful responses may contain no files, instead describing the error
+/// via [`errorcode`] and [`description`].
+///
+/// Variations sent by other pomf-compatible hosts are also tolerated: a `null`
+/// or missing `files` is taken as no files, a missing `success` is inferred
+/// from whether an `errorcode` is present, and file sizes may be given as
+/// strings such as `"1024"`.
+///
+/// # Examples
+///
+/// Iterate over the uploaded files, by reference or by value:
+///
+/// ```rust,no_run
+/// # use owo::model::FileUploadResponse;
+/// #
+/// # fn print(response: FileUploadResponse) {
+/// for file in &response {
+///     println!("{}: {}", file.hash, file.full_url());
+/// }
+///
+/// let urls = response.into_iter()
+///     .map(|file| file.url)
+///     .collect::<Vec<String>>();
+/// # }
+/// ```
+///
+/// [`description`]: #structfield.description
+/// [`errorcode`]: #structfield.errorcode
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize))]
 pub struct FileUploadResponse {
+    /// The service's description of why the upload failed, if it did.
+    #[cfg_attr(
+        feature = "serde_derive",
+        serde(skip_serializing_if = "Option::is_none")
+    )]
+    pub description: Option<String>,
+    /// The error code given by the service if the upload failed, usually
+    /// matching the HTTP status of the response.
+    ///
+    /// This can be turned into an [`ApiErrorCode`] to find its meaning.
+    ///
+    /// [`ApiErrorCode`]: ../enum.ApiErrorCode.html
+    #[cfg_attr(
+        feature = "serde_derive",
+        serde(skip_serializing_if = "Option::is_none")
+    )]
+    pub errorcode: Option<i64>,
     /// The uploaded files.
+    ///
+    /// Responses which do not include any, such as those of failed uploads,
+    /// leave this empty.
     pub files: Vec<UploadedFile>,
     /// Whether uploading the file(s) was successful.
     pub success: bool,
 }
 
+impl FileUploadResponse {
+    /// Retrieves the uploaded files.
+    pub fn files(&self) -> &[UploadedFile] {
+        &self.files
+    }
+
+    /// Retrieves the first uploaded file, such as the only file of a
+    /// single-file upload, if there is one.
+    pub fn first(&self) -> Option<&UploadedFile> {
+        self.files.first()
+    }
+
+    /// Whether the response contains no files, such as that of a failed
+    /// upload.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Retrieves the number of files in the response.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Produces the full URLs to the uploaded files on the [default domain],
+    /// in the order they were uploaded.
+    ///
+    /// Files which the service rejected are skipped.
+    ///
+    /// Use [`urls_on`] for files served from another domain.
+    ///
+    /// [`urls_on`]: #method.urls_on
+    /// [default domain]: ../constants/constant.DEFAULT_DOMAIN.html
+    pub fn urls(&self) -> Vec<String> {
+        self.urls_on(constants::DEFAULT_DOMAIN)
+    }
+
+    /// Produces the full URLs to the uploaded files on a domain, in the order
+    /// they were uploaded.
+    ///
+    /// Files which the service rejected are skipped. The domain is treated as
+    /// by [`UploadedFile::full_url_on`].
+    ///
+    /// # Examples
+    ///
+    /// Print links to the files on a vanity domain. To link to files on the
+    /// domain of a reqwest client, such as one set via its builder's
+    /// `preferred_domain`, pass the client's `domain()`.
+    ///
+    /// ```rust,no_run
+    /// # use owo::model::FileUploadResponse;
+    /// #
+    /// # fn print(response: FileUploadResponse) {
+    /// // Prints URLs such as "https://uwu.whats-th.is/a1b2c3.png".
+    /// for url in response.urls_on("uwu.whats-th.is") {
+    ///     println!("{}", url);
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`UploadedFile::full_url_on`]: struct.UploadedFile.html#method.full_url_on
+    pub fn urls_on(&self, domain: &str) -> Vec<String> {
+        self.files.iter()
+            .filter(|file| !file.is_error())
+            .map(|file| file.full_url_on(domain))
+            .collect()
+    }
+
+    /// Takes the uploaded files out of the response, without cloning them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use owo::model::FileUploadResponse;
+    /// # use std::sync::mpsc::Sender;
+    /// #
+    /// # fn stash(response: FileUploadResponse, sender: Sender<String>) {
+    /// for file in response.into_files() {
+    ///     sender.send(file.url).unwrap();
+    /// }
+    /// # }
+    /// ```
+    pub fn into_files(self) -> Vec<UploadedFile> {
+        self.files
+    }
+
+    /// Takes the first uploaded file out of the response, without cloning
+    /// it, if there is one.
+    pub fn into_first(self) -> Option<UploadedFile> {
+        self.files.into_iter().next()
+    }
+
+    /// Takes the only file out of the response to a single-file upload,
+    /// checking that the upload succeeded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use owo::model::FileUploadResponse;
+    /// #
+    /// # fn try_main(response: FileUploadResponse) -> owo::Result<()> {
+    /// let file = response.into_single()?;
+    ///
+    /// println!("Uploaded to {}", file.full_url());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Api`] if the upload failed with an error code, or
+    /// [`Error::ApiFailure`] if it failed without one.
+    ///
+    /// Returns [`Error::NoFilesReturned`] if the response contains no files.
+    ///
+    /// [`Error::Api`]: ../enum.Error.html#variant.Api
+    /// [`Error::ApiFailure`]: ../enum.Error.html#variant.ApiFailure
+    /// [`Error::NoFilesReturned`]: ../enum.Error.html#variant.NoFilesReturned
+    #[cfg(feature = "serde_json")]
+    pub fn into_single(self) -> Result<UploadedFile> {
+        self.single()?;
+
+        self.into_first().ok_or(Error::NoFilesReturned)
+    }
+
+    /// Borrows the only file of the response to a single-file upload,
+    /// failing in the same ways as [`into_single`].
+    ///
+    /// [`into_single`]: #method.into_single
+    #[cfg(feature = "serde_json")]
+    pub(crate) fn single(&self) -> Result<&UploadedFile> {
+        if !self.success {
+            return Err(match self.errorcode {
+                Some(code) => Error::Api {
+                    code,
+                    description: self.description.clone().unwrap_or_default(),
+                    kind: ApiErrorCode::from(code),
+                },
+                None => Error::ApiFailure {
+                    body: serde_json::to_string(self).unwrap_or_default(),
+                },
+            });
+        }
+
+        self.first().ok_or(Error::NoFilesReturned)
+    }
+}
+
+#[cfg(feature = "serde_derive")]
+impl<'de> Deserialize<'de> for FileUploadResponse {
+    fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
+        where D: Deserializer<'de> {
+        let RawUploadResponse {
+            description,
+            errorcode,
+            files,
+            hash,
+            name,
+            size,
+            success,
+            url,
+        } = RawUploadResponse::deserialize(deserializer)?;
+        let has_success = success.is_some();
+        let success = success.unwrap_or_else(|| errorcode.is_none());
+
+        // The shape returned by some older pomf forks, with a single file's
+        // URL at the top level.
+        if let Some(url) = url {
+            if files.is_some() {
+                return Err(D::Error::custom(
+                    "expected either `files` or a top-level `url`, not both",
+                ));
+            }
+
+            return Ok(FileUploadResponse {
+                description,
+                errorcode,
+                files: vec![UploadedFile {
+                    content_type: None,
+                    delete_url: None,
+                    description: None,
+                    error: None,
+                    errorcode: None,
+                    hash: hash.unwrap_or_default(),
+                    name,
+                    size: size.unwrap_or(0),
+                    url,
+                }],
+                success,
+            });
+        }
+
+        // The service's own shape, with an array of files, or an error code
+        // and description if the upload failed. Every field is optional, so
+        // at least one of them is required to tell it apart from any other
+        // object.
+        if files.is_none() && errorcode.is_none() && !has_success {
+            return Err(D::Error::custom(
+                "expected at least one of `files`, `success` or `errorcode`",
+            ));
+        }
+
+        // The fields of the legacy shape are only known alongside its `url`.
+        if cfg!(feature = "strict-models") {
+            let legacy = [
+                ("hash", hash.is_some()),
+                ("name", name.is_some()),
+                ("size", size.is_some()),
+            ];
+
+            if let Some(&(field, _)) = legacy.iter().find(|&&(_, set)| set) {
+                return Err(D::Error::unknown_field(field, UPLOAD_FIELDS));
+            }
+        }
+
+        Ok(FileUploadResponse {
+            description,
+            errorcode,
+            files: files.unwrap_or_default(),
+            success,
+        })
+    }
+}
+
+/// The fields of the service's shape of upload response bodies.
+#[cfg(feature = "serde_derive")]
+const UPLOAD_FIELDS: &[&str] = &[
+    "description",
+    "errorcode",
+    "files",
+    "success",
+];
+
+/// Formats the response for end users, as the [full URL] of each file on its
+/// own line - or the reason it was rejected - preceded by the reason the
+/// upload failed, if it did.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use owo::model::FileUploadResponse;
+/// #
+/// # fn print(response: FileUploadResponse) {
+/// // Prints a line such as "https://owo.whats-th.is/a1b2c3.png" per file.
+/// println!("{}", response);
+/// # }
+/// ```
+///
+/// [full URL]: struct.UploadedFile.html#method.full_url
+impl Display for FileUploadResponse {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let mut first = true;
+
+        if !self.success {
+            f.write_str("Upload failed")?;
+
+            match (self.description.as_ref(), self.errorcode) {
+                (Some(description), _) => write!(f, ": {}", description)?,
+                (None, Some(code)) => write!(f, " with error code {}", code)?,
+                (None, None) => {},
+            }
+
+            first = false;
+        }
+
+        for file in &self.files {
+            if !first {
+                f.write_str("\n")?;
+            }
+
+            Display::fmt(file, f)?;
+            first = false;
+        }
+
+        Ok(())
+    }
+}
+
+impl Index<usize> for FileUploadResponse {
+    type Output = UploadedFile;
+
+    fn index(&self, idx: usize) -> &UploadedFile {
+        &self.files[idx]
+    }
+}
+
+impl IntoIterator for FileUploadResponse {
+    type Item = UploadedFile;
+    type IntoIter = IntoIter<UploadedFile>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.files.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a FileUploadResponse {
+    type Item = &'a UploadedFile;
+    type IntoIter = Iter<'a, UploadedFile>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.files.iter()
+    }
+}
+
+/// The progress of a file upload.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Progress {
+    /// The number of bytes of the file sent so far.
+    pub sent: u64,
+    /// The total size of the file, in bytes.
+    pub total: u64,
+}
+
+/// The fields of every accepted shape of upload response bodies.
+///
+/// Rather than trying each shape in turn - which would hide why a body
+/// matched none of them - the shape is told apart by which fields are
+/// present, so that errors such as a field of the wrong type come through.
+#[cfg(feature = "serde_derive")]
+#[derive(Deserialize)]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+struct RawUploadResponse {
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    errorcode: Option<i64>,
+    #[serde(default)]
+    files: Option<Vec<UploadedFile>>,
+    #[serde(default)]
+    hash: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default, deserialize_with = "optional_string_or_number")]
+    size: Option<u64>,
+    #[serde(default)]
+    success: Option<bool>,
+    #[serde(default)]
+    url: Option<String>,
+}
+
+/// Deserializes a size which may be omitted, given either as a number or as a
+/// string containing one.
+#[cfg(feature = "serde_derive")]
+fn optional_string_or_number<'de, D>(deserializer: D)
+    -> StdResult<Option<u64>, D::Error>
+    where D: Deserializer<'de> {
+    string_or_number(deserializer).map(Some)
+}
+
+/// Deserializes a size given either as a number or as a string containing
+/// one, as some pomf-compatible hosts send the latter.
+#[cfg(feature = "serde_derive")]
+fn string_or_number<'de, D>(deserializer: D) -> StdResult<u64, D::Error>
+    where D: Deserializer<'de> {
+    struct SizeVisitor;
+
+    impl<'de> Visitor<'de> for SizeVisitor {
+        type Value = u64;
+
+        fn expecting(&self, f: &mut Formatter) -> FmtResult {
+            f.write_str("a size as a number or a string")
+        }
+
+        fn visit_u64<E: DeError>(self, value: u64) -> StdResult<u64, E> {
+            Ok(value)
+        }
+
+        fn visit_i64<E: DeError>(self, value: i64) -> StdResult<u64, E> {
+            if value < 0 {
+                return Err(E::invalid_value(Unexpected::Signed(value), &self));
+            }
+
+            Ok(value as u64)
+        }
+
+        fn visit_str<E: DeError>(self, value: &str) -> StdResult<u64, E> {
+            value.trim()
+                .parse()
+                .map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+        }
+    }
+
+    deserializer.deserialize_any(SizeVisitor)
+}
+
+/// Representation of the body response to a URL shortening request which
+/// asked for a JSON response.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde_derive", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
+pub struct ShortenResponse {
+    /// The action which was performed, such as `"shorten"`.
+    pub action: String,
+    /// The short URL.
+    pub result: String,
+}
+
 /// Definition of the structure representing information of an uploaded file.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde_derive", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "strict-models", serde(deny_unknown_fields))]
 pub struct UploadedFile {
+    /// MIME type which the service recognized the file as, such as
+    /// `"image/png"`, if the service provided one.
+    ///
+    /// This can be used to verify that the file was stored as the expected
+    /// type. Responses which do not include it leave this as `None`.
+    #[cfg_attr(
+        feature = "serde_derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub content_type: Option<String>,
+    /// URL which deletes the file when requested, if the service provided one.
+    ///
+    /// Keep this to be able to delete the file later, such as via
+    /// `OwoReqwestClient::delete_file`. Responses which do not include it -
+    /// such as those of services not supporting deletion - leave this as
+    /// `None`.
+    #[cfg_attr(
+        feature = "serde_derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub delete_url: Option<String>,
+    /// The service's description of why the file was rejected, if it was.
+    #[cfg_attr(
+        feature = "serde_derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub description: Option<String>,
+    /// Whether the file was rejected, such as when one file of a batch is
+    /// not allowed, if the service said.
+    ///
+    /// Refer to [`is_error`] to check this.
+    ///
+    /// [`is_error`]: #method.is_error
+    #[cfg_attr(
+        feature = "serde_derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub error: Option<bool>,
+    /// The error code given by the service for the file, if it was rejected.
+    ///
+    /// This can be turned into an [`ApiErrorCode`] to find its meaning.
+    ///
+    /// [`ApiErrorCode`]: ../enum.ApiErrorCode.html
+    #[cfg_attr(
+        feature = "serde_derive",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub errorcode: Option<i64>,
     /// Identifying hash of the uploaded file.
+    ///
+    /// Entries of rejected files may not include this, leaving it empty.
+    #[cfg_attr(feature = "serde_derive", serde(default))]
     pub hash: String,
     /// Name of the file when uploaded, if given.
     pub name: Option<String>,
     /// Size of the file in bytes.
+    ///
+    /// Entries of rejected files may not include this, leaving it as `0`.
+    #[cfg_attr(
+        feature = "serde_derive",
+        serde(default, deserialize_with = "string_or_number")
+    )]
     pub size: u64,
     /// URL fragment to the file.
+    ///
+    /// Entries of rejected files may not include this, leaving it empty.
+    ///
+    /// Responses in the legacy shape of older pomf forks contain the full URL
+    /// instead, which [`full_url`] returns as-is.
+    ///
+    /// [`full_url`]: #method.full_url
+    #[cfg_attr(feature = "serde_derive", serde(default))]
     pub url: String,
 }
+
+impl UploadedFile {
+    /// Retrieves the identifier of the file, being its [`url`] fragment
+    /// without the file extension.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use owo::model::UploadedFile;
+    /// #
+    /// # fn print(file: UploadedFile) {
+    /// // Prints "a1b2c3" for a file with the URL fragment "a1b2c3.png".
+    /// println!("{}", file.id());
+    /// # }
+    /// ```
+    ///
+    /// [`url`]: #structfield.url
+    pub fn id(&self) -> &str {
+        let file_name = self.file_name();
+
+        match file_name.find('.') {
+            Some(idx) => &file_name[..idx],
+            None => file_name,
+        }
+    }
+
+    /// Retrieves the file extension from the file's [`url`] fragment, without
+    /// the leading dot, if it has one.
+    ///
+    /// Identifiers never contain a dot, so everything after the first dot is
+    /// the extension, such as `"tar.gz"` for the fragment `"a1b2c3.tar.gz"`.
+    ///
+    /// [`url`]: #structfield.url
+    pub fn extension(&self) -> Option<&str> {
+        let file_name = self.file_name();
+
+        match file_name.find('.') {
+            Some(idx) if idx + 1 < file_name.len() => {
+                Some(&file_name[idx + 1..])
+            },
+            _ => None,
+        }
+    }
+
+    /// Formats a link to the file on a domain as Markdown, embedding it as an
+    /// image if it is one, such as `![cat.png](https://owo.whats-th.is/a.png)`.
+    ///
+    /// The text of the link is the file's [`name`] - or its [`id`] if it has
+    /// none - with Markdown's special characters escaped.
+    ///
+    /// [`id`]: #method.id
+    /// [`name`]: #structfield.name
+    pub fn as_markdown(&self, domain: &str) -> String {
+        let mut text = String::new();
+
+        for c in self.display_name().chars() {
+            if "\\`*_[]()<>!#".contains(c) {
+                text.push('\\');
+            }
+
+            text.push(c);
+        }
+
+        let url = self.full_url_on(domain)
+            .replace(' ', "%20")
+            .replace('(', "%28")
+            .replace(')', "%29");
+
+        if self.is_image() {
+            format!("![{}]({})", text, url)
+        } else {
+            format!("[{}]({})", text, url)
+        }
+    }
+
+    /// Formats a link to the file on a domain as HTML, embedding it as an
+    /// `<img>` if it is an image, or as an `<a>` otherwise.
+    ///
+    /// The file's [`name`] - or its [`id`] if it has none - is used as the
+    /// image's alternative text or the link's text, escaped for HTML.
+    ///
+    /// [`id`]: #method.id
+    /// [`name`]: #structfield.name
+    pub fn as_html(&self, domain: &str) -> String {
+        let text = escape_html(self.display_name());
+        let url = escape_html(&self.full_url_on(domain));
+
+        if self.is_image() {
+            format!("<img src=\"{}\" alt=\"{}\">", url, text)
+        } else {
+            format!("<a href=\"{}\">{}</a>", url, text)
+        }
+    }
+
+    /// Formats a link to the file on a domain as BBCode, such as for forums,
+    /// embedding it via `[img]` if it is an image, or via `[url]` otherwise.
+    ///
+    /// BBCode has no way of escaping, so square brackets in the file's
+    /// [`name`] are replaced with parentheses in the text of a `[url]`.
+    ///
+    /// [`name`]: #structfield.name
+    pub fn as_bbcode(&self, domain: &str) -> String {
+        let url = self.full_url_on(domain)
+            .replace('[', "%5B")
+            .replace(']', "%5D");
+
+        if self.is_image() {
+            return format!("[img]{}[/img]", url);
+        }
+
+        let text = self.display_name().replace('[', "(").replace(']', ")");
+
+        format!("[url={}]{}[/url]", url, text)
+    }
+
+    /// Whether the file is an image, going by its [`content_type`] if the
+    /// service gave one, or by its [`extension`] otherwise.
+    ///
+    /// [`content_type`]: #structfield.content_type
+    /// [`extension`]: #method.extension
+    pub fn is_image(&self) -> bool {
+        const EXTENSIONS: [&str; 8] = [
+            "bmp", "gif", "jpeg", "jpg", "png", "svg", "tiff", "webp",
+        ];
+
+        if let Some(ref content_type) = self.content_type {
+            return content_type.starts_with("image/");
+        }
+
+        match self.extension() {
+            Some(extension) => EXTENSIONS.iter()
+                .any(|known| known.eq_ignore_ascii_case(extension)),
+            None => false,
+        }
+    }
+
+    /// Formats the [`size`] of the file for end users, in binary units with
+    /// one decimal place, such as `"3.3 MiB"`.
+    ///
+    /// Sizes under a kibibyte are given in whole bytes, such as `"512 B"`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use owo::model::UploadedFile;
+    /// #
+    /// # fn print(file: UploadedFile) {
+    /// // Prints a line such as "Uploaded 3.3 MiB".
+    /// println!("Uploaded {}", file.size_human());
+    /// # }
+    /// ```
+    ///
+    /// [`size`]: #structfield.size
+    pub fn size_human(&self) -> String {
+        const UNITS: [&str; 6] = [
+            "KiB", "MiB", "GiB", "TiB", "PiB", "EiB",
+        ];
+
+        if self.size < 1024 {
+            return format!("{} B", self.size);
+        }
+
+        let mut value = self.size as f64 / 1024.0;
+        let mut unit = 0;
+
+        // Moving up while the value would round to 1024.0 of a unit keeps
+        // sizes just under the next unit from showing as such.
+        while value >= 1023.95 && unit + 1 < UNITS.len() {
+            value /= 1024.0;
+            unit += 1;
+        }
+
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+
+    /// The name to show for the file in links: its name if it has one, or its
+    /// identifier otherwise.
+    fn display_name(&self) -> &str {
+        match self.name {
+            Some(ref name) if !name.is_empty() => name,
+            _ => self.id(),
+        }
+    }
+
+    /// Retrieves the final path segment of the file's URL, without any query
+    /// string or fragment.
+    fn file_name(&self) -> &str {
+        let url = self.url
+            .split(|c| c == '?' || c == '#')
+            .next()
+            .unwrap_or("");
+
+        url.rsplit('/').next().unwrap_or(url)
+    }
+
+    /// Whether the service rejected the file, going by its [`error`] flag or
+    /// the presence of an [`errorcode`].
+    ///
+    /// [`error`]: #structfield.error
+    /// [`errorcode`]: #structfield.errorcode
+    pub fn is_error(&self) -> bool {
+        self.error.unwrap_or(false) || self.errorcode.is_some()
+    }
+
+    /// Produces the full URL to the file on the [default domain].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use owo::model::UploadedFile;
+    /// #
+    /// # fn print(file: UploadedFile) {
+    /// // Prints a URL such as "https://owo.whats-th.is/a1b2c3.png".
+    /// println!("{}", file.full_url());
+    /// # }
+    /// ```
+    ///
+    /// If the service returned a full URL rather than a fragment, it is
+    /// returned unchanged.
+    ///
+    /// Use [`full_url_on`] for files served from another domain.
+    ///
+    /// [`full_url_on`]: #method.full_url_on
+    /// [default domain]: ../constants/constant.DEFAULT_DOMAIN.html
+    pub fn full_url(&self) -> String {
+        self.full_url_on(constants::DEFAULT_DOMAIN)
+    }
+
+    /// Produces the full URL to the file on a domain, such as one of the
+    /// service's vanity domains.
+    ///
+    /// The domain may be given with or without a scheme - defaulting to
+    /// `https` - and with or without a trailing slash.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use owo::model::UploadedFile;
+    /// #
+    /// # fn print(file: UploadedFile) {
+    /// // Both print a URL such as "https://i.am-a.furry/a1b2c3.png".
+    /// println!("{}", file.full_url_on("i.am-a.furry"));
+    /// println!("{}", file.full_url_on("https://i.am-a.furry/"));
+    /// # }
+    /// ```
+    ///
+    /// If the service returned a full URL rather than a fragment, it is
+    /// returned unchanged.
+    pub fn full_url_on(&self, domain: &str) -> String {
+        if self.url.contains("://") {
+            return self.url.clone();
+        }
+
+        let domain = domain.trim_end_matches('/');
+        let path = self.url.trim_start_matches('/');
+
+        if domain.contains("://") {
+            format!("{}/{}", domain, path)
+        } else {
+            format!("https://{}/{}", domain, path)
+        }
+    }
+}
+
+/// Formats the file for end users, as its [full URL] on the default domain, or
+/// as the reason it was rejected.
+///
+/// [full URL]: #method.full_url
+impl Display for UploadedFile {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        if !self.is_error() {
+            return f.write_str(&self.full_url());
+        }
+
+        match self.name {
+            Some(ref name) => write!(f, "{} was rejected", name)?,
+            None => f.write_str("File was rejected")?,
+        }
+
+        match (self.description.as_ref(), self.errorcode) {
+            (Some(description), _) => write!(f, ": {}", description),
+            (None, Some(code)) => write!(f, " with error code {}", code),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Escapes text for use within HTML, including within quoted attributes.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// An event of a file upload, as yielded by
+/// `OwoHyperClient::upload_file_progress_stream`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UploadEvent {
+    /// More of the file was sent to the service.
+    Progress(Progress),
+    /// The upload completed, containing the response of the service. This is
+    /// the last event of an upload.
+    Complete(FileUploadResponse),
+}
@@ -0,0 +1,48 @@
+//! A "prelude" re-exporting the commonly used traits and types of the
+//! library.
+//!
+//! Importing the prelude brings the requester traits - and so their methods
+//! on the HTTP clients - into scope, alongside the client types, the models,
+//! and the error types.
+//!
+//! # Examples
+//!
+//! Import the prelude to upload a file via a reqwest Client:
+//!
+//! ```rust,no_run
+//! extern crate owo;
+//! # #[cfg(feature = "reqwest")]
+//! extern crate reqwest;
+//!
+//! # #[cfg(feature = "reqwest")]
+//! # fn try_main() -> owo::Result<()> {
+//! use owo::prelude::*;
+//! use reqwest::Client;
+//!
+//! let client = Client::new();
+//! let response: FileUploadResponse = client.upload_file("key", vec![])?;
+//! #     Ok(())
+//! # }
+//! #
+//! # #[cfg(feature = "reqwest")]
+//! # fn main() {
+//! #     try_main().unwrap();
+//! # }
+//! #
+//! # #[cfg(not(feature = "reqwest"))]
+//! # fn main() { }
+//! ```
+
+pub use ::{ApiErrorCode, Error, ErrorKind, NetworkErrorKind, Result};
+
+#[cfg(feature = "hyper")]
+pub use ::{OwoHyperClient, OwoHyperClientBuilder, OwoHyperRequester};
+pub use ::model::{
+    FileUploadResponse,
+    Progress,
+    ShortenResponse,
+    UploadEvent,
+    UploadedFile,
+};
+#[cfg(feature = "reqwest")]
+pub use ::{OwoReqwestClient, OwoReqwestClientBuilder, OwoReqwestRequester};
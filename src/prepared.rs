@@ -0,0 +1,38 @@
+//! Descriptions of requests which were built but not sent.
+
+/// A request which a client built but did not send, as returned by methods
+/// such as [`OwoReqwestClient::prepare_upload_files`].
+///
+/// This describes exactly what would have been sent, so that an integration
+/// can be checked - such as in tests - without a server.
+///
+/// [`OwoReqwestClient::prepare_upload_files`]: ../bridge/reqwest/struct.OwoClient.html#method.prepare_upload_files
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PreparedRequest {
+    /// The body of the request, such as the multipart body of an upload.
+    ///
+    /// This is empty for requests without a body.
+    pub body: Vec<u8>,
+    /// The headers of the request, as pairs of names and values.
+    pub headers: Vec<(String, String)>,
+    /// The HTTP method of the request, such as `"POST"`.
+    pub method: String,
+    /// The URL of the request, with the key redacted.
+    pub url: String,
+}
+
+impl PreparedRequest {
+    /// Retrieves the size of the body, in bytes.
+    pub fn body_len(&self) -> usize {
+        self.body.len()
+    }
+
+    /// Retrieves the value of a header, if the request has it.
+    ///
+    /// Header names are matched case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
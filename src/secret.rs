@@ -0,0 +1,65 @@
+//! Storage for sensitive strings, such as keys and URLs containing keys.
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+use ::{Error, Result};
+
+/// A string containing sensitive data.
+///
+/// With the `zeroize` feature enabled, the string is wiped from memory when it
+/// is dropped.
+#[derive(Clone)]
+pub(crate) struct Secret(String);
+
+impl Secret {
+    pub(crate) fn new(value: String) -> Self {
+        Secret(value)
+    }
+
+    /// Creates a secret holding a key, trimmed of surrounding whitespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidKey`] if the key is empty after trimming.
+    ///
+    /// [`Error::InvalidKey`]: ../enum.Error.html#variant.InvalidKey
+    pub(crate) fn key(key: String) -> Result<Self> {
+        let key = Secret::new(key);
+        let trimmed = key.as_str().trim();
+
+        if trimmed.is_empty() {
+            return Err(Error::InvalidKey);
+        }
+
+        Ok(Secret::new(trimmed.to_owned()))
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Replaces each occurrence of the secret within some text, such as a
+    /// response body which is to be kept in an error.
+    #[cfg(feature = "serde_json")]
+    pub(crate) fn redact(&self, text: &str) -> String {
+        if self.0.is_empty() {
+            return text.to_owned();
+        }
+
+        text.replace(self.as_str(), "[redacted]")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for Secret {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
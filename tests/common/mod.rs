@@ -0,0 +1,207 @@
+//! Helpers shared between the integration tests.
+
+#![allow(dead_code)]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// A request received by a [`MockServer`].
+///
+/// [`MockServer`]: struct.MockServer.html
+#[derive(Debug)]
+pub struct MockRequest {
+    /// The request line, such as `"POST /upload/pomf?key=key HTTP/1.1"`.
+    pub line: String,
+    /// The headers of the request, with lowercased names.
+    pub headers: Vec<(String, String)>,
+    /// The body of the request.
+    pub body: Vec<u8>,
+}
+
+impl MockRequest {
+    /// Retrieves the value of the first header with the given lowercase name.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A local HTTP server which responds to each request with the same canned
+/// response, recording the requests it receives.
+pub struct MockServer {
+    /// The base URL of the server, such as `"http://127.0.0.1:1234"`.
+    pub url: String,
+    requests: Receiver<MockRequest>,
+}
+
+impl MockServer {
+    /// Starts a server responding with the given status line - such as
+    /// `"200 OK"` - and body.
+    pub fn start(status: &'static str, body: &'static str) -> Self {
+        Self::start_with_headers(status, &[], body)
+    }
+
+    /// Starts a server responding with the given status line, additional
+    /// headers - such as `"Retry-After: 2"` - and body.
+    pub fn start_with_headers(
+        status: &'static str,
+        headers: &'static [&'static str],
+        body: &'static str,
+    ) -> Self {
+        Self::start_raw(status, headers, body.as_bytes())
+    }
+
+    /// Starts a server responding with the given status line, additional
+    /// headers and body, which need not be valid UTF-8.
+    pub fn start_raw(
+        status: &'static str,
+        headers: &'static [&'static str],
+        body: &'static [u8],
+    ) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind err");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, requests) = mpsc::channel();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+                let request = match read_request(&mut stream) {
+                    Some(request) => request,
+                    None => continue,
+                };
+
+                let extra = headers.iter()
+                    .map(|header| format!("{}\r\n", header))
+                    .collect::<String>();
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {}\r\n\
+                     Content-Type: application/json\r\n\
+                     Content-Length: {}\r\n\
+                     Connection: close\r\n\
+                     {}\
+                     \r\n",
+                    status,
+                    body.len(),
+                    extra,
+                ).and_then(|_| stream.write_all(body));
+
+                if sender.send(request).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            url,
+            requests,
+        }
+    }
+
+    /// Waits for the next request received by the server.
+    pub fn request(&self) -> MockRequest {
+        self.requests.recv().expect("no request received")
+    }
+}
+
+/// Starts a server which answers TLS handshakes in plain HTTP, returning an
+/// `https` URL for it, so that the handshake fails whichever TLS backend is in
+/// use.
+///
+/// The host is `localhost` rather than an IP address, as `rustls` only
+/// connects to host names.
+pub fn plaintext_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind err");
+    let port = listener.local_addr().expect("addr err").port();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue,
+            };
+
+            let _ = stream.write_all(
+                b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n",
+            );
+            let _ = stream.read(&mut [0; 1024]);
+        }
+    });
+
+    format!("https://localhost:{}", port)
+}
+
+fn read_request<R: Read>(stream: R) -> Option<MockRequest> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+
+    let mut headers = vec![];
+
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).ok()?;
+        let header = header.trim_end();
+
+        if header.is_empty() {
+            break;
+        }
+
+        let colon = header.find(':')?;
+        headers.push((
+            header[..colon].trim().to_lowercase(),
+            header[colon + 1..].trim().to_owned(),
+        ));
+    }
+
+    let chunked = headers.iter().any(|(name, value)| {
+        name == "transfer-encoding" && value.eq_ignore_ascii_case("chunked")
+    });
+    let body = if chunked {
+        read_chunked(&mut reader)?
+    } else {
+        let length = headers.iter()
+            .find(|(name, _)| name == "content-length")
+            .and_then(|(_, value)| value.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).ok()?;
+
+        body
+    };
+
+    Some(MockRequest {
+        line: line.trim_end().to_owned(),
+        headers,
+        body,
+    })
+}
+
+/// Reads a body sent with chunked transfer encoding, such as those of the
+/// hyper bridge's uploads.
+fn read_chunked<R: BufRead>(reader: &mut R) -> Option<Vec<u8>> {
+    let mut body = vec![];
+
+    loop {
+        let mut size = String::new();
+        reader.read_line(&mut size).ok()?;
+        let size = size.trim_end().split(';').next()?;
+        let size = usize::from_str_radix(size, 16).ok()?;
+
+        let mut chunk = vec![0; size + 2];
+        reader.read_exact(&mut chunk).ok()?;
+
+        if size == 0 {
+            return Some(body);
+        }
+
+        body.extend_from_slice(&chunk[..size]);
+    }
+}
@@ -0,0 +1,157 @@
+#![cfg(feature = "config")]
+
+extern crate owo;
+#[cfg(feature = "serde-items")]
+extern crate serde_json;
+
+use owo::{Config, Error};
+use std::env;
+use std::time::Duration;
+
+#[test]
+fn test_from_toml() {
+    let config = Config::from_toml(
+        "key = \"my key\"\n\
+         base_url = \"https://api.example.com\"\n\
+         domain = \"example.com\"\n\
+         shorten_path = \"/api/v1/shorten\"\n\
+         upload_path = \"/api/v1/upload\"\n\
+         timeout = 30",
+    ).expect("config err");
+
+    assert_eq!(config.key, "my key");
+    assert_eq!(config.base_url.as_ref().unwrap(), "https://api.example.com");
+    assert_eq!(config.domain.as_ref().unwrap(), "example.com");
+    assert_eq!(config.shorten_path.as_ref().unwrap(), "/api/v1/shorten");
+    assert_eq!(config.upload_path.as_ref().unwrap(), "/api/v1/upload");
+    assert_eq!(config.timeout, Some(Duration::from_secs(30)));
+}
+
+#[test]
+fn test_from_toml_defaults() {
+    let config = Config::from_toml("key = \"my key\"").expect("config err");
+
+    assert_eq!(config.key, "my key");
+    assert!(config.base_url.is_none());
+    assert!(config.domain.is_none());
+    assert!(config.shorten_path.is_none());
+    assert!(config.timeout.is_none());
+    assert!(config.upload_path.is_none());
+}
+
+#[test]
+fn test_from_toml_ignores_unknown_fields() {
+    let config = Config::from_toml("key = \"my key\"\ncolour = \"pink\"")
+        .expect("config err");
+
+    assert_eq!(config.key, "my key");
+}
+
+#[test]
+fn test_from_toml_missing_key() {
+    match Config::from_toml("timeout = 30") {
+        Err(Error::Config { field, .. }) => assert_eq!(field, "key"),
+        other => panic!("expected config error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_from_toml_key_var() {
+    env::set_var("OWO_TEST_CONFIG_KEY", "my key");
+
+    let config = Config::from_toml("key_var = \"OWO_TEST_CONFIG_KEY\"")
+        .expect("config err");
+
+    assert!(config.key.is_empty());
+    assert_eq!(config.key_var.as_ref().unwrap(), "OWO_TEST_CONFIG_KEY");
+    assert_eq!(config.resolve_key().expect("key err"), "my key");
+
+    let mut config = Config::new("");
+    config.key_var = Some("OWO_TEST_CONFIG_MISSING".to_owned());
+
+    match config.resolve_key() {
+        Err(Error::Config { field, .. }) => {
+            assert_eq!(field, "OWO_TEST_CONFIG_MISSING");
+        },
+        other => panic!("expected config error: {:?}", other),
+    }
+}
+
+#[cfg(feature = "serde-items")]
+#[test]
+fn test_serde_round_trip() {
+    let mut config = Config::new("my key");
+    config.base_url = Some("https://api.example.com".to_owned());
+    config.key_var = Some("OWO_KEY".to_owned());
+    config.timeout = Some(Duration::from_secs(30));
+
+    let json = serde_json::to_string(&config).expect("ser err");
+
+    assert!(!json.contains("my key"));
+    assert!(json.contains("\"timeout\":30"));
+
+    let parsed = serde_json::from_str::<Config>(&json).expect("de err");
+
+    assert!(parsed.key.is_empty());
+    assert_eq!(parsed.base_url, config.base_url);
+    assert_eq!(parsed.key_var, config.key_var);
+    assert_eq!(parsed.timeout, config.timeout);
+    assert!(parsed.domain.is_none());
+}
+
+#[test]
+fn test_from_toml_invalid_field() {
+    match Config::from_toml("key = \"my key\"\ntimeout = \"soon\"") {
+        Err(Error::Config { field, reason }) => {
+            assert_eq!(field, "timeout");
+            assert!(reason.contains("string"));
+        },
+        other => panic!("expected config error: {:?}", other),
+    }
+
+    match Config::from_toml("key = \"my key\"\ntimeout = -1") {
+        Err(Error::Config { field, .. }) => assert_eq!(field, "timeout"),
+        other => panic!("expected config error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_from_toml_invalid_syntax() {
+    match Config::from_toml("key = ") {
+        Err(Error::Toml(_)) => {},
+        other => panic!("expected toml error: {:?}", other),
+    }
+}
+
+#[cfg(feature = "reqwest")]
+#[test]
+fn test_client_from_config() {
+    use owo::OwoReqwestClient;
+
+    let client = OwoReqwestClient::from_config("tests/resources/owo.toml")
+        .expect("client err");
+
+    assert_eq!(client.key(), "my key");
+    assert_eq!(client.base_url(), "https://api.example.com");
+    assert_eq!(client.domain(), "example.com");
+}
+
+#[cfg(feature = "reqwest")]
+#[test]
+fn test_client_from_config_missing() {
+    use owo::OwoReqwestClient;
+    use std::io::ErrorKind as IoErrorKind;
+
+    let path = "tests/resources/missing.toml";
+    let error = OwoReqwestClient::from_config(path).err().expect("client ok");
+
+    assert!(error.to_string().contains(path));
+
+    match error {
+        Error::File { path: ref err_path, ref error } => {
+            assert_eq!(err_path.to_str(), Some(path));
+            assert_eq!(error.kind(), IoErrorKind::NotFound);
+        },
+        other => panic!("expected file error: {:?}", other),
+    }
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "keyring")]
+
+extern crate owo;
+
+use owo::{Error, credentials};
+
+// These tests require access to the operating system's credential store, so
+// are ignored by default.
+
+#[ignore]
+#[test]
+fn test_store_and_load_key() {
+    credentials::store_key("owo-rs-tests", "store", " key\n")
+        .expect("store err");
+
+    let key = credentials::load_key("owo-rs-tests", "store")
+        .expect("load err");
+    assert_eq!(key, "key");
+
+    credentials::delete_key("owo-rs-tests", "store").expect("delete err");
+}
+
+#[ignore]
+#[test]
+fn test_load_missing_key() {
+    match credentials::load_key("owo-rs-tests", "missing") {
+        Err(Error::KeyNotFound { account, service }) => {
+            assert_eq!(account, "missing");
+            assert_eq!(service, "owo-rs-tests");
+        },
+        other => panic!("expected key not found error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_store_empty_key() {
+    match credentials::store_key("owo-rs-tests", "empty", " ") {
+        Err(Error::InvalidKey) => {},
+        other => panic!("expected invalid key error: {:?}", other),
+    }
+}
@@ -0,0 +1,56 @@
+extern crate owo;
+#[cfg(feature = "serde-items")]
+extern crate serde_json;
+
+use owo::{domains, Error};
+
+#[test]
+fn test_known_domains() {
+    for domain in domains::KNOWN {
+        assert!(domains::is_known(domain));
+        assert_eq!(domains::validate(domain).unwrap(), *domain);
+    }
+
+    assert!(domains::KNOWN.contains(&owo::constants::DEFAULT_DOMAIN));
+}
+
+#[test]
+fn test_validate_normalizes() {
+    for domain in &[
+        "uwu.whats-th.is",
+        " UWU.whats-th.is ",
+        "uwu.whats-th.is/",
+        "https://uwu.whats-th.is/",
+        "HTTP://uwu.whats-th.is",
+    ] {
+        assert_eq!(domains::validate(domain).unwrap(), "uwu.whats-th.is");
+    }
+}
+
+#[test]
+fn test_validate_unknown() {
+    for domain in &["", "example.com", "whats-th.is", "ftp://uwu.whats-th.is"] {
+        match domains::validate(domain) {
+            Err(Error::UnknownDomain(ref unknown)) => {
+                assert_eq!(unknown, *domain);
+            },
+            other => panic!("expected unknown domain error: {:?}", other),
+        }
+    }
+}
+
+#[cfg(feature = "serde-items")]
+#[test]
+fn test_full_url() {
+    use owo::model::UploadedFile;
+
+    let file: UploadedFile = serde_json::from_str(
+        r#"{"hash": "a", "name": null, "size": 5, "url": "a.png"}"#,
+    ).expect("err parsing file");
+
+    assert_eq!(
+        domains::full_url(&file, "https://uwu.whats-th.is").unwrap(),
+        "https://uwu.whats-th.is/a.png",
+    );
+    assert!(domains::full_url(&file, "example.com").is_err());
+}
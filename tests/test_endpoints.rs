@@ -0,0 +1,81 @@
+extern crate owo;
+
+use owo::endpoints;
+use std::time::Duration;
+
+#[test]
+fn test_upload_trims_key() {
+    assert_eq!(
+        endpoints::upload("  key\n"),
+        "https://api.awau.moe/upload/pomf?key=key",
+    );
+}
+
+#[test]
+fn test_upload_encodes_key() {
+    assert_eq!(
+        endpoints::upload("a&b=c d/é"),
+        "https://api.awau.moe/upload/pomf?key=a%26b%3Dc%20d%2F%C3%A9",
+    );
+}
+
+#[test]
+fn test_upload_with_expiry() {
+    assert_eq!(
+        endpoints::upload_with_expiry("key", Duration::from_secs(3600)),
+        "https://api.awau.moe/upload/pomf?key=key&expiry=3600",
+    );
+}
+
+#[test]
+fn test_shorten_encodes_key() {
+    assert_eq!(
+        endpoints::shorten("k&y\r\n", "https://google.com"),
+        "https://api.awau.moe/shorten/polr?action=shorten\
+         &url=https://google.com&key=k%26y",
+    );
+}
+
+#[test]
+fn test_shorten_json() {
+    assert_eq!(
+        endpoints::shorten_json("key", "https://google.com"),
+        "https://api.awau.moe/shorten/polr?action=shorten\
+         &url=https://google.com&key=key&response_type=json",
+    );
+}
+
+#[test]
+fn test_shorten_with_description() {
+    assert_eq!(
+        endpoints::shorten_with_description(
+            "key",
+            "https://google.com",
+            "my file.png",
+        ),
+        "https://api.awau.moe/shorten/polr?action=shorten\
+         &url=https://google.com&key=key&description=my%20file.png",
+    );
+}
+
+#[test]
+fn test_parse_short_url() {
+    let parsed = |host: &str, slug: &str| {
+        Some((host.to_owned(), slug.to_owned()))
+    };
+
+    for &(url, ref expected) in &[
+        ("https://awau.moe/a1b2c3", parsed("awau.moe", "a1b2c3")),
+        ("https://awau.moe/a1b2c3/", parsed("awau.moe", "a1b2c3")),
+        ("https://awau.moe/a1b2c3?ref=x", parsed("awau.moe", "a1b2c3")),
+        ("https://awau.moe/a1b2c3#top", parsed("awau.moe", "a1b2c3")),
+        (" http://awau.moe:8080/s/a1b2c3\n", parsed("awau.moe:8080", "a1b2c3")),
+        ("https://awau.moe/", None),
+        ("https://awau.moe", None),
+        ("https:///a1b2c3", None),
+        ("awau.moe/a1b2c3", None),
+        ("", None),
+    ] {
+        assert_eq!(&endpoints::parse_short_url(url), expected, "{:?}", url);
+    }
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "reqwest")]
+
+extern crate owo;
+
+mod common;
+
+use common::MockServer;
+use owo::{Error, OwoReqwestClient, OwoReqwestClientBuilder};
+use std::env;
+
+// The environment is shared between threads, so all of the checks involving
+// environment variables are performed in a single test.
+#[test]
+fn test_api_url_env() {
+    let server = MockServer::start("200 OK", "https://awau.moe/abc");
+
+    env::set_var("OWO_API_URL", &server.url);
+
+    let client = OwoReqwestClient::new("key").expect("client err");
+    assert_eq!(client.base_url(), server.url);
+
+    let short = client.shorten_url("https://google.com").expect("shorten err");
+    assert_eq!(short, "https://awau.moe/abc");
+
+    let request = server.request();
+    assert!(request.line.starts_with("GET /shorten/polr?action=shorten"));
+
+    // An explicitly configured base URL takes precedence.
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url("https://api.example.com")
+        .build()
+        .expect("client err");
+    assert_eq!(client.base_url(), "https://api.example.com");
+
+    // The key is also read from the environment by `from_env`.
+    env::set_var("OWO_KEY", " env key ");
+    let client = OwoReqwestClient::from_env().expect("client err");
+    assert_eq!(client.key(), "env key");
+    assert_eq!(client.base_url(), server.url);
+
+    for invalid in &["api.example.com", "ftp://example.com", "https://"] {
+        env::set_var("OWO_API_URL", invalid);
+
+        match OwoReqwestClient::new("key") {
+            Err(Error::Config { field, .. }) => {
+                assert_eq!(field, "OWO_API_URL");
+            },
+            Err(why) => panic!("expected config error: {:?}", why),
+            Ok(_) => panic!("expected config error for {}", invalid),
+        }
+    }
+
+    env::remove_var("OWO_API_URL");
+    env::remove_var("OWO_KEY");
+
+    let client = OwoReqwestClient::new("key").expect("client err");
+    assert_eq!(client.base_url(), "https://api.awau.moe");
+
+    match OwoReqwestClient::from_env() {
+        Err(Error::Config { field, .. }) => assert_eq!(field, "OWO_KEY"),
+        Err(why) => panic!("expected config error: {:?}", why),
+        Ok(_) => panic!("expected config error"),
+    }
+}
@@ -0,0 +1,546 @@
+#[cfg(feature = "hyper")]
+extern crate hyper;
+extern crate owo;
+#[cfg(feature = "serde-items")]
+extern crate serde_json;
+
+use owo::{ApiErrorCode, Error, ErrorKind, NetworkErrorKind, Trace};
+use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[test]
+fn test_api_error_code_from() {
+    assert_eq!(ApiErrorCode::from(413), ApiErrorCode::FileTooLarge);
+    assert_eq!(ApiErrorCode::from(999), ApiErrorCode::Unknown(999));
+    assert_eq!(ApiErrorCode::from(403).code(), 403);
+    assert_eq!(ApiErrorCode::Unknown(999).code(), 999);
+}
+
+#[test]
+fn test_api_error_display() {
+    let known = Error::Api {
+        code: 413,
+        description: "too big".to_owned(),
+        kind: ApiErrorCode::from(413),
+    };
+    assert_eq!(
+        known.to_string(),
+        "Error from the service (413, file too large): too big",
+    );
+
+    let unknown = Error::Api {
+        code: 999,
+        description: "what".to_owned(),
+        kind: ApiErrorCode::from(999),
+    };
+    assert_eq!(unknown.to_string(), "Error from the service (999): what");
+}
+
+#[test]
+fn test_error_source() {
+    use std::error::Error as StdError;
+
+    assert!(Error::InvalidKey.source().is_none());
+
+    let error = Error::from(IoError::new(IoErrorKind::Other, "oh no"));
+    assert_eq!(error.source().unwrap().to_string(), "oh no");
+
+    let batch = Error::Batch(Arc::new(Error::InvalidKey));
+    assert!(batch.source().unwrap().to_string().starts_with("Invalid key"));
+}
+
+#[test]
+fn test_io_error_conversion() {
+    use std::fs::File;
+
+    fn open(path: &str) -> owo::Result<File> {
+        Ok(File::open(path)?)
+    }
+
+    match open("tests/resources/missing.png") {
+        Err(Error::Io(ref inner, ..)) => {
+            assert_eq!(inner.kind(), IoErrorKind::NotFound);
+        },
+        other => panic!("expected io error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_io_error_shape() {
+    // The variant has the same shape with or without the `backtrace`
+    // feature.
+    let inner = IoError::new(IoErrorKind::Other, "oh no");
+    let error = Error::Io(inner, Trace::default());
+
+    match error {
+        Error::Io(ref inner, _) => assert_eq!(inner.to_string(), "oh no"),
+        ref other => panic!("expected io error: {:?}", other),
+    }
+}
+
+// The environment is shared between threads, so all of the checks involving
+// environment variables are performed in a single test.
+#[cfg(feature = "backtrace")]
+#[test]
+fn test_backtrace() {
+    use std::env;
+
+    env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    let traced = Error::from(IoError::new(IoErrorKind::Other, "oh no"));
+    assert!(traced.backtrace().is_some());
+    assert_eq!(traced.to_string(), "oh no");
+    assert!(format!("{:#}", traced).starts_with("oh no\n\n"));
+
+    env::set_var("RUST_LIB_BACKTRACE", "0");
+
+    let error = Error::from(IoError::new(IoErrorKind::Other, "oh no"));
+    assert!(error.backtrace().is_none());
+    assert_eq!(format!("{:#}", error), "oh no");
+    assert!(format!("{:?}", traced).len() > format!("{:?}", error).len());
+    assert!(Error::InvalidKey.backtrace().is_none());
+
+    env::remove_var("RUST_LIB_BACKTRACE");
+}
+
+#[test]
+fn test_file_error() {
+    use std::error::Error as StdError;
+
+    let error = Error::File {
+        path: PathBuf::from("uploads/my-file.png"),
+        error: IoError::new(IoErrorKind::NotFound, "oh no"),
+    };
+
+    assert_eq!(
+        error.to_string(),
+        "Error accessing `uploads/my-file.png`: oh no",
+    );
+    assert_eq!(error.source().unwrap().to_string(), "oh no");
+    assert!(!error.is_network());
+}
+
+#[test]
+fn test_error_boxed_send_sync() {
+    use std::error::Error as StdError;
+    use std::thread;
+
+    let boxed: Box<dyn StdError + Send + Sync> = Box::new(Error::InvalidKey);
+    let message = thread::spawn(move || boxed.to_string())
+        .join()
+        .expect("thread panicked");
+
+    assert!(message.starts_with("Invalid key"));
+}
+
+#[test]
+fn test_error_predicates() {
+    use std::time::Duration;
+
+    assert!(Error::InvalidKey.is_invalid_key());
+    assert!(!Error::InvalidKey.is_rate_limited());
+
+    let rate_limited = Error::RateLimited {
+        retry_after: Some(Duration::from_secs(1)),
+    };
+    assert!(rate_limited.is_rate_limited());
+    assert!(!rate_limited.is_network());
+
+    let timeout = Error::Timeout {
+        elapsed: Duration::from_millis(1500),
+    };
+    assert!(timeout.is_timeout());
+    assert!(timeout.is_network());
+    assert_eq!(timeout.to_string(), "Request timed out after 1.500 seconds");
+
+    let too_many = Error::TooManyFiles { provided: 4, limit: 3 };
+    assert!(too_many.is_too_many_files());
+    assert!(!too_many.is_invalid_key());
+
+    let io = Error::from(IoError::new(IoErrorKind::ConnectionReset, "reset"));
+    assert!(io.is_network());
+
+    let batch = Error::Batch(Arc::new(rate_limited));
+    assert!(batch.is_rate_limited());
+    assert_eq!(batch.status(), Some(429));
+    assert!(!batch.is_invalid_key());
+}
+
+#[test]
+fn test_error_status() {
+    let status = Error::Status {
+        status: 502,
+        body: String::new(),
+    };
+    assert_eq!(status.status(), Some(502));
+
+    let unavailable = Error::ServiceUnavailable {
+        status: 522,
+        body: String::new(),
+    };
+    assert_eq!(unavailable.status(), Some(522));
+
+    assert_eq!(Error::RateLimited { retry_after: None }.status(), Some(429));
+    assert_eq!(Error::PayloadTooLarge { size: None }.status(), Some(413));
+    assert_eq!(Error::InvalidKey.status(), None);
+}
+
+#[test]
+fn test_error_kind() {
+    use std::time::Duration;
+
+    let errors = vec![
+        (
+            Error::Api {
+                code: 400,
+                description: String::new(),
+                kind: ApiErrorCode::BadRequest,
+            },
+            ErrorKind::Api,
+        ),
+        (
+            Error::ApiFailure { body: String::new() },
+            ErrorKind::ApiFailure,
+        ),
+        (Error::Batch(Arc::new(Error::InvalidKey)), ErrorKind::Batch),
+        (
+            Error::Config {
+                field: "key".to_owned(),
+                reason: "missing".to_owned(),
+            },
+            ErrorKind::Config,
+        ),
+        (
+            Error::File {
+                path: PathBuf::from("my-file.png"),
+                error: IoError::new(IoErrorKind::NotFound, "oh no"),
+            },
+            ErrorKind::File,
+        ),
+        (
+            Error::FileTooLarge { size: 2, limit: 1 },
+            ErrorKind::FileTooLarge,
+        ),
+        (
+            Error::HtmlResponse(String::new()),
+            ErrorKind::HtmlResponse,
+        ),
+        (
+            Error::InvalidBoundary(String::new()),
+            ErrorKind::InvalidBoundary,
+        ),
+        (Error::InvalidKey, ErrorKind::InvalidKey),
+        (
+            Error::from(IoError::new(IoErrorKind::Other, "oh no")),
+            ErrorKind::Io,
+        ),
+        (Error::NoFilesReturned, ErrorKind::NoFilesReturned),
+        (
+            Error::PayloadTooLarge { size: None },
+            ErrorKind::PayloadTooLarge,
+        ),
+        (
+            Error::RateLimited { retry_after: Some(Duration::from_secs(1)) },
+            ErrorKind::RateLimited,
+        ),
+        (
+            Error::ResponseTooLarge { limit: 1024 },
+            ErrorKind::ResponseTooLarge,
+        ),
+        (
+            Error::ServiceUnavailable { status: 503, body: String::new() },
+            ErrorKind::ServiceUnavailable,
+        ),
+        (
+            Error::Status { status: 502, body: String::new() },
+            ErrorKind::Status,
+        ),
+        (
+            Error::Timeout { elapsed: Duration::from_secs(1) },
+            ErrorKind::Timeout,
+        ),
+        (
+            Error::TooManyFiles { provided: 4, limit: 3 },
+            ErrorKind::TooManyFiles,
+        ),
+        (
+            Error::UnexpectedResponse(String::new()),
+            ErrorKind::UnexpectedResponse,
+        ),
+        (
+            Error::UnknownDomain("example.com".to_owned()),
+            ErrorKind::UnknownDomain,
+        ),
+        (
+            Error::UrlTooLong { length: 2, limit: 1 },
+            ErrorKind::UrlTooLong,
+        ),
+    ];
+
+    for (error, kind) in errors {
+        assert_eq!(error.kind(), kind);
+    }
+
+    assert_ne!(ErrorKind::Api, ErrorKind::Status);
+}
+
+#[test]
+fn test_user_message() {
+    use std::time::Duration;
+
+    let api = |kind: ApiErrorCode| Error::Api {
+        code: kind.code(),
+        description: "technical details".to_owned(),
+        kind,
+    };
+    let io = |kind: IoErrorKind| Error::from(IoError::new(kind, "oh no"));
+    let status = |status: u16| Error::Status {
+        status,
+        body: "technical details".to_owned(),
+    };
+
+    let messages = vec![
+        (
+            api(ApiErrorCode::BadRequest),
+            "The service couldn't handle the request.",
+        ),
+        (
+            api(ApiErrorCode::BannedKey),
+            "Your API key has been banned from the service.",
+        ),
+        (
+            api(ApiErrorCode::DisallowedType),
+            "The service does not allow this type of file.",
+        ),
+        (
+            api(ApiErrorCode::FileTooLarge),
+            "This file is larger than the service allows.",
+        ),
+        (
+            api(ApiErrorCode::InternalError),
+            "The service ran into a problem. Try again later.",
+        ),
+        (
+            api(ApiErrorCode::InvalidKey),
+            "Your API key was rejected. Check that it's correct.",
+        ),
+        (
+            api(ApiErrorCode::RateLimited),
+            "Too many requests were made. Wait a moment and try again.",
+        ),
+        (
+            api(ApiErrorCode::Unknown(999)),
+            "The service couldn't handle the request.",
+        ),
+        (
+            Error::ApiFailure { body: String::new() },
+            "The service didn't confirm the upload. Try again later.",
+        ),
+        (
+            Error::Batch(Arc::new(Error::InvalidKey)),
+            "Your API key was rejected. Check that it's correct.",
+        ),
+        (
+            Error::Config {
+                field: "timeout".to_owned(),
+                reason: "expected a number of seconds".to_owned(),
+            },
+            "The setting `timeout` in your configuration is invalid.",
+        ),
+        (
+            Error::FileTooLarge {
+                size: 200 * 1024 * 1024,
+                limit: 100 * 1024 * 1024,
+            },
+            "This file is larger than the 100 MB limit.",
+        ),
+        (
+            Error::FileTooLarge { size: 2048, limit: 1536 },
+            "This file is larger than the 1.5 KB limit.",
+        ),
+        (
+            Error::HtmlResponse("<html></html>".to_owned()),
+            "The service sent a web page instead of a response. It may be \
+             down, or asking to check that you aren't a robot.",
+        ),
+        (
+            Error::InvalidBoundary(String::new()),
+            "The upload couldn't be prepared due to a configuration problem.",
+        ),
+        (
+            Error::InvalidKey,
+            "Your API key was rejected. Check that it's correct.",
+        ),
+        (
+            Error::File {
+                path: PathBuf::from("my-file.png"),
+                error: IoError::new(IoErrorKind::NotFound, "oh no"),
+            },
+            "The file `my-file.png` couldn't be found.",
+        ),
+        (
+            Error::File {
+                path: PathBuf::from("my-file.png"),
+                error: IoError::new(IoErrorKind::InvalidData, "oh no"),
+            },
+            "The file `my-file.png` couldn't be read.",
+        ),
+        (io(IoErrorKind::NotFound), "The file couldn't be found."),
+        (
+            io(IoErrorKind::PermissionDenied),
+            "You don't have permission to read the file.",
+        ),
+        (
+            io(IoErrorKind::ConnectionRefused),
+            "Couldn't connect to the service. Check your internet connection.",
+        ),
+        (
+            io(IoErrorKind::ConnectionReset),
+            "The connection to the service was interrupted. Try again.",
+        ),
+        (
+            io(IoErrorKind::Other),
+            "Something went wrong while talking to the service. Try again.",
+        ),
+        (
+            Error::NoFilesReturned,
+            "The service didn't confirm the upload. Try again later.",
+        ),
+        (
+            Error::PayloadTooLarge { size: None },
+            "The files are larger than the service allows.",
+        ),
+        (
+            Error::RateLimited {
+                retry_after: Some(Duration::from_secs(30)),
+            },
+            "Too many requests were made. Try again in 30 seconds.",
+        ),
+        (
+            Error::RateLimited { retry_after: None },
+            "Too many requests were made. Wait a moment and try again.",
+        ),
+        (
+            Error::ResponseTooLarge { limit: 1024 },
+            "The service sent a response which was too large to read.",
+        ),
+        (
+            Error::ServiceUnavailable {
+                status: 502,
+                body: String::new(),
+            },
+            "The service appears to be down. Try again later.",
+        ),
+        (
+            status(401),
+            "Your API key was rejected. Check that it's correct.",
+        ),
+        (
+            status(503),
+            "The service appears to be down. Try again later.",
+        ),
+        (status(404), "The service couldn't handle the request."),
+        (
+            Error::Timeout {
+                elapsed: Duration::from_secs(30),
+            },
+            "The service took too long to respond. Try again later.",
+        ),
+        (
+            Error::TooManyFiles { provided: 4, limit: 3 },
+            "Too many files. Only 3 can be uploaded at once.",
+        ),
+        (
+            Error::UnexpectedResponse(String::new()),
+            "The service sent a response which couldn't be understood.",
+        ),
+        (
+            Error::UnknownDomain("example.com".to_owned()),
+            "`example.com` isn't one of the service's domains. Check your \
+             settings.",
+        ),
+        (
+            Error::UrlTooLong { length: 2, limit: 1 },
+            "This link is too long to shorten.",
+        ),
+    ];
+
+    for (error, message) in messages {
+        assert_eq!(error.user_message(), message, "{:?}", error);
+    }
+}
+
+#[cfg(feature = "reqwest")]
+#[test]
+fn test_error_kind_reqwest() {
+    let json = serde_json::from_str::<u8>("nope").unwrap_err();
+    assert_eq!(Error::from(json).kind(), ErrorKind::Json);
+
+    let json = serde_json::from_str::<u8>("nope").unwrap_err();
+    let invalid = Error::InvalidJson {
+        body: "nope".to_owned(),
+        error: json,
+    };
+    assert_eq!(invalid.kind(), ErrorKind::InvalidJson);
+
+    assert_eq!(Error::NoKeyConfigured.kind(), ErrorKind::NoKeyConfigured);
+}
+
+#[cfg(feature = "hyper")]
+#[test]
+fn test_error_kind_hyper() {
+    use std::error::Error as StdError;
+
+    let error = Error::from(hyper::Error::Incomplete);
+
+    assert_eq!(error.kind(), ErrorKind::Hyper);
+    assert_eq!(error.network_kind(), Some(NetworkErrorKind::Reset));
+    assert!(error.source().is_some());
+}
+
+#[cfg(feature = "config")]
+#[test]
+fn test_error_kind_config() {
+    match owo::Config::from_toml("key = ") {
+        Err(error) => assert_eq!(error.kind(), ErrorKind::Toml),
+        Ok(_) => panic!("expected toml error"),
+    }
+}
+
+#[test]
+fn test_network_kind() {
+    use std::time::Duration;
+
+    let kinds = [
+        (IoErrorKind::ConnectionRefused, NetworkErrorKind::Connect),
+        (IoErrorKind::ConnectionReset, NetworkErrorKind::Reset),
+        (IoErrorKind::BrokenPipe, NetworkErrorKind::Reset),
+        (IoErrorKind::TimedOut, NetworkErrorKind::Timeout),
+        (IoErrorKind::WouldBlock, NetworkErrorKind::Timeout),
+        (IoErrorKind::Other, NetworkErrorKind::Other),
+    ];
+
+    for &(io, kind) in &kinds {
+        let error = Error::from(IoError::new(io, "oh no"));
+
+        assert_eq!(error.network_kind(), Some(kind));
+        assert!(error.is_network());
+    }
+
+    // Failures to resolve a host name are only reported with a message, which
+    // isn't relied on.
+    let dns = IoError::new(
+        IoErrorKind::Other,
+        "failed to lookup address information: Name or service not known",
+    );
+    assert_eq!(NetworkErrorKind::from_io(&dns), NetworkErrorKind::Other);
+
+    let timeout = Error::Timeout {
+        elapsed: Duration::from_secs(1),
+    };
+    assert_eq!(timeout.network_kind(), Some(NetworkErrorKind::Timeout));
+
+    assert_eq!(Error::InvalidKey.network_kind(), None);
+    assert!(!Error::InvalidKey.is_network());
+}
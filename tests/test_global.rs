@@ -0,0 +1,41 @@
+#![cfg(feature = "reqwest")]
+
+extern crate owo;
+
+mod common;
+
+use common::MockServer;
+use owo::Error;
+use std::env;
+
+// The global client is shared between threads, so all of the checks involving
+// it are performed in a single test.
+#[test]
+fn test_global_client() {
+    match owo::shorten_url("https://google.com") {
+        Err(Error::NoKeyConfigured) => {},
+        other => panic!("expected no key configured error: {:?}", other),
+    }
+
+    match owo::set_key(" ") {
+        Err(Error::InvalidKey) => {},
+        other => panic!("expected invalid key error: {:?}", other),
+    }
+
+    let server = MockServer::start("200 OK", "https://awau.moe/abc");
+    env::set_var("OWO_API_URL", &server.url);
+
+    owo::set_key("first").expect("key err");
+    let short = owo::shorten_url("https://google.com").expect("shorten err");
+    assert_eq!(short, "https://awau.moe/abc");
+    assert!(server.request().line.ends_with("&key=first HTTP/1.1"));
+
+    owo::set_key("second").expect("key err");
+    owo::shorten_url("https://google.com").expect("shorten err");
+    assert!(server.request().line.ends_with("&key=second HTTP/1.1"));
+
+    // An invalid key keeps the previous one.
+    assert!(owo::set_key("").is_err());
+    owo::shorten_url("https://google.com").expect("shorten err");
+    assert!(server.request().line.ends_with("&key=second HTTP/1.1"));
+}
@@ -0,0 +1,106 @@
+#![cfg(all(feature = "hyper", feature = "serde_json"))]
+
+extern crate futures;
+extern crate owo;
+extern crate tokio_core;
+
+mod common;
+
+use common::MockServer;
+use futures::Stream;
+use owo::model::FileUploadResponse;
+use owo::{Error, NetworkErrorKind, OwoHyperClientBuilder};
+use std::time::Duration;
+use tokio_core::reactor::Core;
+
+/// Uploads a file to the URL via the hyper client, returning the result of
+/// the upload.
+fn upload(url: &str) -> owo::Result<FileUploadResponse> {
+    let mut core = Core::new().expect("core err");
+    let client = OwoHyperClientBuilder::new("key")
+        .base_url(url)
+        .build(&core.handle())
+        .expect("client err");
+
+    let uploads = client.upload_files_stream(vec![vec![1, 2, 3]], 1)
+        .expect("stream err")
+        .collect();
+
+    core.run(uploads).expect("run err").remove(0)
+}
+
+#[test]
+fn test_clones_share_configuration() {
+    let core = Core::new().expect("core err");
+    let client = OwoHyperClientBuilder::new("first")
+        .base_url("https://owo.example.com")
+        .boundary("fixed-boundary")
+        .build(&core.handle())
+        .expect("client err");
+    let clone = client.clone();
+    let other = client.clone_with_key("second").expect("key err");
+
+    assert_eq!(clone.key(), "first");
+    assert_eq!(other.key(), "second");
+
+    for each in &[clone, other] {
+        assert_eq!(each.boundary(), Some("fixed-boundary"));
+        assert_eq!(each.base_url(), client.base_url());
+    }
+}
+
+#[test]
+fn test_upload() {
+    let server = MockServer::start("200 OK", r#"{
+        "success": true,
+        "files": [{"hash": "a", "name": null, "size": 3, "url": "a.png"}]
+    }"#);
+
+    let response = upload(&server.url).expect("upload err");
+    assert_eq!(response.files[0].url, "a.png");
+}
+
+#[test]
+fn test_rejected_key() {
+    let server = MockServer::start("401 Unauthorized", r#"{"success":false}"#);
+
+    match upload(&server.url) {
+        Err(Error::InvalidKey) => {},
+        other => panic!("expected invalid key error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_rate_limited() {
+    let server = MockServer::start_with_headers(
+        "429 Too Many Requests",
+        &["Retry-After: 2"],
+        "slow down",
+    );
+
+    match upload(&server.url) {
+        Err(Error::RateLimited { retry_after }) => {
+            assert_eq!(retry_after, Some(Duration::from_secs(2)));
+        },
+        other => panic!("expected rate limited error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_service_unavailable() {
+    let server = MockServer::start(
+        "502 Bad Gateway",
+        "<!DOCTYPE html><html>Bad gateway</html>",
+    );
+
+    match upload(&server.url) {
+        Err(Error::ServiceUnavailable { status: 502, .. }) => {},
+        other => panic!("expected service unavailable error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_network_kind_tls() {
+    let error = upload(&common::plaintext_url()).expect_err("handshake");
+    assert_eq!(error.network_kind(), Some(NetworkErrorKind::Tls));
+}
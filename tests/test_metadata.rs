@@ -0,0 +1,45 @@
+#![cfg(feature = "exif-strip")]
+
+extern crate owo;
+
+use owo::metadata;
+use std::fs::File;
+use std::io::Read;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+fn read(relative_path: &str) -> Vec<u8> {
+    let mut buffer = vec![];
+    let mut file = File::open(relative_path).expect("err opening file");
+    file.read_to_end(&mut buffer).expect("err reading file");
+
+    buffer
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+#[test]
+fn test_strip_png() {
+    let image = read("tests/resources/cat.png");
+    assert!(contains(&image, b"iTXt"));
+    assert!(contains(&image, b"tIME"));
+
+    let stripped = metadata::strip(image.clone());
+
+    assert!(stripped.starts_with(PNG_SIGNATURE));
+    assert!(stripped.len() < image.len());
+    assert!(contains(&stripped, b"IDAT"));
+    assert!(!contains(&stripped, b"iTXt"));
+    assert!(!contains(&stripped, b"tIME"));
+
+    assert_eq!(metadata::strip(stripped.clone()), stripped);
+}
+
+#[test]
+fn test_strip_unrecognized() {
+    let bytes = b"not an image".to_vec();
+
+    assert_eq!(metadata::strip(bytes.clone()), bytes);
+}
@@ -0,0 +1,575 @@
+#![cfg(feature = "serde-items")]
+
+extern crate owo;
+extern crate serde_json;
+
+use owo::model::{FileUploadResponse, Progress, ShortenResponse};
+use owo::{ApiErrorCode, Error};
+use std::collections::HashSet;
+
+const UPLOAD_RESPONSE: &str = r#"{
+    "success": true,
+    "files": [
+        {"hash": "a", "name": null, "size": 1, "url": "a.png",
+         "delete_url": "https://owo.whats-th.is/delete/a?token=t"},
+        {"hash": "b", "name": "b.png", "size": 2, "url": "b.png",
+         "content_type": "image/png"}
+    ]
+}"#;
+
+fn upload_response() -> FileUploadResponse {
+    serde_json::from_str(UPLOAD_RESPONSE).expect("err parsing response")
+}
+
+#[test]
+fn test_upload_response_iter() {
+    let res = upload_response();
+
+    let urls = (&res).into_iter().map(|file| &file.url[..]).collect::<Vec<_>>();
+    assert_eq!(urls, vec!["a.png", "b.png"]);
+
+    let mut count = 0;
+
+    for file in &res {
+        assert!(file.size > 0);
+        count += 1;
+    }
+
+    assert_eq!(count, res.len());
+
+    let hashes = res.into_iter().map(|file| file.hash).collect::<Vec<_>>();
+    assert_eq!(hashes, vec!["a", "b"]);
+
+    let empty = r#"{"success": false, "files": []}"#;
+    let res = serde_json::from_str::<FileUploadResponse>(empty)
+        .expect("err parsing response");
+    assert!(res.is_empty());
+    assert_eq!(res.into_iter().count(), 0);
+}
+
+#[test]
+fn test_upload_response_index() {
+    let res = upload_response();
+
+    assert_eq!(res[0].hash, "a");
+    assert_eq!(res[1].name, Some("b.png".to_owned()));
+}
+
+#[test]
+fn test_uploaded_file_full_url() {
+    let res = upload_response();
+
+    assert_eq!(res[0].full_url(), "https://owo.whats-th.is/a.png");
+}
+
+#[test]
+fn test_uploaded_file_full_url_on() {
+    let mut file = upload_response()[0].clone();
+    let expected = "https://i.am-a.furry/a.png";
+
+    assert_eq!(file.full_url_on("i.am-a.furry"), expected);
+    assert_eq!(file.full_url_on("i.am-a.furry/"), expected);
+    assert_eq!(file.full_url_on("https://i.am-a.furry/"), expected);
+    assert_eq!(
+        file.full_url_on("http://localhost:8080"),
+        "http://localhost:8080/a.png",
+    );
+
+    file.url = "/a.png".to_owned();
+    assert_eq!(file.full_url_on("i.am-a.furry/"), expected);
+}
+
+#[test]
+fn test_uploaded_file_id_and_extension() {
+    let mut file = upload_response()[0].clone();
+
+    for &(url, id, extension) in &[
+        ("a1b2c3.png", "a1b2c3", Some("png")),
+        ("a1b2c3", "a1b2c3", None),
+        ("a1b2c3.", "a1b2c3", None),
+        ("a1b2c3.tar.gz", "a1b2c3", Some("tar.gz")),
+        ("a1b2c3.png?download=1", "a1b2c3", Some("png")),
+        ("a1b2c3.png#top", "a1b2c3", Some("png")),
+        ("/a1b2c3.png", "a1b2c3", Some("png")),
+        ("https://pomf.example.com/a1b2c3.png", "a1b2c3", Some("png")),
+        ("", "", None),
+    ] {
+        file.url = url.to_owned();
+
+        assert_eq!(file.id(), id, "id of {:?}", url);
+        assert_eq!(file.extension(), extension, "extension of {:?}", url);
+    }
+}
+
+#[test]
+fn test_uploaded_file_link_formats() {
+    let mut file = upload_response()[0].clone();
+    file.content_type = None;
+    file.url = "a1b2c3.png".to_owned();
+    file.name = Some("[cat] \"pic\" <ñ>.png".to_owned());
+
+    assert!(file.is_image());
+    assert_eq!(
+        file.as_markdown("owo.whats-th.is"),
+        "![\\[cat\\] \"pic\" \\<ñ\\>.png](https://owo.whats-th.is/a1b2c3.png)",
+    );
+    assert_eq!(
+        file.as_html("owo.whats-th.is"),
+        "<img src=\"https://owo.whats-th.is/a1b2c3.png\" \
+         alt=\"[cat] &quot;pic&quot; &lt;ñ&gt;.png\">",
+    );
+    assert_eq!(
+        file.as_bbcode("owo.whats-th.is"),
+        "[img]https://owo.whats-th.is/a1b2c3.png[/img]",
+    );
+
+    file.url = "a1b2c3.zip".to_owned();
+    file.name = Some("it's [v2] ünïcödé.zip".to_owned());
+
+    assert!(!file.is_image());
+    assert_eq!(
+        file.as_markdown("owo.whats-th.is"),
+        "[it's \\[v2\\] ünïcödé.zip](https://owo.whats-th.is/a1b2c3.zip)",
+    );
+    assert_eq!(
+        file.as_html("owo.whats-th.is"),
+        "<a href=\"https://owo.whats-th.is/a1b2c3.zip\">\
+         it&#39;s [v2] ünïcödé.zip</a>",
+    );
+    assert_eq!(
+        file.as_bbcode("owo.whats-th.is"),
+        "[url=https://owo.whats-th.is/a1b2c3.zip]\
+         it's (v2) ünïcödé.zip[/url]",
+    );
+
+    file.name = None;
+    assert_eq!(
+        file.as_markdown("owo.whats-th.is"),
+        "[a1b2c3](https://owo.whats-th.is/a1b2c3.zip)",
+    );
+}
+
+#[test]
+fn test_uploaded_file_size_human() {
+    let mut file = upload_response()[0].clone();
+
+    for &(size, expected) in &[
+        (0, "0 B"),
+        (1, "1 B"),
+        (1023, "1023 B"),
+        (1024, "1.0 KiB"),
+        (1536, "1.5 KiB"),
+        (1024 * 1024 - 1, "1.0 MiB"),
+        (3_487_561, "3.3 MiB"),
+        (5 * 1024 * 1024 * 1024 / 2, "2.5 GiB"),
+        (80 * 1024 * 1024 * 1024, "80.0 GiB"),
+        (u64::max_value(), "16.0 EiB"),
+    ] {
+        file.size = size;
+
+        assert_eq!(file.size_human(), expected, "size of {}", size);
+    }
+}
+
+#[test]
+fn test_upload_response_accessors() {
+    let res = upload_response();
+
+    assert_eq!(res.files().len(), 2);
+    assert_eq!(res.len(), 2);
+    assert!(!res.is_empty());
+    assert_eq!(res.first().map(|file| &file.hash[..]), Some("a"));
+
+    let hashes = upload_response()
+        .into_iter()
+        .map(|file| file.hash)
+        .collect::<Vec<_>>();
+    assert_eq!(hashes, vec!["a", "b"]);
+
+    assert_eq!(upload_response().into_files().len(), 2);
+    let first = upload_response().into_first().expect("no first file");
+    assert_eq!(first.hash, "a");
+}
+
+const MIXED_RESPONSE: &str = r#"{
+    "success": true,
+    "files": [
+        {"hash": "a", "name": "a.png", "size": 1, "url": "a.png"},
+        {"name": "b.exe", "error": true, "errorcode": 415,
+         "description": "file type not allowed"}
+    ]
+}"#;
+
+#[test]
+fn test_upload_response_into_single() {
+    let file = upload_response().into_single().expect("err taking file");
+    assert_eq!(file.hash, "a");
+
+    let empty = r#"{"success": true, "files": []}"#;
+    match serde_json::from_str::<FileUploadResponse>(empty).unwrap()
+        .into_single() {
+        Err(Error::NoFilesReturned) => {},
+        other => panic!("expected no files returned error: {:?}", other),
+    }
+
+    let res = serde_json::from_str::<FileUploadResponse>(
+        include_str!("resources/upload_error.json"),
+    ).expect("err parsing response");
+    match res.into_single() {
+        Err(Error::Api { code, description, kind }) => {
+            assert_eq!(code, 415);
+            assert_eq!(description, "file type not allowed");
+            assert_eq!(kind, ApiErrorCode::from(415));
+        },
+        other => panic!("expected api error: {:?}", other),
+    }
+
+    let failed = r#"{"success": false, "files": []}"#;
+    match serde_json::from_str::<FileUploadResponse>(failed).unwrap()
+        .into_single() {
+        Err(Error::ApiFailure { .. }) => {},
+        other => panic!("expected api failure error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_uploaded_file_errors() {
+    let res = serde_json::from_str::<FileUploadResponse>(MIXED_RESPONSE)
+        .expect("err parsing response");
+
+    assert!(!res[0].is_error());
+    assert_eq!(res[0].errorcode, None);
+
+    assert!(res[1].is_error());
+    assert_eq!(res[1].error, Some(true));
+    assert_eq!(res[1].errorcode, Some(415));
+    assert_eq!(
+        res[1].description,
+        Some("file type not allowed".to_owned()),
+    );
+    assert_eq!(res[1].name, Some("b.exe".to_owned()));
+    assert_eq!(res[1].url, "");
+}
+
+#[test]
+fn test_uploaded_file_errors_round_trip() {
+    let res = serde_json::from_str::<FileUploadResponse>(MIXED_RESPONSE)
+        .expect("err parsing response");
+    let json = serde_json::to_string(&res).expect("err serializing response");
+    let res = serde_json::from_str::<FileUploadResponse>(&json)
+        .expect("err parsing serialized response");
+
+    assert_eq!(res[0].hash, "a");
+    assert!(!res[0].is_error());
+    assert_eq!(res[1].error, Some(true));
+    assert_eq!(res[1].errorcode, Some(415));
+    assert_eq!(
+        res[1].description,
+        Some("file type not allowed".to_owned()),
+    );
+}
+
+#[test]
+fn test_upload_response_eq_round_trip() {
+    for body in &[UPLOAD_RESPONSE, MIXED_RESPONSE] {
+        let res = serde_json::from_str::<FileUploadResponse>(body)
+            .expect("err parsing response");
+        let json = serde_json::to_string(&res)
+            .expect("err serializing response");
+
+        assert_eq!(
+            serde_json::from_str::<FileUploadResponse>(&json)
+                .expect("err parsing serialized response"),
+            res,
+        );
+    }
+
+    let res = upload_response();
+    let mut other = res.clone();
+    other.files[1].size += 1;
+    assert_ne!(res, other);
+}
+
+#[test]
+fn test_uploaded_file_hash() {
+    let res = upload_response();
+    let files = res.files().iter()
+        .chain(res.files())
+        .cloned()
+        .collect::<HashSet<_>>();
+
+    assert_eq!(files.len(), 2);
+    assert!(files.contains(&res[0]));
+    assert!(files.contains(&res[1]));
+}
+
+#[test]
+fn test_shorten_response_eq_round_trip() {
+    let res = serde_json::from_str::<ShortenResponse>(
+        r#"{"action": "shorten", "result": "https://awau.moe/abc"}"#,
+    ).expect("err parsing response");
+    let json = serde_json::to_string(&res).expect("err serializing response");
+
+    assert_eq!(
+        serde_json::from_str::<ShortenResponse>(&json)
+            .expect("err parsing serialized response"),
+        res,
+    );
+}
+
+#[test]
+fn test_upload_response_legacy() {
+    let res = serde_json::from_str::<FileUploadResponse>(
+        r#"{"success": true, "url": "https://pomf.example.com/a1b2c3.png"}"#,
+    ).expect("err parsing response");
+
+    assert!(res.success);
+    assert_eq!(res.files.len(), 1);
+    assert_eq!(res[0].url, "https://pomf.example.com/a1b2c3.png");
+    assert_eq!(res[0].full_url(), "https://pomf.example.com/a1b2c3.png");
+    assert_eq!(res[0].hash, "");
+    assert_eq!(res[0].size, 0);
+    assert!(res[0].name.is_none());
+}
+
+#[test]
+fn test_upload_response_legacy_with_details() {
+    let res = serde_json::from_str::<FileUploadResponse>(
+        r#"{
+            "success": true,
+            "hash": "a1b2c3",
+            "name": "cat.png",
+            "size": 1024,
+            "url": "https://pomf.example.com/a1b2c3.png"
+        }"#,
+    ).expect("err parsing response");
+
+    assert_eq!(res[0].hash, "a1b2c3");
+    assert_eq!(res[0].name, Some("cat.png".to_owned()));
+    assert_eq!(res[0].size, 1024);
+}
+
+#[test]
+fn test_upload_response_invalid() {
+    for body in &[
+        r#"[]"#,
+        r#"{}"#,
+        r#"{"description": "no shape"}"#,
+        r#"{"url": "https://a.example/a.png", "files": []}"#,
+        r#"{"files": {}}"#,
+        r#"{"success": "yes"}"#,
+        r#"{"success": true, "files": [{"size": "big", "url": "a.png"}]}"#,
+        r#"{"success": true, "files": [{"size": -1, "url": "a.png"}]}"#,
+    ] {
+        assert!(
+            serde_json::from_str::<FileUploadResponse>(body).is_err(),
+            "parsed {}",
+            body,
+        );
+    }
+}
+
+#[test]
+fn test_upload_response_variations() {
+    // Each body, with whether it was successful, and the sizes of its files.
+    for &(body, success, sizes) in &[
+        (
+            r#"{"success": true, "files": [{"size": 1024, "url": "a.png"}]}"#,
+            true,
+            &[1024u64][..],
+        ),
+        (
+            r#"{"success": true, "files": [{"size": "1024", "url": "a.png"}]}"#,
+            true,
+            &[1024][..],
+        ),
+        (r#"{"files": [{"size": 5, "url": "a.png"}]}"#, true, &[5][..]),
+        (r#"{"success": true, "files": null}"#, true, &[][..]),
+        (r#"{"success": true}"#, true, &[][..]),
+        (r#"{"files": []}"#, true, &[][..]),
+        (r#"{"errorcode": 413, "files": null}"#, false, &[][..]),
+        (r#"{"success": false, "files": null}"#, false, &[][..]),
+        (r#"{"url": "https://a.example/a.png", "size": "7"}"#, true, &[7][..]),
+        (
+            r#"{"success": false, "url": "https://a.example/a.png"}"#,
+            false,
+            &[0][..],
+        ),
+    ] {
+        let res = serde_json::from_str::<FileUploadResponse>(body)
+            .unwrap_or_else(|why| panic!("err parsing {}: {}", body, why));
+        let parsed_sizes = res.files().iter()
+            .map(|file| file.size)
+            .collect::<Vec<_>>();
+
+        assert_eq!(res.success, success, "success of {}", body);
+        assert_eq!(&parsed_sizes[..], sizes, "sizes of {}", body);
+    }
+}
+
+#[test]
+fn test_upload_response_error_fixture() {
+    let res = serde_json::from_str::<FileUploadResponse>(
+        include_str!("resources/upload_error.json"),
+    ).expect("err parsing response");
+
+    assert!(!res.success);
+    assert!(res.files.is_empty());
+    assert_eq!(res.errorcode, Some(415));
+    assert_eq!(res.description, Some("file type not allowed".to_owned()));
+}
+
+#[test]
+fn test_upload_response_success_fixture() {
+    let res = serde_json::from_str::<FileUploadResponse>(
+        include_str!("resources/upload_success.json"),
+    ).expect("err parsing response");
+
+    assert!(res.success);
+    assert_eq!(res.errorcode, None);
+    assert_eq!(res.description, None);
+    assert_eq!(res.files.len(), 1);
+    assert_eq!(res[0].url, "a1b2c3.png");
+}
+
+#[test]
+fn test_upload_response_mixed_fixture() {
+    let res = serde_json::from_str::<FileUploadResponse>(
+        include_str!("resources/upload_mixed.json"),
+    ).expect("err parsing response");
+
+    assert!(!res.success);
+    assert_eq!(res.errorcode, Some(415));
+    assert_eq!(
+        res.description,
+        Some("some files were not allowed".to_owned()),
+    );
+    assert_eq!(res.files.len(), 2);
+    assert!(!res[0].is_error());
+    assert!(res[1].is_error());
+}
+
+#[cfg(not(feature = "strict-models"))]
+#[test]
+fn test_upload_response_extra_field_fixture() {
+    let res = serde_json::from_str::<FileUploadResponse>(
+        include_str!("resources/upload_extra_field.json"),
+    ).expect("err parsing response");
+
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].url, "a1b2c3.png");
+}
+
+#[cfg(feature = "strict-models")]
+#[test]
+fn test_upload_response_extra_field_fixture_strict() {
+    let why = serde_json::from_str::<FileUploadResponse>(
+        include_str!("resources/upload_extra_field.json"),
+    ).expect_err("parsed response with an extra field");
+    assert!(why.to_string().contains("unknown field `expires_at`"), "{}", why);
+
+    let why = serde_json::from_str::<FileUploadResponse>(
+        r#"{"success": true, "files": [], "hash": "a1b2c3"}"#,
+    ).expect_err("parsed response with a legacy field");
+    assert!(why.to_string().contains("unknown field `hash`"), "{}", why);
+
+    let res = serde_json::from_str::<ShortenResponse>(
+        r#"{"action": "shorten", "result": "https://awau.moe/a", "id": 1}"#,
+    );
+    assert!(res.is_err());
+
+    serde_json::from_str::<FileUploadResponse>(
+        include_str!("resources/upload_success.json"),
+    ).expect("err parsing response");
+}
+
+#[test]
+fn test_upload_response_urls() {
+    let res = serde_json::from_str::<FileUploadResponse>(
+        include_str!("resources/upload_mixed.json"),
+    ).expect("err parsing response");
+
+    assert_eq!(res.urls(), vec!["https://owo.whats-th.is/a1b2c3.png"]);
+    assert_eq!(
+        res.urls_on("https://uwu.whats-th.is/"),
+        vec!["https://uwu.whats-th.is/a1b2c3.png"],
+    );
+
+    let res = upload_response();
+    let urls = res.files().iter()
+        .map(|file| file.full_url_on("i.am-a.furry"))
+        .collect::<Vec<_>>();
+    assert_eq!(res.urls_on("i.am-a.furry"), urls);
+}
+
+#[test]
+fn test_upload_response_display() {
+    assert_eq!(
+        upload_response().to_string(),
+        "https://owo.whats-th.is/a.png\n\
+         https://owo.whats-th.is/b.png",
+    );
+}
+
+#[test]
+fn test_upload_response_display_error_fixture() {
+    let res = serde_json::from_str::<FileUploadResponse>(
+        include_str!("resources/upload_error.json"),
+    ).expect("err parsing response");
+
+    assert_eq!(res.to_string(), "Upload failed: file type not allowed");
+}
+
+#[test]
+fn test_upload_response_display_mixed_fixture() {
+    let res = serde_json::from_str::<FileUploadResponse>(
+        include_str!("resources/upload_mixed.json"),
+    ).expect("err parsing response");
+
+    assert_eq!(
+        res.to_string(),
+        "Upload failed: some files were not allowed\n\
+         https://owo.whats-th.is/a1b2c3.png\n\
+         virus.exe was rejected: file type not allowed",
+    );
+}
+
+#[test]
+fn test_upload_response_display_error_code() {
+    let res = serde_json::from_str::<FileUploadResponse>(
+        r#"{"success": false, "errorcode": 500}"#,
+    ).expect("err parsing response");
+    assert_eq!(res.to_string(), "Upload failed with error code 500");
+
+    let res = serde_json::from_str::<FileUploadResponse>(
+        r#"{"success": false, "files": [{"name": null, "error": true}]}"#,
+    ).expect("err parsing response");
+    assert_eq!(res.to_string(), "Upload failed\nFile was rejected");
+}
+
+#[test]
+fn test_uploaded_file_delete_url() {
+    let res = upload_response();
+
+    assert_eq!(
+        res[0].delete_url,
+        Some("https://owo.whats-th.is/delete/a?token=t".to_owned()),
+    );
+    assert_eq!(res[1].delete_url, None);
+}
+
+#[test]
+fn test_uploaded_file_content_type() {
+    let res = upload_response();
+
+    assert_eq!(res[0].content_type, None);
+    assert_eq!(res[1].content_type, Some("image/png".to_owned()));
+}
+
+#[test]
+fn test_progress() {
+    let progress = Progress { sent: 1024, total: 4096 };
+
+    assert_eq!(progress, Progress { sent: 1024, total: 4096 });
+    assert_ne!(progress, Progress { sent: 2048, total: 4096 });
+}
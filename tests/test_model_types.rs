@@ -0,0 +1,46 @@
+//! Tests of the models which don't deserialize them, so that the models are
+//! also checked to be usable without the `serde-items` feature.
+
+extern crate owo;
+
+use owo::model::{FileUploadResponse, UploadedFile};
+
+fn file(url: &str) -> UploadedFile {
+    UploadedFile {
+        content_type: None,
+        delete_url: None,
+        description: None,
+        error: None,
+        errorcode: None,
+        hash: "a1b2c3".to_owned(),
+        name: Some("cat.png".to_owned()),
+        size: 1024,
+        url: url.to_owned(),
+    }
+}
+
+#[test]
+fn test_uploaded_file_without_serde() {
+    let file = file("a1b2c3.png");
+
+    assert_eq!(file.full_url(), "https://owo.whats-th.is/a1b2c3.png");
+    assert_eq!(file.id(), "a1b2c3");
+    assert_eq!(file.size_human(), "1.0 KiB");
+    assert!(!file.is_error());
+}
+
+#[test]
+fn test_upload_response_without_serde() {
+    let res = FileUploadResponse {
+        description: None,
+        errorcode: None,
+        files: vec![file("a.png"), file("b.png")],
+        success: true,
+    };
+
+    assert_eq!(res.len(), 2);
+    assert_eq!(
+        res.urls(),
+        vec!["https://owo.whats-th.is/a.png", "https://owo.whats-th.is/b.png"],
+    );
+}
@@ -1,13 +1,30 @@
-#![cfg(feature = "reqwest-support")]
+#![cfg(feature = "reqwest")]
 
 extern crate owo;
 extern crate reqwest;
+extern crate serde_json;
 
-use owo::OwoReqwestRequester;
+mod common;
+
+use common::MockServer;
+use owo::{
+    ApiErrorCode,
+    BackoffStrategy,
+    Error,
+    NetworkErrorKind,
+    OwoReqwestClient,
+    OwoReqwestClientBuilder,
+    OwoReqwestRequester,
+    ServiceLimits,
+};
 use reqwest::Client;
-use std::fs::File;
+use serde_json::Value;
+use std::convert::TryFrom;
+use std::fs::{self, File};
 use std::io::Read;
-use std::env;
+use std::net::TcpListener;
+use std::time::Duration;
+use std::{env, process, thread};
 
 fn read(relative_path: &str) -> Vec<u8> {
     let mut buffer = vec![];
@@ -17,6 +34,11 @@ fn read(relative_path: &str) -> Vec<u8> {
     buffer
 }
 
+const UPLOAD_RESPONSE: &str = r#"{
+    "success": true,
+    "files": [{"hash": "a", "name": null, "size": 5, "url": "a.png"}]
+}"#;
+
 #[inline]
 fn key() -> String {
     env::var("OWO_KEY").expect("OWO_KEY env var not present")
@@ -57,6 +79,44 @@ fn test_upload_files() {
     }
 }
 
+#[test]
+fn test_upload_too_many_files() {
+    let client = Client::new();
+
+    let files = vec![vec![], vec![], vec![], vec![]];
+
+    match client.upload_files("key", files) {
+        Err(Error::TooManyFiles { provided, limit }) => {
+            assert_eq!(provided, 4);
+            assert_eq!(limit, 3);
+        },
+        other => panic!("expected too many files error: {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn test_upload_all() {
+    let client = OwoReqwestClientBuilder::new(key())
+        .retries(2)
+        .retry_budget(3)
+        .build()
+        .expect("client err");
+
+    let buffer1 = read("tests/resources/cat.png");
+    let buffer2 = read("tests/resources/horse.png");
+
+    let results = client.upload_all(vec![buffer1, buffer2]);
+    assert_eq!(results.len(), 2);
+
+    for result in results {
+        let res = result.expect("file err");
+
+        assert!(res.success);
+        assert_eq!(res.files.len(), 1);
+    }
+}
+
 #[ignore]
 #[test]
 fn test_shorten_url() {
@@ -66,6 +126,1466 @@ fn test_shorten_url() {
     let res = client.shorten_url(&key, "https://google.com")
         .expect("shorten err");
 
-    assert!(res.len() > 1);
-    assert!(res.split('/').collect::<Vec<_>>().len() > 2);
+    assert!(res.starts_with("https://"));
+    assert!(res.split('/').collect::<Vec<_>>().len() > 3);
+}
+
+#[ignore]
+#[test]
+fn test_shorten_url_slug() {
+    let key = key();
+    let client = Client::new();
+
+    let slug = client.shorten_url_slug(&key, "https://google.com")
+        .expect("shorten err");
+
+    assert!(!slug.is_empty());
+    assert!(!slug.contains('/'));
+}
+
+#[ignore]
+#[test]
+fn test_shorten_urls() {
+    let client = OwoReqwestClientBuilder::new(key())
+        .retries(1)
+        .build()
+        .expect("client err");
+
+    let urls = ["https://google.com", "https://github.com"];
+    let results = client.shorten_urls(&urls);
+    assert_eq!(results.len(), 2);
+
+    for result in results {
+        assert!(result.expect("shorten err").len() > 1);
+    }
+}
+
+#[test]
+fn test_clone_with_key() {
+    let client = OwoReqwestClient::new("first").expect("client err");
+    let other = client.clone_with_key("second").expect("key err");
+
+    assert_eq!(client.key(), "first");
+    assert_eq!(other.key(), "second");
+}
+
+#[test]
+fn test_clones_share_configuration() {
+    let client = OwoReqwestClientBuilder::new("first")
+        .boundary("fixed-boundary")
+        .domain("i.am-a.furry")
+        .build()
+        .expect("client err");
+    let clone = client.clone();
+    let other = client.clone_with_key("second").expect("key err");
+
+    for each in &[clone, other] {
+        assert_eq!(each.boundary(), Some("fixed-boundary"));
+        assert_eq!(each.domain(), "i.am-a.furry");
+        assert_eq!(each.base_url(), client.base_url());
+    }
+}
+
+#[test]
+fn test_from_client() {
+    let http = Client::new();
+    let client = OwoReqwestClient::from_client(http.clone(), " key\n")
+        .expect("client err");
+    let other = OwoReqwestClient::new("key").expect("client err");
+
+    assert_eq!(client.key(), "key");
+    assert_eq!(client.base_url(), other.base_url());
+
+    match OwoReqwestClient::from_client(http, "") {
+        Err(Error::InvalidKey) => {},
+        Err(why) => panic!("expected invalid key error: {:?}", why),
+        Ok(_) => panic!("expected invalid key error"),
+    }
+}
+
+#[test]
+fn test_client_trims_key() {
+    let mut client = OwoReqwestClient::new("  key\n").expect("client err");
+    assert_eq!(client.key(), "key");
+
+    client.set_key("\tother key \r\n").expect("key err");
+    assert_eq!(client.key(), "other key");
+}
+
+#[test]
+fn test_client_rejects_empty_key() {
+    match OwoReqwestClient::new(" \n") {
+        Err(Error::InvalidKey) => {},
+        Err(why) => panic!("expected invalid key error: {:?}", why),
+        Ok(_) => panic!("expected invalid key error"),
+    }
+
+    let mut client = OwoReqwestClient::new("key").expect("client err");
+
+    assert!(client.set_key("").is_err());
+    assert_eq!(client.key(), "key");
+}
+
+#[test]
+fn test_requester_rejects_empty_key() {
+    let client = Client::new();
+
+    match client.upload_file(" \n", b"hello".to_vec()) {
+        Err(Error::InvalidKey) => {},
+        other => panic!("expected invalid key error: {:?}", other),
+    }
+
+    match client.shorten_url("", "https://google.com") {
+        Err(Error::InvalidKey) => {},
+        other => panic!("expected invalid key error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_client_try_from_validates_key() {
+    let client = OwoReqwestClient::try_from("key").expect("client err");
+    assert_eq!(client.key(), "key");
+
+    for key in &["", " ", "key\n", " key", "my key", "key\r\n"] {
+        match OwoReqwestClient::try_from(*key) {
+            Err(Error::InvalidKey) => {},
+            Err(why) => panic!("expected invalid key error: {:?}", why),
+            Ok(_) => panic!("expected invalid key error for {:?}", key),
+        }
+    }
+}
+
+#[ignore]
+#[test]
+fn test_client_clones_across_threads() {
+    let client = OwoReqwestClient::new(key()).expect("client err");
+
+    let handles = ["tests/resources/cat.png", "tests/resources/horse.png"]
+        .iter()
+        .map(|path| {
+            let client = client.clone();
+            let buffer = read(path);
+
+            thread::spawn(move || client.upload_file(buffer))
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        let res = handle.join().expect("thread panicked").expect("file err");
+
+        assert!(res.success);
+        assert_eq!(res.files.len(), 1);
+    }
+}
+
+#[test]
+fn test_max_response_bytes() {
+    let server = MockServer::start("200 OK", UPLOAD_RESPONSE);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .max_response_bytes(16)
+        .build()
+        .expect("client err");
+
+    match client.upload_file(b"hello".to_vec()) {
+        Err(Error::ResponseTooLarge { limit }) => assert_eq!(limit, 16),
+        other => panic!("expected response too large error: {:?}", other),
+    }
+
+    let server = MockServer::start("200 OK", "https://awau.moe/abc");
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .max_response_bytes(8)
+        .build()
+        .expect("client err");
+
+    match client.shorten_url("https://google.com") {
+        Err(Error::ResponseTooLarge { limit }) => assert_eq!(limit, 8),
+        other => panic!("expected response too large error: {:?}", other),
+    }
+
+    let server = MockServer::start("200 OK", UPLOAD_RESPONSE);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .max_response_bytes(UPLOAD_RESPONSE.len())
+        .build()
+        .expect("client err");
+
+    let res = client.upload_file(b"hello".to_vec()).expect("file err");
+    assert_eq!(res.files[0].url, "a.png");
+}
+
+#[test]
+fn test_default_max_response_bytes() {
+    let page = Box::leak("x".repeat(1024 * 1024 + 1).into_boxed_str());
+    let server = MockServer::start("200 OK", page);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.upload_file(b"hello".to_vec()) {
+        Err(Error::ResponseTooLarge { limit }) => {
+            assert_eq!(limit, 1024 * 1024);
+        },
+        other => panic!("expected response too large error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_upload_with_boundary() {
+    let server = MockServer::start("200 OK", UPLOAD_RESPONSE);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .boundary("fixed-boundary")
+        .build()
+        .expect("client err");
+    assert_eq!(client.boundary(), Some("fixed-boundary"));
+
+    let res = client.upload_file(b"hello".to_vec()).expect("file err");
+    assert_eq!(res.files[0].url, "a.png");
+
+    let request = server.request();
+    assert_eq!(request.line, "POST /upload/pomf?key=key HTTP/1.1");
+    assert_eq!(
+        request.header("content-type"),
+        Some("multipart/form-data; boundary=fixed-boundary"),
+    );
+    assert_eq!(
+        String::from_utf8(request.body).unwrap(),
+        "--fixed-boundary\r\n\
+         Content-Disposition: form-data; name=\"files[]\"\r\n\
+         Content-Type: application/octet-stream\r\n\
+         \r\n\
+         hello\r\n\
+         --fixed-boundary--\r\n",
+    );
+}
+
+#[test]
+fn test_upload_with_quoted_boundary() {
+    let server = MockServer::start("200 OK", UPLOAD_RESPONSE);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .boundary("fixed boundary")
+        .build()
+        .expect("client err");
+
+    client.upload_file(b"hello".to_vec()).expect("file err");
+
+    let request = server.request();
+    assert_eq!(
+        request.header("content-type"),
+        Some("multipart/form-data; boundary=\"fixed boundary\""),
+    );
+    assert!(String::from_utf8(request.body)
+        .unwrap()
+        .starts_with("--fixed boundary\r\n"));
+}
+
+#[test]
+fn test_preferred_domain() {
+    let client = OwoReqwestClientBuilder::new("key")
+        .preferred_domain("https://uwu.whats-th.is/")
+        .build()
+        .expect("client err");
+    assert_eq!(client.domain(), "uwu.whats-th.is");
+
+    let result = OwoReqwestClientBuilder::new("key")
+        .preferred_domain("example.com")
+        .build();
+
+    match result {
+        Err(Error::UnknownDomain(ref domain)) => {
+            assert_eq!(domain, "example.com");
+        },
+        Err(why) => panic!("expected unknown domain error: {:?}", why),
+        Ok(_) => panic!("expected unknown domain error"),
+    }
+
+    let client = OwoReqwestClientBuilder::new("key")
+        .preferred_domain("example.com")
+        .domain("example.com")
+        .build()
+        .expect("client err");
+    assert_eq!(client.domain(), "example.com");
+}
+
+#[test]
+fn test_invalid_boundary() {
+    let long = "a".repeat(71);
+    let boundaries = ["", "ends with space ", "new\nline", &long[..]];
+
+    for boundary in boundaries.iter() {
+        let result = OwoReqwestClientBuilder::new("key")
+            .boundary(*boundary)
+            .build();
+
+        match result {
+            Err(Error::InvalidBoundary(ref invalid)) => {
+                assert_eq!(invalid, *boundary);
+            },
+            Err(why) => panic!("expected invalid boundary error: {:?}", why),
+            Ok(_) => panic!("expected invalid boundary error: {:?}", boundary),
+        }
+    }
+}
+
+/// Produces a base URL which nothing is listening on.
+fn unreachable_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind err");
+
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+/// Starts a server which accepts connections but never responds.
+fn unresponsive_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind err");
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        let streams = listener.incoming().collect::<Vec<_>>();
+        drop(streams);
+    });
+
+    url
+}
+
+#[test]
+fn test_timeout() {
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(unresponsive_url())
+        .timeout(Duration::from_millis(200))
+        .build()
+        .expect("client err");
+
+    match client.upload_file(vec![1, 2, 3]) {
+        Err(why @ Error::Timeout { .. }) => {
+            assert!(why.is_timeout());
+            assert!(why.is_network());
+
+            if let Error::Timeout { elapsed } = why {
+                assert!(elapsed >= Duration::from_millis(200));
+            }
+        },
+        other => panic!("expected timeout error: {:?}", other),
+    }
+
+    match client.shorten_url("https://google.com") {
+        Err(Error::Timeout { .. }) => {},
+        other => panic!("expected timeout error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_network_kind_connect() {
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(unreachable_url())
+        .build()
+        .expect("client err");
+
+    let error = client.shorten_url("https://google.com")
+        .expect_err("unreachable");
+    assert_eq!(error.network_kind(), Some(NetworkErrorKind::Connect));
+}
+
+#[test]
+fn test_network_kind_unresolvable() {
+    // The `.invalid` top-level domain is reserved, so never resolves.
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url("http://owo.invalid")
+        .build()
+        .expect("client err");
+
+    let error = client.shorten_url("https://google.com")
+        .expect_err("unresolvable");
+    assert_eq!(error.network_kind(), Some(NetworkErrorKind::Connect));
+}
+
+#[test]
+fn test_network_kind_tls() {
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(common::plaintext_url())
+        .build()
+        .expect("client err");
+
+    let error = client.shorten_url("https://google.com")
+        .expect_err("handshake");
+    assert_eq!(error.network_kind(), Some(NetworkErrorKind::Tls));
+}
+
+#[test]
+fn test_mirror_failover() {
+    let unreachable = unreachable_url();
+    let mirror = MockServer::start("200 OK", "https://awau.moe/abc");
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(unreachable.clone())
+        .mirror(mirror.url.clone())
+        .build()
+        .expect("client err");
+    assert_eq!(client.base_url(), unreachable);
+
+    let short = client.shorten_url("https://google.com").expect("shorten err");
+    assert_eq!(short, "https://awau.moe/abc");
+    mirror.request();
+
+    // The mirror is remembered, including by clones.
+    assert_eq!(client.base_url(), mirror.url);
+    let other = client.clone_with_key("other").expect("key err");
+    assert_eq!(other.base_url(), mirror.url);
+}
+
+#[test]
+fn test_mirror_no_failover_on_http_error() {
+    let primary = MockServer::start("401 Unauthorized", "unauthorized");
+    let mirror = MockServer::start("200 OK", "https://awau.moe/abc");
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(primary.url.clone())
+        .mirror(mirror.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.shorten_url("https://google.com") {
+        Err(Error::InvalidKey) => {},
+        other => panic!("expected invalid key error: {:?}", other),
+    }
+    primary.request();
+
+    assert_eq!(client.base_url(), primary.url);
+}
+
+#[test]
+fn test_invalid_mirror() {
+    let result = OwoReqwestClientBuilder::new("key")
+        .mirror("api.example.com")
+        .build();
+
+    match result {
+        Err(Error::Config { field, .. }) => assert_eq!(field, "mirror"),
+        Err(why) => panic!("expected config error: {:?}", why),
+        Ok(_) => panic!("expected config error"),
+    }
+}
+
+#[test]
+fn test_delete_file() {
+    let server = MockServer::start("200 OK", "");
+    let client = OwoReqwestClient::new("key").expect("client err");
+    let delete_url = format!("{}/delete/a?token=t", server.url);
+
+    client.delete_file(&delete_url).expect("delete err");
+    assert_eq!(server.request().line, "GET /delete/a?token=t HTTP/1.1");
+
+    let server = MockServer::start("404 Not Found", "no such file");
+    let delete_url = format!("{}/delete/a?token=t", server.url);
+
+    match client.delete_file(&delete_url) {
+        Err(Error::UnexpectedResponse(body)) => {
+            assert_eq!(body, "no such file");
+        },
+        other => panic!("expected unexpected response error: {:?}", other),
+    }
+
+    // Long error pages are truncated.
+    let page = Box::leak("no such file ".repeat(1024).into_boxed_str());
+    let server = MockServer::start("404 Not Found", page);
+    let delete_url = format!("{}/delete/a?token=t", server.url);
+
+    match client.delete_file(&delete_url) {
+        Err(Error::UnexpectedResponse(body)) => {
+            assert_eq!(body.len(), 512);
+            assert!(page.starts_with(&body));
+        },
+        other => panic!("expected unexpected response error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_stricter_limits() {
+    let limits = ServiceLimits {
+        max_file_size: Some(4),
+        max_files: Some(1),
+        max_url_length: Some(10),
+    };
+    let client = OwoReqwestClientBuilder::new("key")
+        .limits(limits)
+        .build()
+        .expect("client err");
+    assert_eq!(client.limits(), &limits);
+
+    match client.upload_files(vec![vec![], vec![]]) {
+        Err(Error::TooManyFiles { provided, limit }) => {
+            assert_eq!(provided, 2);
+            assert_eq!(limit, 1);
+        },
+        other => panic!("expected too many files error: {:?}", other),
+    }
+
+    match client.upload_file(b"hello".to_vec()) {
+        Err(Error::FileTooLarge { size, limit }) => {
+            assert_eq!(size, 5);
+            assert_eq!(limit, 4);
+        },
+        other => panic!("expected file too large error: {:?}", other),
+    }
+
+    match client.shorten_url("https://google.com") {
+        Err(Error::UrlTooLong { length, limit }) => {
+            assert_eq!(length, 18);
+            assert_eq!(limit, 10);
+        },
+        other => panic!("expected url too long error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_looser_limits() {
+    let server = MockServer::start("200 OK", UPLOAD_RESPONSE);
+    let limits = ServiceLimits {
+        max_files: Some(5),
+        ..ServiceLimits::unlimited()
+    };
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .boundary("boundary")
+        .limits(limits)
+        .build()
+        .expect("client err");
+
+    let files = vec![vec![1], vec![2], vec![3], vec![4]];
+    client.upload_files(files).expect("file err");
+
+    let body = String::from_utf8(server.request().body).unwrap();
+    assert_eq!(body.matches("--boundary\r\n").count(), 4);
+}
+
+#[test]
+fn test_warm_up() {
+    // Any response is enough for the connection to be established.
+    let server = MockServer::start("404 Not Found", "");
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    client.warm_up().expect("warm up err");
+    assert_eq!(server.request().line, "HEAD / HTTP/1.1");
+
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(unreachable_url())
+        .build()
+        .expect("client err");
+
+    match client.warm_up() {
+        Err(Error::Reqwest(..)) => {},
+        other => panic!("expected reqwest error: {:?}", other),
+    }
+}
+
+#[cfg(all(feature = "dangerous-config", feature = "reqwest-native-tls"))]
+#[test]
+fn test_danger_disable_hostname_verification() {
+    let server = MockServer::start("200 OK", "https://awau.moe/abc");
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .danger_disable_hostname_verification(true)
+        .build()
+        .expect("client err");
+
+    let short = client.shorten_url("https://google.com").expect("shorten err");
+    assert_eq!(short, "https://awau.moe/abc");
+}
+
+const ERROR_RESPONSE: &str = r#"{
+    "success": false,
+    "errorcode": 400,
+    "description": "no files were uploaded"
+}"#;
+
+#[test]
+fn test_upload_api_error() {
+    let server = MockServer::start("400 Bad Request", ERROR_RESPONSE);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.upload_file(vec![1, 2, 3]) {
+        Err(Error::Api { code, description, kind }) => {
+            assert_eq!(code, 400);
+            assert_eq!(description, "no files were uploaded");
+            assert_eq!(kind, ApiErrorCode::BadRequest);
+        },
+        other => panic!("expected api error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_shorten_url_api_error() {
+    let server = MockServer::start("400 Bad Request", ERROR_RESPONSE);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.shorten_url("https://google.com") {
+        Err(Error::Api { code, .. }) => assert_eq!(code, 400),
+        other => panic!("expected api error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_upload_and_shorten_no_files() {
+    let server = MockServer::start("200 OK", r#"{"success":true,"files":[]}"#);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.upload_and_shorten(vec![1, 2, 3]) {
+        Err(Error::NoFilesReturned) => {},
+        other => panic!("expected no files returned error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_unsuccessful_response() {
+    let body = r#"{"success":false,"files":[]}"#;
+
+    // By default, the response is an error.
+    let server = MockServer::start("200 OK", body);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.upload_files(vec![vec![1], vec![2]]) {
+        Err(Error::ApiFailure { body }) => {
+            assert_eq!(body, r#"{"files":[],"success":false}"#);
+        },
+        other => panic!("expected api failure error: {:?}", other),
+    }
+
+    match client.upload_file(vec![1, 2, 3]) {
+        Err(Error::ApiFailure { .. }) => {},
+        other => panic!("expected api failure error: {:?}", other),
+    }
+
+    // Unless the client allows the response to be returned for the caller to
+    // check.
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .allow_unsuccessful()
+        .build()
+        .expect("client err");
+
+    let response = client.upload_files(vec![vec![1], vec![2]])
+        .expect("upload err");
+    assert!(!response.success);
+}
+
+#[test]
+fn test_unsuccessful_response_with_error_code() {
+    let body = r#"{"success":false,"errorcode":415,"description":"no exes"}"#;
+    let server = MockServer::start("200 OK", body);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.upload_file(vec![1, 2, 3]) {
+        Err(Error::Api { code, ref description, kind }) => {
+            assert_eq!(code, 415);
+            assert_eq!(description, "no exes");
+            assert_eq!(kind, ApiErrorCode::DisallowedType);
+        },
+        other => panic!("expected api error: {:?}", other),
+    }
+
+    // Unless the client allows the response to be returned for the caller to
+    // check.
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .allow_unsuccessful()
+        .build()
+        .expect("client err");
+
+    let response = client.upload_files(vec![vec![1], vec![2]])
+        .expect("upload err");
+    assert!(!response.success);
+    assert_eq!(response.errorcode, Some(415));
+    assert_eq!(response.description, Some("no exes".to_owned()));
+}
+
+#[test]
+fn test_unsuccessful_response_with_error_code_only() {
+    // Without a description, the body isn't recognised as an error until its
+    // `success` field is checked.
+    let body = r#"{"success":false,"errorcode":415}"#;
+    let server = MockServer::start("200 OK", body);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.upload_files(vec![vec![1], vec![2]]) {
+        Err(Error::Api { code, kind, .. }) => {
+            assert_eq!(code, 415);
+            assert_eq!(kind, ApiErrorCode::DisallowedType);
+        },
+        other => panic!("expected api error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_upload_file_no_files() {
+    let body = r#"{"success":true,"files":[]}"#;
+    let server = MockServer::start("200 OK", body);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.upload_file(vec![1, 2, 3]) {
+        Err(Error::NoFilesReturned) => {},
+        other => panic!("expected no files returned error: {:?}", other),
+    }
+
+    // Batch uploads leave checking the files to the caller.
+    let server = MockServer::start("200 OK", body);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    let response = client.upload_files(vec![vec![1], vec![2]])
+        .expect("upload err");
+    assert!(response.files.is_empty());
+}
+
+#[test]
+fn test_status_error_redacts_key() {
+    let server = MockServer::start(
+        "502 Bad Gateway",
+        "upstream failed for /upload/pomf?key=secret-key",
+    );
+    let client = OwoReqwestClientBuilder::new("secret-key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.upload_file(vec![1, 2, 3]) {
+        Err(Error::Status { status, body }) => {
+            assert_eq!(status, 502);
+            assert_eq!(body, "upstream failed for /upload/pomf?key=[redacted]");
+        },
+        other => panic!("expected status error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_mirror_url() {
+    let remote = MockServer::start("200 OK", "image bytes");
+    let server = MockServer::start("200 OK", UPLOAD_RESPONSE);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    let url = format!("{}/images/cat.png?size=large", remote.url);
+    let res = client.mirror_url(&url).expect("mirror err");
+    assert_eq!(res.files[0].url, "a.png");
+
+    assert_eq!(
+        remote.request().line,
+        "GET /images/cat.png?size=large HTTP/1.1",
+    );
+
+    let body = String::from_utf8(server.request().body).expect("utf8 err");
+    assert!(body.contains("filename=\"cat.png\""));
+    assert!(body.contains("image bytes"));
+}
+
+#[test]
+fn test_mirror_url_too_large() {
+    let remote = MockServer::start("200 OK", "image bytes");
+    let limits = ServiceLimits {
+        max_file_size: Some(5),
+        ..ServiceLimits::unlimited()
+    };
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(unreachable_url())
+        .limits(limits)
+        .build()
+        .expect("client err");
+
+    match client.mirror_url(&format!("{}/cat.png", remote.url)) {
+        Err(Error::FileTooLarge { size, limit }) => {
+            assert_eq!(size, 11);
+            assert_eq!(limit, 5);
+        },
+        other => panic!("expected file too large error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_mirror_url_remote_error() {
+    let remote = MockServer::start("404 Not Found", "no such image");
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(unreachable_url())
+        .build()
+        .expect("client err");
+
+    match client.mirror_url(&format!("{}/cat.png", remote.url)) {
+        Err(Error::Status { status, body }) => {
+            assert_eq!(status, 404);
+            assert_eq!(body, "no such image");
+        },
+        other => panic!("expected status error: {:?}", other),
+    }
+}
+
+/// Asserts that the key is absent from the `Display` and `Debug` output of an
+/// error and of each of its sources.
+fn assert_redacted(error: &Error, key: &str) {
+    use std::error::Error as StdError;
+
+    assert!(!error.to_string().contains(key), "display: {}", error);
+    assert!(!format!("{:?}", error).contains(key), "debug: {:?}", error);
+
+    let mut source = error.source();
+
+    while let Some(inner) = source {
+        assert!(!inner.to_string().contains(key), "source: {}", inner);
+        assert!(!format!("{:?}", inner).contains(key), "source: {:?}", inner);
+
+        source = inner.source();
+    }
+}
+
+#[test]
+fn test_errors_redact_key() {
+    const KEY: &str = "sentinel-key-1234";
+
+    let responses = [
+        ("400 Bad Request", concat!(
+            r#"{"success":false,"errorcode":400,"#,
+            r#""description":"bad key sentinel-key-1234"}"#,
+        )),
+        ("401 Unauthorized", "sentinel-key-1234"),
+        ("413 Payload Too Large", "sentinel-key-1234"),
+        ("429 Too Many Requests", "sentinel-key-1234"),
+        ("502 Bad Gateway", "/upload/pomf?key=sentinel-key-1234"),
+        ("503 Service Unavailable", "<html>sentinel-key-1234</html>"),
+        ("200 OK", "not json: sentinel-key-1234"),
+    ];
+
+    for &(status, body) in &responses {
+        let server = MockServer::start(status, body);
+        let client = OwoReqwestClientBuilder::new(KEY)
+            .base_url(server.url.clone())
+            .build()
+            .expect("client err");
+
+        let error = client.upload_file(vec![1, 2, 3]).expect_err(status);
+        assert_redacted(&error, KEY);
+
+        let error = client.shorten_url("https://google.com").expect_err(status);
+        assert_redacted(&error, KEY);
+    }
+
+    // Short URLs which a slug can't be taken from are kept in the error.
+    let server = MockServer::start(
+        "200 OK",
+        "https://awau.moe/?key=sentinel-key-1234",
+    );
+    let client = OwoReqwestClientBuilder::new(KEY)
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    let error = client.shorten_url_slug("https://google.com")
+        .expect_err("no slug");
+    assert_redacted(&error, KEY);
+
+    // Errors from the reqwest crate contain the URL of the request.
+    let client = OwoReqwestClientBuilder::new(KEY)
+        .base_url(unreachable_url())
+        .build()
+        .expect("client err");
+
+    let error = client.upload_file(vec![1, 2, 3]).expect_err("unreachable");
+    assert_redacted(&error, KEY);
+
+    let error = client.shorten_url("https://google.com")
+        .expect_err("unreachable");
+    assert_redacted(&error, KEY);
+}
+
+#[test]
+fn test_upload_file_as() {
+    let server = MockServer::start("200 OK", UPLOAD_RESPONSE);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    let res: Value = client.upload_file_as(vec![1, 2, 3]).expect("file err");
+    assert_eq!(res["files"][0]["url"], "a.png");
+}
+
+#[test]
+fn test_rejected_key() {
+    let server = MockServer::start("401 Unauthorized", r#"{"success":false}"#);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.upload_file(vec![1, 2, 3]) {
+        Err(Error::InvalidKey) => {},
+        other => panic!("expected invalid key error: {:?}", other),
+    }
+
+    let server = MockServer::start("403 Forbidden", "banned");
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.shorten_url("https://google.com") {
+        Err(Error::InvalidKey) => {},
+        other => panic!("expected invalid key error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_shorten_url_invalid_utf8() {
+    let server = MockServer::start_raw("200 OK", &[], b"<html>\xff</html>");
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.shorten_url("https://google.com") {
+        Err(Error::UnexpectedResponse(body)) => {
+            assert_eq!(body, "<html>\u{fffd}</html>");
+        },
+        other => panic!("expected unexpected response error: {:?}", other),
+    }
+
+    let server = MockServer::start_raw("502 Bad Gateway", &[], b"bad \xfe\xff");
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.shorten_url("https://google.com") {
+        Err(Error::Status { status, body }) => {
+            assert_eq!(status, 502);
+            assert_eq!(body, "bad \u{fffd}\u{fffd}");
+        },
+        other => panic!("expected status error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_shorten_url_not_a_url() {
+    let server = MockServer::start("200 OK", "<html>maintenance</html>");
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.shorten_url("https://google.com") {
+        Err(Error::UnexpectedResponse(body)) => {
+            assert_eq!(body, "<html>maintenance</html>");
+        },
+        other => panic!("expected unexpected response error: {:?}", other),
+    }
+
+    let server = MockServer::start("200 OK", "https://awau.moe/abc\n");
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    let short = client.shorten_url("https://google.com").expect("shorten err");
+    assert_eq!(short, "https://awau.moe/abc");
+
+    // Long pages are truncated.
+    let page = Box::leak("maintenance ".repeat(1024).into_boxed_str());
+    let server = MockServer::start("200 OK", page);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.shorten_url("https://google.com") {
+        Err(Error::UnexpectedResponse(body)) => assert_eq!(body.len(), 512),
+        other => panic!("expected unexpected response error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_rate_limited() {
+    let server = MockServer::start_with_headers(
+        "429 Too Many Requests",
+        &["Retry-After: 2"],
+        "slow down",
+    );
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.upload_file(vec![1, 2, 3]) {
+        Err(Error::RateLimited { retry_after }) => {
+            assert_eq!(retry_after, Some(Duration::from_secs(2)));
+        },
+        other => panic!("expected rate limited error: {:?}", other),
+    }
+
+    let server = MockServer::start("429 Too Many Requests", "slow down");
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.shorten_url("https://google.com") {
+        Err(Error::RateLimited { retry_after: None }) => {},
+        other => panic!("expected rate limited error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_rate_limited_retried() {
+    let server = MockServer::start_with_headers(
+        "429 Too Many Requests",
+        &["Retry-After: 0"],
+        "slow down",
+    );
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .retries(1)
+        .build()
+        .expect("client err");
+
+    let results = client.upload_all(vec![vec![1, 2, 3]]);
+
+    match results[0] {
+        Err(Error::RateLimited { .. }) => {},
+        ref other => panic!("expected rate limited error: {:?}", other),
+    }
+
+    // The initial request and its retry.
+    server.request();
+    server.request();
+}
+
+#[test]
+fn test_backoff_delay() {
+    let ms = Duration::from_millis;
+    let fixed = BackoffStrategy::Fixed(ms(250));
+    let exponential = BackoffStrategy::Exponential {
+        base: ms(100),
+        max: ms(1000),
+    };
+    let jitter = BackoffStrategy::ExponentialJitter {
+        base: ms(100),
+        max: ms(1000),
+    };
+
+    assert_eq!(fixed.delay(0, 0.5), ms(250));
+    assert_eq!(fixed.delay(5, 0.5), ms(250));
+
+    assert_eq!(exponential.delay(0, 0.5), ms(100));
+    assert_eq!(exponential.delay(1, 0.5), ms(200));
+    assert_eq!(exponential.delay(3, 0.5), ms(800));
+    assert_eq!(exponential.delay(4, 0.5), ms(1000));
+    assert_eq!(exponential.delay(100, 0.5), ms(1000));
+
+    assert_eq!(jitter.delay(0, 0.0), ms(0));
+    assert_eq!(jitter.delay(0, 0.5), ms(50));
+    assert_eq!(jitter.delay(3, 0.25), ms(200));
+    assert_eq!(jitter.delay(100, 0.5), ms(500));
+
+    assert_eq!(BackoffStrategy::default(), BackoffStrategy::ExponentialJitter {
+        base: ms(100),
+        max: Duration::from_secs(10),
+    });
+}
+
+#[test]
+fn test_payload_too_large() {
+    let server = MockServer::start(
+        "413 Payload Too Large",
+        "<html><h1>413 Request Entity Too Large</h1></html>",
+    );
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.upload_files(vec![vec![0; 10], vec![0; 5]]) {
+        Err(Error::PayloadTooLarge { size }) => assert_eq!(size, Some(15)),
+        other => panic!("expected payload too large error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_json_keeps_body() {
+    use std::error::Error as StdError;
+
+    let server = MockServer::start("200 OK", "not json: secret-key");
+    let client = OwoReqwestClientBuilder::new("secret-key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.upload_file(vec![1, 2, 3]) {
+        Err(why @ Error::InvalidJson { .. }) => {
+            assert!(why.source().is_some());
+
+            if let Error::InvalidJson { body, .. } = why {
+                assert_eq!(body, "not json: [redacted]");
+            }
+        },
+        other => panic!("expected invalid json error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_endpoint_paths() {
+    let server = MockServer::start("200 OK", UPLOAD_RESPONSE);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .upload_path("/api/v1/upload")
+        .shorten_path("api/v1/shorten")
+        .build()
+        .expect("client err");
+    assert_eq!(client.shorten_path(), "/api/v1/shorten");
+
+    client.upload_file(vec![1, 2, 3]).expect("file err");
+    assert_eq!(server.request().line, "POST /api/v1/upload?key=key HTTP/1.1");
+
+    let result = OwoReqwestClientBuilder::new("key")
+        .upload_path("/upload?format=json")
+        .build();
+
+    match result {
+        Err(Error::Config { field, .. }) => assert_eq!(field, "upload_path"),
+        Err(why) => panic!("expected config error: {:?}", why),
+        Ok(_) => panic!("expected config error"),
+    }
+}
+
+#[test]
+fn test_html_error_page() {
+    let server = MockServer::start(
+        "522 Origin Connection Time-out",
+        "<!DOCTYPE html><html><body>Connection timed out</body></html>",
+    );
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.upload_file(vec![1, 2, 3]) {
+        Err(why @ Error::ServiceUnavailable { .. }) => {
+            assert!(!why.to_string().contains("<html>"));
+
+            if let Error::ServiceUnavailable { status, body } = why {
+                assert_eq!(status, 522);
+                assert!(body.contains("Connection timed out"));
+            }
+        },
+        other => panic!("expected service unavailable error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_html_page_with_success_status() {
+    let server = MockServer::start(
+        "200 OK",
+        "\n  <!DOCTYPE html><html><body>Are you a robot?</body></html>",
+    );
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    match client.upload_file(vec![1, 2, 3]) {
+        Err(Error::HtmlResponse(body)) => {
+            assert!(body.contains("Are you a robot?"));
+        },
+        other => panic!("expected html response error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_shorten_url_json() {
+    let server = MockServer::start(
+        "200 OK",
+        r#"{"action":"shorten","result":"https://awau.moe/abc"}"#,
+    );
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    let res = client.shorten_url_json("https://google.com")
+        .expect("shorten err");
+    assert_eq!(res.action, "shorten");
+    assert_eq!(res.result, "https://awau.moe/abc");
+
+    let request = server.request();
+    assert!(request.line.contains("&response_type=json "));
+}
+
+#[test]
+fn test_prepare_upload_files() {
+    let client = OwoReqwestClientBuilder::new("secret-key")
+        .base_url("http://127.0.0.1:1")
+        .boundary("fixed-boundary")
+        .build()
+        .expect("client err");
+
+    let request = client.prepare_upload_files(&[b"hello".to_vec()])
+        .expect("prepare err");
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.url, "http://127.0.0.1:1/upload/pomf?key=[redacted]");
+    assert_eq!(request.header("user-agent"), Some(owo::constants::USER_AGENT));
+    assert_eq!(
+        request.header("content-type"),
+        Some("multipart/form-data; boundary=fixed-boundary"),
+    );
+    assert!(String::from_utf8(request.body).unwrap().contains("hello"));
+}
+
+#[test]
+fn test_prepare_shorten_url() {
+    let client = OwoReqwestClientBuilder::new("secret-key")
+        .base_url("http://127.0.0.1:1")
+        .build()
+        .expect("client err");
+
+    let request = client.prepare_shorten_url("https://google.com")
+        .expect("prepare err");
+    assert_eq!(request.method, "GET");
+    assert!(!request.url.contains("secret-key"));
+    assert_eq!(request.header("content-type"), None);
+    assert_eq!(request.body_len(), 0);
+}
+
+#[test]
+fn test_prepare_redacts_encoded_key() {
+    let client = OwoReqwestClientBuilder::new("a&b+c d")
+        .base_url("http://127.0.0.1:1")
+        .build()
+        .expect("client err");
+
+    let request = client.prepare_upload_files(&[b"hello".to_vec()])
+        .expect("prepare err");
+    assert_eq!(request.url, "http://127.0.0.1:1/upload/pomf?key=[redacted]");
+
+    let request = client.prepare_shorten_url("https://google.com")
+        .expect("prepare err");
+    assert!(request.url.contains("key=[redacted]"));
+    assert!(!request.url.contains("b%2Bc"), "url: {}", request.url);
+}
+
+#[test]
+fn test_shorten_url_with_description() {
+    let server = MockServer::start("200 OK", "https://awau.moe/abc");
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    let res = client.shorten_url_with_description(
+        "https://google.com",
+        "cat.png",
+    ).expect("shorten err");
+    assert_eq!(res, "https://awau.moe/abc");
+
+    let request = server.request();
+    assert!(request.line.contains("&description=cat.png "));
+}
+
+#[test]
+fn test_upload_body_len() {
+    let server = MockServer::start("200 OK", UPLOAD_RESPONSE);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    let file = read("tests/resources/cat.png");
+    let expected = client.upload_body_len(&[&file[..]]);
+    assert!(expected > file.len() as u64);
+
+    client.upload_file(file).expect("file err");
+
+    assert_eq!(server.request().body.len() as u64, expected);
+}
+
+#[test]
+fn test_upload_dir() {
+    let name = format!("owo-rs-upload-dir-{}", process::id());
+    let dir = env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("sub")).expect("dir err");
+    fs::write(dir.join("a.txt"), b"a").expect("write err");
+    fs::write(dir.join("big.txt"), b"too big").expect("write err");
+    fs::write(dir.join("sub").join("b.txt"), b"b").expect("write err");
+
+    let server = MockServer::start("200 OK", UPLOAD_RESPONSE);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .limits(ServiceLimits {
+            max_file_size: Some(4),
+            ..ServiceLimits::default()
+        })
+        .build()
+        .expect("client err");
+
+    let results = client.upload_dir(&dir).expect("dir err");
+    let paths = results.iter().map(|(path, _)| path.clone());
+    assert_eq!(
+        paths.collect::<Vec<_>>(),
+        vec![dir.join("a.txt"), dir.join("big.txt")],
+    );
+    assert!(results[0].1.is_ok());
+
+    match results[1].1 {
+        Err(Error::FileTooLarge { size: 7, limit: 4 }) => {},
+        ref other => panic!("expected file too large error: {:?}", other),
+    }
+
+    let body = String::from_utf8(server.request().body).unwrap();
+    assert!(body.contains("filename=\"a.txt\""));
+
+    // Both small files are sent in one request, whose response only
+    // contains one file.
+    let results = client.upload_dir_recursive(&dir).expect("dir err");
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[2].0, dir.join("sub").join("b.txt"));
+
+    for (_, result) in &[&results[0], &results[2]] {
+        match *result {
+            Err(Error::UnexpectedResponse(_)) => {},
+            ref other => panic!("expected unexpected response: {:?}", other),
+        }
+    }
+
+    let body = String::from_utf8(server.request().body).unwrap();
+    assert!(body.contains("filename=\"a.txt\""));
+    assert!(body.contains("filename=\"b.txt\""));
+
+    fs::remove_dir_all(&dir).expect("cleanup err");
+
+    match client.upload_dir(&dir) {
+        Err(Error::File { ref path, .. }) => assert_eq!(path, &dir),
+        other => panic!("expected file error: {:?}", other),
+    }
+}
+
+#[test]
+fn test_upload_dir_batches() {
+    const RESPONSE: &str = r#"{
+        "success": true,
+        "files": [
+            {"hash": "a", "name": "a.txt", "size": 1, "url": "a.txt"},
+            {"hash": "b", "name": "b.txt", "size": 1, "url": "b.txt"}
+        ]
+    }"#;
+
+    let name = format!("owo-rs-upload-dir-batches-{}", process::id());
+    let dir = env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("dir err");
+    fs::write(dir.join("a.txt"), b"a").expect("write err");
+    fs::write(dir.join("b.txt"), b"b").expect("write err");
+    fs::write(dir.join("c.txt"), b"c").expect("write err");
+
+    let server = MockServer::start("200 OK", RESPONSE);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .limits(ServiceLimits {
+            max_files: Some(2),
+            ..ServiceLimits::default()
+        })
+        .build()
+        .expect("client err");
+
+    let results = client.upload_dir(&dir).expect("dir err");
+    fs::remove_dir_all(&dir).expect("cleanup err");
+    assert_eq!(results.len(), 3);
+
+    for (path, result) in &results[..2] {
+        let response = result.as_ref().expect("upload err");
+        let name = path.file_name().and_then(|name| name.to_str());
+        assert_eq!(response.files.len(), 1);
+        assert_eq!(response.files[0].name.as_deref(), name);
+    }
+
+    let body = String::from_utf8(server.request().body).unwrap();
+    assert!(body.contains("filename=\"a.txt\""));
+    assert!(body.contains("filename=\"b.txt\""));
+    assert!(!body.contains("filename=\"c.txt\""));
+
+    let body = String::from_utf8(server.request().body).unwrap();
+    assert!(body.contains("filename=\"c.txt\""));
+    assert!(results[2].1.is_ok());
+}
+
+#[test]
+fn test_upload_dir_batch_errors() {
+    let name = format!("owo-rs-upload-dir-errors-{}", process::id());
+    let dir = env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("dir err");
+    fs::write(dir.join("a.txt"), b"a").expect("write err");
+    fs::write(dir.join("b.txt"), b"b").expect("write err");
+
+    // A rejected batch is not sent again file by file.
+    let server = MockServer::start("401 Unauthorized", r#"{"success":false}"#);
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    let results = client.upload_dir(&dir).expect("dir err");
+    assert_eq!(results.len(), 2);
+
+    for (_, result) in &results {
+        match *result {
+            Err(Error::Batch(ref inner)) => assert!(inner.is_invalid_key()),
+            ref other => panic!("expected batch error: {:?}", other),
+        }
+    }
+
+    server.request();
+
+    // A batch which is too large is sent again file by file.
+    let server = MockServer::start("413 Payload Too Large", "too large");
+    let client = OwoReqwestClientBuilder::new("key")
+        .base_url(server.url.clone())
+        .build()
+        .expect("client err");
+
+    let results = client.upload_dir(&dir).expect("dir err");
+    fs::remove_dir_all(&dir).expect("cleanup err");
+
+    for (_, result) in &results {
+        match *result {
+            Err(Error::PayloadTooLarge { .. }) => {},
+            ref other => panic!("expected payload too large: {:?}", other),
+        }
+    }
+
+    let requests = (0..3).map(|_| server.request().body.len());
+    let lens = requests.collect::<Vec<_>>();
+    assert!(lens[0] > lens[1] && lens[0] > lens[2]);
 }
@@ -0,0 +1,18 @@
+#![cfg(feature = "reqwest")]
+
+extern crate owo;
+
+use owo::model::{FileUploadResponse, UploadedFile};
+use owo::{Config, Error, OwoReqwestClient, ServiceLimits};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_send_sync() {
+    assert_send_sync::<Config>();
+    assert_send_sync::<Error>();
+    assert_send_sync::<FileUploadResponse>();
+    assert_send_sync::<OwoReqwestClient>();
+    assert_send_sync::<ServiceLimits>();
+    assert_send_sync::<UploadedFile>();
+}